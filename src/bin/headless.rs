@@ -0,0 +1,30 @@
+#![feature(coverage_attribute)]
+
+use rustrail::{
+    headless::{HeadlessSimulation, Profile},
+    landscape::{load_or_parse, HeightMap},
+    scenario::ScenarioData,
+};
+
+#[coverage(off)]
+fn main() {
+    let args: Vec<String> = std::env::args().collect();
+    let [_, scenario_file, profile_file] = args.as_slice() else {
+        eprintln!("usage: headless <scenario.toml> <profile.toml>");
+        std::process::exit(1);
+    };
+
+    let scenario_data = ScenarioData::load_from_file(scenario_file);
+    let profile = Profile::load_from_file(profile_file);
+
+    let data = load_or_parse(&scenario_data.map.osm_data);
+    // without a height map the track is considered flat
+    let height_map = std::path::Path::new(&scenario_data.map.height_map)
+        .exists()
+        .then(|| HeightMap::load_from_file(&scenario_data.map.height_map));
+
+    println!("{}", scenario_data.info.name);
+
+    let mut simulation = HeadlessSimulation::new(scenario_data, profile, data, height_map);
+    println!("{}", simulation.run());
+}
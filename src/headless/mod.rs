@@ -0,0 +1,242 @@
+#[cfg(test)]
+mod tests;
+
+use crate::{
+    landscape::{HeightMap, OSMData, SwitchPositions},
+    scenario::ScenarioData,
+    train::{
+        BrakeLever, BrakeValvePosition, Dimension, Distance, EnergyMeter, EngineBundle, Overspeed,
+        OverspeedViolation, PhysicsSet, PreviousTrackLocation, Sifa, Speed, ThrottleLever,
+        TrackLocation, TrainBundle, TrainComponent, TrainComposition, TrainPhysicsPlugin,
+        WagonBundle, STANDSTILL,
+    },
+};
+use bevy::{
+    prelude::*,
+    time::{TimePlugin, TimeUpdateStrategy},
+};
+use serde::Deserialize;
+
+// lever positions applied once the train has travelled a given distance
+#[derive(Default, Debug, Deserialize)]
+pub struct ProfileStep {
+    // m
+    pub from: f32,
+    #[serde(default)]
+    pub throttle: f32,
+    #[serde(default)]
+    pub brake: BrakeValvePosition,
+    #[serde(default)]
+    pub dynamic_brake: f32,
+}
+
+#[derive(Default, Debug, Deserialize, Resource)]
+pub struct Profile {
+    // model files of the consist, engine first
+    pub engine: String,
+    #[serde(default)]
+    pub wagons: Vec<String>,
+    // share of the wagons' payload capacity, empty by default
+    #[serde(default)]
+    pub load: f32,
+    // s, the run ends at the latest after this time
+    pub max_time: f32,
+    pub steps: Vec<ProfileStep>,
+}
+
+impl Profile {
+    pub fn load_from_file(file_name: &str) -> Self {
+        let data = std::fs::read_to_string(file_name).expect("file to be readable");
+
+        toml::from_str(&data).expect("profile to be valid")
+    }
+
+    pub fn step_at(&self, distance: f32) -> Option<&ProfileStep> {
+        self.steps.iter().rev().find(|step| step.from <= distance)
+    }
+
+    fn is_last_step(&self, distance: f32) -> bool {
+        self.steps.last().is_some_and(|step| step.from <= distance)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct RunResult {
+    // s
+    pub time: f32,
+    // m
+    pub distance: f32,
+    pub energy: EnergyMeter,
+    pub overspeed: Vec<OverspeedViolation>,
+}
+
+impl std::fmt::Display for RunResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "run time: {:.1} s", self.time)?;
+        writeln!(f, "distance: {:.3} km", self.distance / 1000.0)?;
+        writeln!(f, "traction energy: {:.2} kWh", self.energy.traction)?;
+        writeln!(f, "drawn energy: {:.2} kWh", self.energy.drawn)?;
+        writeln!(f, "regenerated energy: {:.2} kWh", self.energy.regenerated)?;
+        writeln!(f, "braking losses: {:.2} kWh", self.energy.braking_losses())?;
+        writeln!(f, "resistance losses: {:.2} kWh", self.energy.resistance)?;
+        writeln!(f, "diesel: {:.1} l", self.energy.diesel)?;
+        writeln!(f, "coal: {:.1} kg", self.energy.coal)?;
+        write!(
+            f,
+            "overspeed violations: {} ({} with penalty brake)",
+            self.overspeed.len(),
+            self.overspeed
+                .iter()
+                .filter(|violation| violation.penalty)
+                .count()
+        )
+    }
+}
+
+fn apply_profile(
+    profile: Res<Profile>,
+    trains: Query<(&Distance, &TrainComposition)>,
+    mut engines: Query<(&mut ThrottleLever, &mut BrakeLever, &mut Sifa)>,
+) {
+    for (distance, composition) in trains.iter() {
+        let Some(step) = profile.step_at(distance.0.abs()) else {
+            continue;
+        };
+
+        for entity in composition.entities() {
+            if let Ok((mut throttle_lever, mut brake_lever, mut sifa)) = engines.get_mut(entity) {
+                // the profile never dozes off
+                sifa.acknowledge = true;
+                throttle_lever.percentage = step.throttle;
+                brake_lever.valve = step.brake;
+                brake_lever.dynamic_brake = step.dynamic_brake;
+            }
+        }
+    }
+}
+
+// a simulation without window or rendering. every update is exactly one
+// physics tick
+pub struct HeadlessSimulation {
+    app: App,
+    train: Entity,
+    // whether the train got moving, it only finishes once it stands again
+    moving: bool,
+}
+
+impl HeadlessSimulation {
+    pub fn new(
+        scenario_data: ScenarioData,
+        profile: Profile,
+        data: OSMData,
+        height_map: Option<HeightMap>,
+    ) -> Self {
+        let mut app = App::new();
+        app.add_plugins((TimePlugin, TrainPhysicsPlugin))
+            .add_systems(FixedUpdate, apply_profile.before(PhysicsSet::Aggregate));
+
+        let timestep = app.world().resource::<Time<Fixed>>().timestep();
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(timestep));
+
+        let mut components = vec![TrainComponent::Engine(
+            app.world_mut()
+                .spawn(EngineBundle::from_file(&profile.engine))
+                .id(),
+        )];
+        for wagon in profile.wagons.iter() {
+            components.push(TrainComponent::Wagon(
+                app.world_mut()
+                    .spawn(WagonBundle::from_file(wagon).with_load(profile.load))
+                    .id(),
+            ));
+        }
+
+        let train = app
+            .world_mut()
+            .spawn(TrainBundle::new("Headless", components))
+            .id();
+
+        let entities = app
+            .world()
+            .get::<TrainComposition>(train)
+            .unwrap()
+            .entities();
+        let lengths: Vec<f32> = entities
+            .iter()
+            .map(|entity| app.world().get::<Dimension>(*entity).unwrap().length)
+            .collect();
+
+        let start = TrackLocation::scenario_start(&data, &scenario_data)
+            .expect("the first stop to be on the track");
+        let locations =
+            start.consist_locations(&data, app.world().resource::<SwitchPositions>(), &lengths);
+
+        app.world_mut().entity_mut(train).insert(start);
+        for (entity, location) in entities.into_iter().zip(locations) {
+            app.world_mut()
+                .entity_mut(entity)
+                .insert((PreviousTrackLocation(location.clone()), location));
+        }
+
+        app.insert_resource(scenario_data.info.rail_condition)
+            .insert_resource(scenario_data.info.vigilance)
+            .insert_resource(data)
+            .insert_resource(scenario_data)
+            .insert_resource(profile);
+
+        if let Some(height_map) = height_map {
+            app.insert_resource(height_map);
+        }
+
+        app.finish();
+        app.cleanup();
+
+        Self {
+            app,
+            train,
+            moving: false,
+        }
+    }
+
+    fn time(&self) -> f32 {
+        self.app.world().resource::<Time<Fixed>>().elapsed_seconds()
+    }
+
+    fn speed(&self) -> f32 {
+        self.app.world().get::<Speed>(self.train).unwrap().0.abs()
+    }
+
+    fn is_finished(&self) -> bool {
+        let world = self.app.world();
+        let profile = world.resource::<Profile>();
+        let distance = world.get::<Distance>(self.train).unwrap().0.abs();
+
+        if self.time() >= profile.max_time {
+            return true;
+        }
+
+        profile.is_last_step(distance) && self.moving && self.speed() < STANDSTILL
+    }
+
+    // runs until the train stands still after the last step or the maximum
+    // time is reached
+    pub fn run(&mut self) -> RunResult {
+        while !self.is_finished() {
+            self.app.update();
+            self.moving |= self.speed() >= STANDSTILL;
+        }
+
+        let world = self.app.world();
+
+        RunResult {
+            time: self.time(),
+            distance: world.get::<Distance>(self.train).unwrap().0.abs(),
+            energy: world.get::<EnergyMeter>(self.train).unwrap().clone(),
+            overspeed: world
+                .get::<Overspeed>(self.train)
+                .unwrap()
+                .violations
+                .clone(),
+        }
+    }
+}
@@ -0,0 +1,153 @@
+use super::*;
+use crate::{
+    landscape::{CoordinatePoint, Path, RailTags},
+    scenario::ScenarioStop,
+    train::Direction,
+};
+use coverage_helper::test;
+use std::collections::HashMap;
+
+#[coverage(off)]
+fn electrified() -> RailTags {
+    RailTags {
+        electrified: Some("contact_line".to_string()),
+        ..default()
+    }
+}
+
+// two rails under a contact line
+#[coverage(off)]
+fn gen_data() -> OSMData {
+    let mut rails = HashMap::default();
+    rails.insert(
+        (0, 1),
+        Path {
+            start_id: 0,
+            end_id: 1,
+            start_coords: CoordinatePoint(-1000.0, 0.0),
+            end_coords: CoordinatePoint(0.0, 0.0),
+            forward_connections: vec![((1, 2), Direction::Forward)],
+            tags: electrified(),
+            ..default()
+        },
+    );
+    rails.insert(
+        (1, 2),
+        Path {
+            start_id: 1,
+            end_id: 2,
+            start_coords: CoordinatePoint(0.0, 0.0),
+            end_coords: CoordinatePoint(10000.0, 0.0),
+            backward_connections: vec![((0, 1), Direction::Backward)],
+            tags: electrified(),
+            ..default()
+        },
+    );
+    OSMData { rails, ..default() }
+}
+
+#[coverage(off)]
+fn gen_scenario() -> ScenarioData {
+    ScenarioData {
+        stops: vec![ScenarioStop {
+            node_id: 1,
+            ..default()
+        }],
+        ..default()
+    }
+}
+
+#[test]
+fn loading_profile() {
+    let profile = Profile::load_from_file("assets/profiles/rb35.toml");
+
+    assert_eq!(profile.engine, "assets/models/BR111.toml");
+    assert_eq!(profile.wagons.len(), 3);
+    assert_eq!(profile.steps.len(), 3);
+    assert_eq!(profile.steps[2].brake, BrakeValvePosition::Service(3));
+}
+
+#[test]
+fn profile_steps() {
+    let profile = Profile {
+        steps: vec![
+            ProfileStep {
+                from: 0.0,
+                throttle: 1.0,
+                ..default()
+            },
+            ProfileStep {
+                from: 100.0,
+                brake: BrakeValvePosition::Emergency,
+                ..default()
+            },
+        ],
+        ..default()
+    };
+
+    assert_eq!(profile.step_at(50.0).unwrap().throttle, 1.0);
+    assert_eq!(
+        profile.step_at(150.0).unwrap().brake,
+        BrakeValvePosition::Emergency
+    );
+    assert!(!profile.is_last_step(50.0));
+    assert!(profile.is_last_step(100.0));
+}
+
+#[test]
+fn run_to_stop() {
+    let profile = Profile {
+        engine: "assets/models/BR147.toml".to_string(),
+        wagons: vec!["assets/models/nwagen.toml".to_string()],
+        load: 0.0,
+        max_time: 600.0,
+        steps: vec![
+            ProfileStep {
+                from: 0.0,
+                throttle: 1.0,
+                ..default()
+            },
+            ProfileStep {
+                from: 500.0,
+                brake: BrakeValvePosition::Service(5),
+                dynamic_brake: 1.0,
+                ..default()
+            },
+        ],
+    };
+
+    let mut simulation = HeadlessSimulation::new(gen_scenario(), profile, gen_data(), None);
+    let result = simulation.run();
+
+    assert!(result.time > 0.0);
+    assert!(result.time < 600.0);
+    assert!(result.distance > 500.0);
+    assert!(result.energy.traction > 0.0);
+    assert!(result.energy.drawn > result.energy.traction);
+    assert!(result.energy.regenerated > 0.0);
+    assert!(result.energy.braking > result.energy.regenerated);
+    assert!(result.energy.resistance > 0.0);
+    assert!(result.to_string().contains("distance: "));
+}
+
+#[test]
+fn stops_at_max_time() {
+    let profile = Profile {
+        engine: "assets/models/BR111.toml".to_string(),
+        max_time: 10.0,
+        steps: vec![ProfileStep {
+            from: 0.0,
+            throttle: 0.5,
+            ..default()
+        }],
+        ..default()
+    };
+
+    let mut simulation = HeadlessSimulation::new(gen_scenario(), profile, gen_data(), None);
+    let result = simulation.run();
+
+    assert!(result.time >= 10.0);
+    assert!(result.time < 10.1);
+    assert!(result.distance > 0.0);
+    assert_eq!(result.energy.regenerated, 0.0);
+}
@@ -1,4 +1,7 @@
-use super::{AssetData, BALLAST_HEIGHT, BALLAST_WIDTH, RAIL_HEIGHT, RAIL_WIDTH};
+use super::{
+    spawn_signal_models::{SIGNAL_HEAD_SIZE, SIGNAL_POLE_HEIGHT},
+    AssetData, BALLAST_HEIGHT, BALLAST_WIDTH, RAIL_HEIGHT, RAIL_WIDTH,
+};
 use bevy::{
     prelude::*,
     render::texture::{
@@ -27,6 +30,15 @@ fn load_repeating_asset(asset_server: &AssetServer, file_name: &str) -> Handle<I
     )
 }
 
+#[coverage(off)]
+fn lamp(color: Color) -> StandardMaterial {
+    StandardMaterial {
+        base_color: color,
+        emissive: color.to_linear() * 4.0,
+        ..default()
+    }
+}
+
 #[coverage(off)]
 pub fn system(
     mut commands: Commands,
@@ -53,5 +65,15 @@ pub fn system(
             &asset_server,
             "textures/commercial.png",
         )),
+        signal_pole_mesh: meshes.add(Cylinder::new(0.08, SIGNAL_POLE_HEIGHT)),
+        signal_head_mesh: meshes.add(Cuboid::new(
+            SIGNAL_HEAD_SIZE,
+            SIGNAL_HEAD_SIZE * 1.6,
+            SIGNAL_HEAD_SIZE / 2.0,
+        )),
+        signal_pole_material: materials.add(Color::srgb(0.3, 0.3, 0.3)),
+        signal_stop_material: materials.add(lamp(Color::srgb(0.9, 0.05, 0.05))),
+        signal_caution_material: materials.add(lamp(Color::srgb(0.95, 0.75, 0.05))),
+        signal_clear_material: materials.add(lamp(Color::srgb(0.05, 0.8, 0.2))),
     });
 }
@@ -1,42 +1,73 @@
-#[cfg(test)]
+#[cfg(all(test, feature = "graphics"))]
 mod tests;
 
 mod coordinate_point;
+#[cfg(feature = "graphics")]
 mod despawn_landscapes;
 mod height_map;
+#[cfg(feature = "graphics")]
 mod init_height_map;
+#[cfg(feature = "graphics")]
 mod load_asset_data;
 mod open_street_map;
+mod route;
+mod signals;
+#[cfg(feature = "graphics")]
 mod spawn_areas;
+#[cfg(feature = "graphics")]
 mod spawn_buildings;
+#[cfg(feature = "graphics")]
 mod spawn_landscape_mesh;
+#[cfg(feature = "graphics")]
 mod spawn_landscapes;
+#[cfg(feature = "graphics")]
 mod spawn_rails;
+#[cfg(feature = "graphics")]
+mod spawn_signal_models;
+mod stops;
+mod switches;
+mod track_magnets;
 
 use bevy::prelude::*;
 pub use coordinate_point::CoordinatePoint;
 pub use height_map::HeightMap;
 #[cfg(test)]
 pub use open_street_map::Path;
-pub use open_street_map::{OSMData, PathId};
+pub use open_street_map::{
+    load_or_parse, OSMData, PathId, RailKind, RailTags, SignalData, SignalKind,
+};
+pub use route::{Route, StopProblem};
+pub use signals::{
+    index_signals, movement_authority, Aspect, AuthorityEnd, BlockEnd, Signal, SignalIndex,
+    SignalPlugin,
+};
+pub use stops::{distance_to_window, StopPlugin, StopWindow, StopWindows};
+pub use switches::{Switch, SwitchLeg, SwitchPlugin, SwitchPositions, SwitchTrailed};
+pub use track_magnets::{passed_magnets, MagnetFrequency, MagnetSource, TrackMagnet};
 
+#[cfg(feature = "graphics")]
 use crate::scenario::ScenarioData;
 
+#[cfg(feature = "graphics")]
 const TRIANGLE_SIZE: i32 = 10;
 const LANDSCAPE_SIZE: i32 = 1000;
+#[cfg(feature = "graphics")]
 const HALF_LANDSCAPE_SIZE: i32 = LANDSCAPE_SIZE / 2;
 // lifetime of a landscape. if it is not renewed, it will despawn
 const DEFAULT_TTL: f32 = 30.0;
+#[cfg(feature = "graphics")]
 const SPAWN_RADIUS: i32 = 5;
 
 pub const BALLAST_WIDTH: f32 = RAIL_DISTANCE + 1.75;
 pub const BALLAST_HEIGHT: f32 = 0.4;
+#[cfg(feature = "graphics")]
 const MAX_RAIL_SEGMENT_LENGTH: f64 = 3.0;
 
 pub const RAIL_HEIGHT: f32 = 0.2;
 const RAIL_DISTANCE: f32 = 1.435;
 pub const RAIL_WIDTH: f32 = 0.1;
 
+#[cfg(feature = "graphics")]
 #[derive(Resource, Default)]
 pub struct AssetData {
     rail_mesh: Handle<Mesh>,
@@ -49,6 +80,12 @@ pub struct AssetData {
     office_material: Handle<StandardMaterial>,
     industrial_material: Handle<StandardMaterial>,
     commercial_material: Handle<StandardMaterial>,
+    signal_pole_mesh: Handle<Mesh>,
+    signal_head_mesh: Handle<Mesh>,
+    signal_pole_material: Handle<StandardMaterial>,
+    signal_stop_material: Handle<StandardMaterial>,
+    signal_caution_material: Handle<StandardMaterial>,
+    signal_clear_material: Handle<StandardMaterial>,
 }
 
 #[derive(Component, Clone)]
@@ -69,8 +106,10 @@ impl Default for Landscape {
 #[derive(Resource, Default, Clone)]
 pub struct OriginOffset(pub CoordinatePoint);
 
+#[cfg(feature = "graphics")]
 pub struct LandscapePlugin;
 
+#[cfg(feature = "graphics")]
 impl Plugin for LandscapePlugin {
     fn build(&self, app: &mut App) {
         app.add_systems(Startup, load_asset_data::system)
@@ -91,6 +130,8 @@ impl Plugin for LandscapePlugin {
                     spawn_rails::system,
                     spawn_buildings::system,
                     spawn_areas::system,
+                    spawn_signal_models::system,
+                    spawn_signal_models::update_lamps,
                 )
                     .run_if(resource_exists::<HeightMap>.and_then(resource_exists::<OSMData>)),
             );
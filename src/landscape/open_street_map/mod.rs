@@ -1,23 +1,34 @@
 mod osm_data;
 mod path;
 
+#[cfg(feature = "graphics")]
 use crate::scenario::ScenarioData;
+#[cfg(feature = "graphics")]
 use bevy::prelude::*;
-pub use osm_data::{AreaType, BuildingType, OSMData};
-pub use path::{Path, PathId};
+#[cfg(feature = "graphics")]
+pub use osm_data::AreaType;
+pub use osm_data::{BuildingType, OSMData, SignalData, SignalKind};
+#[cfg(test)]
+pub use osm_data::{BuildingData, SectionData};
+pub use path::{Path, PathId, RailKind, RailTags};
 
+// prefers the parsed cache next to the OpenStreetMap file
 #[coverage(off)]
-pub fn load_data(mut commands: Commands, scenario: Res<ScenarioData>) {
-    let file_name = &scenario.map.osm_data;
+pub fn load_or_parse(file_name: &str) -> OSMData {
     let parsed_file_name = format!("{}.bin", file_name);
 
-    let data = if let Ok(data) = OSMData::load_from_file(&parsed_file_name) {
+    if let Ok(data) = OSMData::load_from_file(&parsed_file_name) {
         // TODO: fail fallback if load fails
         data
     } else {
         let data = OSMData::parse_file(file_name);
         data.save_to_file(&parsed_file_name);
         data
-    };
-    commands.insert_resource(data);
+    }
+}
+
+#[cfg(feature = "graphics")]
+#[coverage(off)]
+pub fn load_data(mut commands: Commands, scenario: Res<ScenarioData>) {
+    commands.insert_resource(load_or_parse(&scenario.map.osm_data));
 }
@@ -1,7 +1,57 @@
-use osmpbfreader::{OsmObj, Way};
+use super::{Path, SignalKind};
+use crate::{
+    landscape::{CoordinatePoint, RailKind, RailTags},
+    train::Direction,
+};
+use osmpbfreader::{Node, OsmObj, Tags, Way};
+
+pub fn rail_kind(obj: &Way) -> Option<RailKind> {
+    match obj.tags.get("railway")?.as_str() {
+        "rail" => Some(RailKind::Rail),
+        "narrow_gauge" => Some(RailKind::NarrowGauge),
+        "light_rail" => Some(RailKind::LightRail),
+        _ => None,
+    }
+}
 
 pub fn is_rail(obj: &Way) -> bool {
-    obj.tags.contains("railway", "rail")
+    rail_kind(obj).is_some()
+}
+
+// the first of several values separated by semicolons
+fn first_value<'a>(tags: &'a Tags, key: &str) -> Option<&'a str> {
+    tags.get(key)?.split(';').next().map(str::trim)
+}
+
+// km/h, values like "none" or "signals" are no limit
+pub fn parse_max_speed(value: &str) -> Option<f32> {
+    match value.strip_suffix("mph") {
+        Some(mph) => mph.trim().parse::<f32>().ok().map(|mph| mph * 1.609_344),
+        None => value.trim().parse::<f32>().ok(),
+    }
+}
+
+pub fn rail_tags(obj: &Way) -> RailTags {
+    let tags = &obj.tags;
+    let string = |key: &str| tags.get(key).map(|value| value.to_string());
+
+    RailTags {
+        kind: rail_kind(obj).unwrap_or_default(),
+        max_speed: first_value(tags, "maxspeed").and_then(parse_max_speed),
+        electrified: string("electrified"),
+        voltage: first_value(tags, "voltage").and_then(|value| value.parse().ok()),
+        frequency: first_value(tags, "frequency").and_then(|value| value.parse().ok()),
+        gauge: first_value(tags, "gauge").and_then(|value| value.parse().ok()),
+        usage: string("usage"),
+        service: string("service"),
+        tunnel: tags
+            .get("tunnel")
+            .is_some_and(|value| value.as_str() != "no"),
+        bridge: tags
+            .get("bridge")
+            .is_some_and(|value| value.as_str() != "no"),
+        layer: tags.get("layer").and_then(|value| value.parse().ok()),
+    }
 }
 
 pub fn is_wood(obj: &Way) -> bool {
@@ -36,14 +86,79 @@ pub fn is_railway_platform(obj: &Way) -> bool {
     obj.tags.contains("railway", "platform")
 }
 
+pub fn signal_kind(obj: &Node) -> Option<SignalKind> {
+    if !obj.tags.contains("railway", "signal") {
+        return None;
+    }
+
+    if obj.tags.contains_key("railway:signal:combined") {
+        Some(SignalKind::Combined)
+    } else if obj.tags.contains_key("railway:signal:main") {
+        Some(SignalKind::Main)
+    } else if obj.tags.contains_key("railway:signal:distant") {
+        Some(SignalKind::Distant)
+    } else {
+        None
+    }
+}
+
+// relative to the direction of the way
+pub fn signal_directions(obj: &Node) -> Vec<Direction> {
+    match obj
+        .tags
+        .get("railway:signal:direction")
+        .map(|direction| direction.as_str())
+    {
+        Some("backward") => vec![Direction::Backward],
+        Some("both") => vec![Direction::Forward, Direction::Backward],
+        _ => vec![Direction::Forward],
+    }
+}
+
 pub fn is_relevant_object(obj: &OsmObj) -> bool {
-    if let OsmObj::Way(obj) = obj {
-        return is_rail(obj)
-            || is_building(obj)
-            || is_railway_platform(obj)
-            || is_wood(obj)
-            || is_water(obj);
+    match obj {
+        OsmObj::Way(obj) => {
+            is_rail(obj)
+                || is_building(obj)
+                || is_railway_platform(obj)
+                || is_wood(obj)
+                || is_water(obj)
+        }
+        OsmObj::Node(obj) => signal_kind(obj).is_some(),
+        _ => false,
+    }
+}
+
+// m along the rail to the point closest to `point` and the distance between
+// the two
+pub fn project_onto(rail: &Path, point: CoordinatePoint) -> (f64, f64) {
+    let direction = rail.end_coords - rail.start_coords;
+    let length = rail.length();
+
+    if length == 0.0 {
+        return (0.0, (point - rail.start_coords).length());
+    }
+
+    let relative = point - rail.start_coords;
+    let offset =
+        ((relative.0 * direction.0 + relative.1 * direction.1) / length).clamp(0.0, length);
+    let closest = rail.start_coords + direction * (offset / length);
+
+    (offset, (point - closest).length())
+}
+
+// curvature (1/radius) of the circle through three points
+pub fn curvature(a: CoordinatePoint, b: CoordinatePoint, c: CoordinatePoint) -> f64 {
+    let ab = (b - a).length();
+    let bc = (c - b).length();
+    let ca = (a - c).length();
+
+    let cross = (b.0 - a.0) * (c.1 - a.1) - (b.1 - a.1) * (c.0 - a.0);
+    let denominator = ab * bc * ca;
+
+    if denominator == 0.0 {
+        return 0.0;
     }
 
-    false
+    2.0 * cross.abs() / denominator
 }
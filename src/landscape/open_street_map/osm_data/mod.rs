@@ -18,6 +18,7 @@ use std::{collections::HashMap, error::Error};
 pub struct OSMData {
     pub rails: HashMap<PathId, Path>,
     pub sections: HashMap<(i64, i64), SectionData>,
+    pub signals: Vec<SignalData>,
 }
 
 #[derive(Default, Debug, Deserialize, Serialize, Clone)]
@@ -25,6 +26,38 @@ pub struct SectionData {
     pub buildings: Vec<BuildingData>,
     pub areas: Vec<AreaData>,
     pub rails: Vec<PathId>,
+    // indices into the signals
+    pub signals: Vec<usize>,
+}
+
+#[derive(Debug, Default, PartialEq, Deserialize, Serialize, Clone, Copy)]
+pub enum SignalKind {
+    // protects the block behind it
+    #[default]
+    Main,
+    // announces the aspect of the next main signal
+    Distant,
+    // main and distant signal in one
+    Combined,
+}
+
+impl SignalKind {
+    pub fn is_main(&self) -> bool {
+        matches!(self, Self::Main | Self::Combined)
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct SignalData {
+    pub node_id: i64,
+    pub kind: SignalKind,
+    pub coords: CoordinatePoint,
+    // the nearest rail, the signal applies to trains travelling along it in
+    // `direction`
+    pub rail: PathId,
+    // m from the start of the rail
+    pub distance: f64,
+    pub direction: Direction,
 }
 
 #[derive(Default, Debug, Deserialize, Serialize, Clone)]
@@ -73,6 +106,10 @@ impl Default for AreaType {
     }
 }
 
+// bump whenever the parsed data changes, so that older cache files get
+// parsed again
+const CACHE_VERSION: u32 = 3;
+
 impl OSMData {
     pub fn load_from_file(file_name: &str) -> Result<Self, Box<dyn Error>> {
         let data = std::fs::read(file_name)?;
@@ -81,12 +118,16 @@ impl OSMData {
         log::info!("Read parsed data file");
 
         // TODO: fail fallback if load fails
-        let data: OSMData = bincode::deserialize(&data)?;
+        let (version, data): (u32, OSMData) = bincode::deserialize(&data)?;
+        if version != CACHE_VERSION {
+            return Err(format!("outdated cache version {}", version).into());
+        }
+
         Ok(data)
     }
 
     pub fn save_to_file(&self, file_name: &str) {
-        let serialized_data = bincode::serialize(&self).unwrap();
+        let serialized_data = bincode::serialize(&(CACHE_VERSION, self)).unwrap();
         std::fs::write(file_name, serialized_data).expect("Unable to write file");
 
         #[cfg(not(coverage))]
@@ -124,8 +165,30 @@ impl OSMData {
         #[cfg(not(coverage))]
         log::info!("extracted data points, parsing");
 
+        // signal nodes show up in the tree of every way they are part of
+        let mut signals = HashMap::new();
+
         for obj_tree in objs.into_iter() {
             for (_id, obj) in obj_tree.iter() {
+                if let osmpbfreader::OsmObj::Node(node) = obj {
+                    if let Some(kind) = signal_kind(node) {
+                        for direction in signal_directions(node) {
+                            signals.insert(
+                                (node.id.0, direction == Direction::Forward),
+                                SignalData {
+                                    node_id: node.id.0,
+                                    kind,
+                                    coords: node_to_coordinates(node),
+                                    // attached to the nearest rail later on
+                                    rail: (0, 0),
+                                    distance: 0.0,
+                                    direction,
+                                },
+                            );
+                        }
+                    }
+                }
+
                 if let osmpbfreader::OsmObj::Way(way) = obj {
                     let nodes: Vec<&osmpbfreader::Node> = way
                         .nodes
@@ -228,6 +291,7 @@ impl OSMData {
                     }
 
                     if is_rail(way) {
+                        let tags = rail_tags(way);
                         let mut node_iter = coordinates.iter();
                         let (mut last_node_id, mut last_node) = node_iter.next().unwrap();
 
@@ -251,6 +315,7 @@ impl OSMData {
                                 end_id: *next_node_id,
                                 start_coords,
                                 end_coords,
+                                tags: tags.clone(),
                                 ..default()
                             };
 
@@ -270,9 +335,47 @@ impl OSMData {
         log::info!("{} data points extracted", count);
 
         data.generate_path_connections();
+        data.generate_path_curvatures();
+        let mut signals: Vec<SignalData> = signals.into_values().collect();
+        signals.sort_by_key(|signal| (signal.node_id, signal.direction == Direction::Backward));
+        data.attach_signals(signals);
         data
     }
 
+    // puts every signal onto the nearest rail of its own or a neighbouring
+    // section, signals without any rail close by are dropped
+    fn attach_signals(&mut self, signals: Vec<SignalData>) {
+        for mut signal in signals {
+            let (x, y) = signal.coords.sector_coordinates();
+
+            let nearest = (x - 1..=x + 1)
+                .flat_map(|x| (y - 1..=y + 1).map(move |y| (x, y)))
+                .filter_map(|sector| self.sections.get(&sector))
+                .flat_map(|section| section.rails.iter())
+                .filter_map(|id| self.rails.get(id))
+                .map(|rail| {
+                    let (offset, distance) = project_onto(rail, signal.coords);
+                    (rail.id(), offset, distance)
+                })
+                .min_by(|a, b| a.2.total_cmp(&b.2));
+
+            let Some((rail, offset, _distance)) = nearest else {
+                continue;
+            };
+
+            signal.rail = rail;
+            signal.distance = offset;
+
+            let index = self.signals.len();
+            self.sections
+                .entry(signal.coords.sector_coordinates())
+                .or_default()
+                .signals
+                .push(index);
+            self.signals.push(signal);
+        }
+    }
+
     fn generate_path_connections(&mut self) {
         #[cfg(not(coverage))]
         log::info!("generating path connections");
@@ -360,4 +463,50 @@ impl OSMData {
         #[cfg(not(coverage))]
         log::info!("{} path connections created", count);
     }
+
+    // curvature of every path is derived from the circle through its own
+    // end points and the far end of the (first) connected path on either side
+    fn generate_path_curvatures(&mut self) {
+        let curvatures: Vec<(PathId, f64)> = self
+            .rails
+            .values()
+            .map(|rail| {
+                let mut curvatures = vec![];
+
+                for (shared_id, connections) in [
+                    (rail.end_id, &rail.forward_connections),
+                    (rail.start_id, &rail.backward_connections),
+                ] {
+                    let Some(other) = connections
+                        .first()
+                        .and_then(|(id, _direction)| self.rails.get(id))
+                    else {
+                        continue;
+                    };
+
+                    let other_coords = if other.start_id == shared_id {
+                        other.end_coords
+                    } else {
+                        other.start_coords
+                    };
+
+                    curvatures.push(curvature(rail.start_coords, rail.end_coords, other_coords));
+                }
+
+                let curvature = if curvatures.is_empty() {
+                    0.0
+                } else {
+                    curvatures.iter().sum::<f64>() / curvatures.len() as f64
+                };
+
+                (rail.id(), curvature)
+            })
+            .collect();
+
+        for (id, curvature) in curvatures {
+            if let Some(rail) = self.rails.get_mut(&id) {
+                rail.curvature = curvature;
+            }
+        }
+    }
 }
@@ -1,4 +1,5 @@
 use super::*;
+use crate::landscape::{RailKind, RailTags};
 use coverage_helper::test;
 use std::{fs::remove_file, path::Path};
 
@@ -75,3 +76,289 @@ fn test_opposite_travel_direction() {
         assert!(path.forward_connections.len() + path.backward_connections.len() > 0);
     }
 }
+
+#[test]
+fn curvature_of_points() {
+    let straight = curvature(
+        CoordinatePoint(0.0, 0.0),
+        CoordinatePoint(10.0, 0.0),
+        CoordinatePoint(20.0, 0.0),
+    );
+    assert_eq!(straight, 0.0);
+
+    let radius = 100.0;
+    let point = |angle: f64| CoordinatePoint(radius * angle.cos(), radius * angle.sin());
+    let circle = curvature(point(0.0), point(0.1), point(0.3));
+    assert!((1.0 / circle - radius).abs() < 1e-6);
+
+    let same = curvature(
+        CoordinatePoint(0.0, 0.0),
+        CoordinatePoint(0.0, 0.0),
+        CoordinatePoint(20.0, 0.0),
+    );
+    assert_eq!(same, 0.0);
+}
+
+#[test]
+fn path_curvatures() {
+    let mut data = OSMData::default();
+
+    for (start_id, end_id, start_coords, end_coords) in [
+        (0, 1, CoordinatePoint(0.0, 0.0), CoordinatePoint(100.0, 0.0)),
+        (
+            1,
+            2,
+            CoordinatePoint(100.0, 0.0),
+            CoordinatePoint(200.0, 10.0),
+        ),
+        (
+            2,
+            3,
+            CoordinatePoint(200.0, 10.0),
+            CoordinatePoint(300.0, 30.0),
+        ),
+    ] {
+        let rail = super::Path {
+            start_id,
+            end_id,
+            start_coords,
+            end_coords,
+            ..default()
+        };
+        data.rails.insert(rail.id(), rail);
+    }
+
+    data.generate_path_connections();
+    data.generate_path_curvatures();
+
+    for rail in data.rails.values() {
+        assert!(rail.curvature > 0.0);
+        assert!(rail.curve_radius().is_finite());
+    }
+
+    // the middle segment sees curves on both sides and is averaged
+    let middle = data.rails.get(&(1, 2)).unwrap();
+    let first = data.rails.get(&(0, 1)).unwrap();
+    let last = data.rails.get(&(2, 3)).unwrap();
+    assert!((middle.curvature - (first.curvature + last.curvature) / 2.0).abs() < 1e-12);
+}
+
+#[coverage(off)]
+fn way(tags: &[(&str, &str)]) -> osmpbfreader::Way {
+    let mut way_tags = osmpbfreader::Tags::new();
+    for (key, value) in tags {
+        way_tags.insert((*key).into(), (*value).into());
+    }
+
+    osmpbfreader::Way {
+        id: osmpbfreader::WayId(1),
+        tags: way_tags,
+        nodes: vec![],
+    }
+}
+
+#[test]
+fn railway_kinds() {
+    assert_eq!(
+        rail_kind(&way(&[("railway", "rail")])),
+        Some(RailKind::Rail)
+    );
+    assert_eq!(
+        rail_kind(&way(&[("railway", "narrow_gauge")])),
+        Some(RailKind::NarrowGauge)
+    );
+    assert_eq!(
+        rail_kind(&way(&[("railway", "light_rail")])),
+        Some(RailKind::LightRail)
+    );
+    assert!(!is_rail(&way(&[("railway", "platform")])));
+    assert!(!is_rail(&way(&[])));
+}
+
+#[test]
+fn rail_tags_of_ways() {
+    let tags = rail_tags(&way(&[
+        ("railway", "rail"),
+        ("maxspeed", "160"),
+        ("electrified", "contact_line"),
+        ("voltage", "15000"),
+        ("frequency", "16.7"),
+        ("gauge", "1435;1000"),
+        ("usage", "main"),
+        ("tunnel", "yes"),
+        ("bridge", "no"),
+        ("layer", "-1"),
+    ]));
+
+    assert_eq!(
+        tags,
+        RailTags {
+            kind: RailKind::Rail,
+            max_speed: Some(160.0),
+            electrified: Some("contact_line".to_string()),
+            voltage: Some(15000),
+            frequency: Some(16.7),
+            gauge: Some(1435),
+            usage: Some("main".to_string()),
+            service: None,
+            tunnel: true,
+            bridge: false,
+            layer: Some(-1),
+        }
+    );
+    assert!(tags.is_electrified());
+
+    let tags = rail_tags(&way(&[
+        ("railway", "narrow_gauge"),
+        ("electrified", "no"),
+        ("service", "siding"),
+    ]));
+    assert!(!tags.is_electrified());
+    assert_eq!(tags.service, Some("siding".to_string()));
+    assert_eq!(tags.max_speed, None);
+    assert!(!RailTags::default().is_electrified());
+}
+
+#[test]
+fn max_speeds() {
+    assert_eq!(parse_max_speed("100"), Some(100.0));
+    assert!((parse_max_speed("50 mph").unwrap() - 80.47).abs() < 0.01);
+    assert_eq!(parse_max_speed("none"), None);
+}
+
+#[test]
+fn outdated_cache() {
+    let file_name = std::env::temp_dir().join("outdated.osm.pbf.bin");
+    let file_name = file_name.to_string_lossy().to_string();
+
+    std::fs::write(
+        &file_name,
+        bincode::serialize(&(CACHE_VERSION - 1, OSMData::default())).unwrap(),
+    )
+    .unwrap();
+    assert!(OSMData::load_from_file(&file_name).is_err());
+
+    OSMData::default().save_to_file(&file_name);
+    assert!(OSMData::load_from_file(&file_name).is_ok());
+
+    remove_file(file_name).unwrap();
+}
+
+#[coverage(off)]
+fn node(tags: &[(&str, &str)]) -> osmpbfreader::Node {
+    let mut node_tags = osmpbfreader::Tags::new();
+    for (key, value) in tags {
+        node_tags.insert((*key).into(), (*value).into());
+    }
+
+    osmpbfreader::Node {
+        id: osmpbfreader::NodeId(1),
+        tags: node_tags,
+        decimicro_lat: 0,
+        decimicro_lon: 0,
+    }
+}
+
+#[test]
+fn signal_nodes() {
+    let main = node(&[("railway", "signal"), ("railway:signal:main", "DE-ESO:hp")]);
+    assert_eq!(signal_kind(&main), Some(SignalKind::Main));
+    assert_eq!(signal_directions(&main), vec![Direction::Forward]);
+    assert!(is_relevant_object(&osmpbfreader::OsmObj::Node(main)));
+
+    let distant = node(&[
+        ("railway", "signal"),
+        ("railway:signal:distant", "DE-ESO:vr"),
+        ("railway:signal:direction", "backward"),
+    ]);
+    assert_eq!(signal_kind(&distant), Some(SignalKind::Distant));
+    assert_eq!(signal_directions(&distant), vec![Direction::Backward]);
+
+    let combined = node(&[
+        ("railway", "signal"),
+        ("railway:signal:combined", "DE-ESO:ks"),
+        ("railway:signal:direction", "both"),
+    ]);
+    assert_eq!(signal_kind(&combined), Some(SignalKind::Combined));
+    assert_eq!(
+        signal_directions(&combined),
+        vec![Direction::Forward, Direction::Backward]
+    );
+
+    // level crossing lights and the like are no train signals
+    let crossing = node(&[
+        ("railway", "signal"),
+        ("railway:signal:crossing", "DE-ESO:bü"),
+    ]);
+    assert_eq!(signal_kind(&crossing), None);
+    assert!(!is_relevant_object(&osmpbfreader::OsmObj::Node(crossing)));
+    assert_eq!(signal_kind(&node(&[])), None);
+}
+
+#[test]
+fn projection_onto_rails() {
+    let rail = crate::landscape::Path {
+        start_coords: CoordinatePoint(0.0, 0.0),
+        end_coords: CoordinatePoint(100.0, 0.0),
+        ..default()
+    };
+
+    assert_eq!(project_onto(&rail, CoordinatePoint(40.0, 3.0)), (40.0, 3.0));
+    assert_eq!(
+        project_onto(&rail, CoordinatePoint(-30.0, 40.0)),
+        (0.0, 50.0)
+    );
+    assert_eq!(
+        project_onto(&rail, CoordinatePoint(130.0, 0.0)),
+        (100.0, 30.0)
+    );
+
+    let point = crate::landscape::Path::default();
+    assert_eq!(project_onto(&point, CoordinatePoint(3.0, 4.0)), (0.0, 5.0));
+}
+
+#[test]
+fn signals_attach_to_the_nearest_rail() {
+    let origin = CoordinatePoint(1_000_000.0, 6_000_000.0);
+    let mut data = OSMData::default();
+
+    for (id, y) in [((0, 1), 0.0), ((2, 3), 10.0)] {
+        let rail = crate::landscape::Path {
+            start_id: id.0,
+            end_id: id.1,
+            start_coords: origin + CoordinatePoint(0.0, y),
+            end_coords: origin + CoordinatePoint(100.0, y),
+            ..default()
+        };
+        data.sections
+            .entry(rail.start_coords.sector_coordinates())
+            .or_default()
+            .rails
+            .push(id);
+        data.rails.insert(id, rail);
+    }
+
+    let signal = |x: f64, y: f64| SignalData {
+        node_id: 7,
+        kind: SignalKind::Main,
+        coords: origin + CoordinatePoint(x, y),
+        rail: (0, 0),
+        distance: 0.0,
+        direction: Direction::Forward,
+    };
+    data.attach_signals(vec![
+        signal(25.0, 8.0),
+        signal(60.0, -3.0),
+        signal(5000.0, 0.0),
+    ]);
+
+    // signals far away from any rail are dropped
+    assert_eq!(data.signals.len(), 2);
+    assert_eq!(data.signals[0].rail, (2, 3));
+    assert_eq!(data.signals[0].distance, 25.0);
+    assert_eq!(data.signals[1].rail, (0, 1));
+    assert_eq!(data.signals[1].distance, 60.0);
+
+    let section = data.sections.get(&origin.sector_coordinates()).unwrap();
+    assert_eq!(section.signals, vec![0, 1]);
+}
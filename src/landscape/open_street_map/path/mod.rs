@@ -7,6 +7,61 @@ use serde::{Deserialize, Serialize};
 
 pub type PathId = (i64, i64);
 
+// km/h, rule of thumb for the speed through a curve of radius r with full cant
+const CURVE_SPEED_FACTOR: f64 = 4.6;
+// km/h, limits are signposted in steps of this
+const SPEED_LIMIT_STEP: f64 = 5.0;
+
+#[derive(Debug, Default, PartialEq, Deserialize, Serialize, Clone, Copy)]
+pub enum RailKind {
+    #[default]
+    Rail,
+    NarrowGauge,
+    LightRail,
+}
+
+// the tags of the OpenStreetMap way a rail belongs to
+#[derive(Debug, Default, PartialEq, Deserialize, Serialize, Clone)]
+pub struct RailTags {
+    pub kind: RailKind,
+    // km/h
+    pub max_speed: Option<f32>,
+    // e.g. contact_line, rail or no
+    pub electrified: Option<String>,
+    // V
+    pub voltage: Option<u32>,
+    // Hz, 0 for direct current
+    pub frequency: Option<f32>,
+    // mm
+    pub gauge: Option<u32>,
+    // e.g. main, branch or industrial
+    pub usage: Option<String>,
+    // e.g. siding, yard or spur
+    pub service: Option<String>,
+    pub tunnel: bool,
+    pub bridge: bool,
+    pub layer: Option<i8>,
+}
+
+impl RailKind {
+    // km/h, line speed where the way has no maxspeed tag
+    pub fn default_speed_limit(&self) -> f32 {
+        match self {
+            Self::Rail => 100.0,
+            Self::NarrowGauge => 50.0,
+            Self::LightRail => 70.0,
+        }
+    }
+}
+
+impl RailTags {
+    pub fn is_electrified(&self) -> bool {
+        self.electrified
+            .as_ref()
+            .is_some_and(|electrified| electrified != "no")
+    }
+}
+
 #[derive(Component, Default, Debug, Deserialize, Serialize, Clone)]
 pub struct Path {
     pub start_id: i64,
@@ -15,6 +70,9 @@ pub struct Path {
     pub end_coords: CoordinatePoint,
     pub forward_connections: Vec<(PathId, Direction)>,
     pub backward_connections: Vec<(PathId, Direction)>,
+    // 1/m, 0.0 for straight track
+    pub curvature: f64,
+    pub tags: RailTags,
 }
 
 impl Path {
@@ -26,6 +84,27 @@ impl Path {
         (self.end_coords - self.start_coords).length()
     }
 
+    pub fn curve_radius(&self) -> f64 {
+        if self.curvature == 0.0 {
+            f64::INFINITY
+        } else {
+            1.0 / self.curvature
+        }
+    }
+
+    // km/h, from the maxspeed tag or else from the curve radius
+    pub fn speed_limit(&self) -> f32 {
+        if let Some(max_speed) = self.tags.max_speed {
+            return max_speed;
+        }
+
+        let default = self.tags.kind.default_speed_limit() as f64;
+        let curve_speed = CURVE_SPEED_FACTOR * self.curve_radius().sqrt();
+        let limit = (curve_speed / SPEED_LIMIT_STEP).floor() * SPEED_LIMIT_STEP;
+
+        limit.clamp(SPEED_LIMIT_STEP, default) as f32
+    }
+
     pub fn angle(&self) -> f64 {
         let diff = self.end_coords - self.start_coords;
         f64::atan2(diff.1, diff.0)
@@ -94,3 +94,37 @@ fn angle() {
 
     assert_eq!(path.angle(), std::f64::consts::PI * 0.75);
 }
+
+#[test]
+fn curve_radius() {
+    let path = Path::default();
+    assert_eq!(path.curve_radius(), f64::INFINITY);
+
+    let path = Path {
+        curvature: 0.002,
+        ..default()
+    };
+    assert_eq!(path.curve_radius(), 500.0);
+}
+
+#[test]
+fn speed_limit() {
+    let mut path = Path::default();
+    assert_eq!(path.speed_limit(), 100.0);
+
+    // 4.6 * sqrt(300) = 79.7
+    path.curvature = 1.0 / 300.0;
+    assert_eq!(path.speed_limit(), 75.0);
+
+    // tight curves still allow walking pace
+    path.curvature = 1.0;
+    assert_eq!(path.speed_limit(), 5.0);
+
+    path.tags.kind = RailKind::NarrowGauge;
+    path.curvature = 1.0 / 300.0;
+    assert_eq!(path.speed_limit(), 50.0);
+
+    // the tag wins over the geometry
+    path.tags.max_speed = Some(120.0);
+    assert_eq!(path.speed_limit(), 120.0);
+}
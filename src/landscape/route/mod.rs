@@ -0,0 +1,237 @@
+#[cfg(test)]
+mod tests;
+
+use super::{OSMData, Switch, SwitchLeg};
+use crate::{
+    scenario::{ScenarioData, ScenarioStop},
+    train::{Direction, TrackLocation},
+};
+use bevy::{prelude::*, utils::HashMap};
+use std::{cmp::Ordering, collections::BinaryHeap};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum StopProblem {
+    // no path leads there from the previous stop
+    Unreachable,
+    // the route to an earlier stop already passed it
+    OutOfOrder,
+    // no rail starts at the first stop, so there is nowhere to start from
+    NotOnTrack,
+}
+
+impl std::fmt::Display for StopProblem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Unreachable => write!(f, "is unreachable"),
+            Self::OutOfOrder => write!(f, "is out of order"),
+            Self::NotOnTrack => write!(f, "is not on the track"),
+        }
+    }
+}
+
+// the way from stop to stop through the rail network
+#[derive(Resource, Default, Debug)]
+pub struct Route {
+    // rails in the order they are travelled, each with the direction it is
+    // left in
+    pub legs: Vec<SwitchLeg>,
+    // branches the facing switches along the route have to be set to
+    pub switches: Vec<(SwitchLeg, SwitchLeg)>,
+    // index of the stop and what is wrong with it
+    pub problems: Vec<(usize, StopProblem)>,
+}
+
+#[derive(PartialEq)]
+struct Candidate {
+    // m
+    distance: f64,
+    leg: SwitchLeg,
+}
+
+impl Eq for Candidate {}
+
+// reversed to turn the max heap into a min heap
+impl Ord for Candidate {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.distance.total_cmp(&self.distance)
+    }
+}
+
+impl PartialOrd for Candidate {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+// node at which a rail is left
+fn leaving_node(data: &OSMData, leg: &SwitchLeg) -> Option<i64> {
+    let rail = data.rails.get(&leg.0)?;
+
+    Some(match leg.1 {
+        Direction::Forward => rail.end_id,
+        Direction::Backward => rail.start_id,
+    })
+}
+
+// shortest way from `start` to a rail that is left at `node_id`, only using
+// connections in the direction of travel. the result starts with `start`
+fn shortest_path(data: &OSMData, start: SwitchLeg, node_id: i64) -> Option<Vec<SwitchLeg>> {
+    let mut distances = HashMap::new();
+    let mut previous: HashMap<SwitchLeg, SwitchLeg> = HashMap::new();
+    let mut candidates = BinaryHeap::new();
+
+    distances.insert(start, 0.0);
+    candidates.push(Candidate {
+        distance: 0.0,
+        leg: start,
+    });
+
+    while let Some(Candidate { distance, leg }) = candidates.pop() {
+        if distances.get(&leg).is_some_and(|known| *known < distance) {
+            continue;
+        }
+
+        if leaving_node(data, &leg) == Some(node_id) {
+            let mut path = vec![leg];
+            while let Some(leg) = previous.get(path.last().unwrap()) {
+                path.push(*leg);
+            }
+            path.reverse();
+
+            return Some(path);
+        }
+
+        let Some(rail) = data.rails.get(&leg.0) else {
+            continue;
+        };
+
+        for next in rail.possible_connections_by_direction(leg.1) {
+            let Some(next_rail) = data.rails.get(&next.0) else {
+                continue;
+            };
+
+            let next_distance = distance + next_rail.length();
+            if distances
+                .get(next)
+                .is_some_and(|known| *known <= next_distance)
+            {
+                continue;
+            }
+
+            distances.insert(*next, next_distance);
+            previous.insert(*next, leg);
+            candidates.push(Candidate {
+                distance: next_distance,
+                leg: *next,
+            });
+        }
+    }
+
+    None
+}
+
+impl Route {
+    // plans from the start location through all following stops in order.
+    // stops that cannot be reached or are passed before their turn are
+    // skipped and reported
+    pub fn plan(data: &OSMData, start: &TrackLocation, stops: &[ScenarioStop]) -> Self {
+        let mut route = Self {
+            legs: vec![(start.id, start.travel_direction)],
+            ..default()
+        };
+
+        let mut passed_early = Vec::new();
+
+        for (index, stop) in stops.iter().enumerate().skip(1) {
+            if passed_early.contains(&index) {
+                route.problems.push((index, StopProblem::OutOfOrder));
+                continue;
+            }
+
+            let current = *route.legs.last().unwrap();
+
+            match shortest_path(data, current, stop.node_id) {
+                Some(path) => {
+                    // the first leg ends at the previous stop, except at the
+                    // start where the train stands at the beginning of it
+                    let skip = if index == 1 { 0 } else { 1 };
+                    let passed: Vec<_> = path
+                        .iter()
+                        .take(path.len() - 1)
+                        .skip(skip)
+                        .filter_map(|leg| leaving_node(data, leg))
+                        .collect();
+
+                    passed_early.extend(
+                        stops
+                            .iter()
+                            .enumerate()
+                            .skip(index + 1)
+                            .filter(|(_, later)| passed.contains(&later.node_id))
+                            .map(|(later_index, _)| later_index),
+                    );
+
+                    route.legs.extend(path.into_iter().skip(1));
+                }
+                None => {
+                    let passed = route
+                        .legs
+                        .iter()
+                        .any(|leg| leaving_node(data, leg) == Some(stop.node_id));
+
+                    route.problems.push((
+                        index,
+                        if passed {
+                            StopProblem::OutOfOrder
+                        } else {
+                            StopProblem::Unreachable
+                        },
+                    ));
+                }
+            }
+        }
+
+        for pair in route.legs.windows(2) {
+            let is_switch = data
+                .rails
+                .get(&pair[0].0)
+                .is_some_and(|rail| rail.possible_connections_by_direction(pair[0].1).len() > 1);
+
+            if is_switch {
+                route.switches.push((pair[0], pair[1]));
+            }
+        }
+
+        route
+    }
+}
+
+pub fn plan_route(mut commands: Commands, data: Res<OSMData>, scenario_data: Res<ScenarioData>) {
+    let route = match TrackLocation::scenario_start(&data, &scenario_data) {
+        Some(start) => Route::plan(&data, &start, &scenario_data.stops),
+        None => Route {
+            problems: (!scenario_data.stops.is_empty())
+                .then_some((0, StopProblem::NotOnTrack))
+                .into_iter()
+                .collect(),
+            ..default()
+        },
+    };
+
+    for (index, problem) in route.problems.iter() {
+        let stop = &scenario_data.stops[*index];
+        log::warn!("stop {} ({}) {}", stop.name, stop.node_id, problem);
+    }
+
+    commands.insert_resource(route);
+}
+
+pub fn set_route_switches(route: Res<Route>, mut switches: Query<&mut Switch>) {
+    let branches: HashMap<SwitchLeg, SwitchLeg> = route.switches.iter().copied().collect();
+
+    for mut switch in switches.iter_mut() {
+        if let Some(branch) = branches.get(&switch.leg) {
+            switch.set_to(*branch);
+        }
+    }
+}
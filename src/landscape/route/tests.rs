@@ -0,0 +1,193 @@
+use super::*;
+use crate::{
+    landscape::{CoordinatePoint, Path, PathId},
+    scenario::ScenarioInfo,
+};
+use coverage_helper::test;
+
+#[coverage(off)]
+fn rail(id: PathId, start: (f64, f64), end: (f64, f64)) -> Path {
+    Path {
+        start_id: id.0,
+        end_id: id.1,
+        start_coords: CoordinatePoint(start.0, start.1),
+        end_coords: CoordinatePoint(end.0, end.1),
+        ..default()
+    }
+}
+
+// a line that splits at node 2 into a branch to node 5 and one to node 6
+#[coverage(off)]
+fn gen_data() -> OSMData {
+    let forward = Direction::Forward;
+    let backward = Direction::Backward;
+    let mut rails = std::collections::HashMap::default();
+
+    let mut a = rail((0, 1), (0.0, 0.0), (100.0, 0.0));
+    a.forward_connections = vec![((1, 2), forward)];
+    let mut b = rail((1, 2), (100.0, 0.0), (200.0, 0.0));
+    b.backward_connections = vec![((0, 1), backward)];
+    b.forward_connections = vec![((2, 3), forward), ((2, 4), forward)];
+    let mut c = rail((2, 3), (200.0, 0.0), (300.0, 0.0));
+    c.backward_connections = vec![((1, 2), backward)];
+    c.forward_connections = vec![((3, 5), forward)];
+    let mut d = rail((2, 4), (200.0, 0.0), (300.0, 30.0));
+    d.backward_connections = vec![((1, 2), backward)];
+    d.forward_connections = vec![((4, 6), forward)];
+    let mut e = rail((3, 5), (300.0, 0.0), (400.0, 0.0));
+    e.backward_connections = vec![((2, 3), backward)];
+    let mut f = rail((4, 6), (300.0, 30.0), (400.0, 60.0));
+    f.backward_connections = vec![((2, 4), backward)];
+
+    for path in [a, b, c, d, e, f] {
+        rails.insert(path.id(), path);
+    }
+
+    OSMData { rails, ..default() }
+}
+
+#[coverage(off)]
+fn gen_stops(node_ids: &[i64]) -> Vec<ScenarioStop> {
+    node_ids
+        .iter()
+        .map(|node_id| ScenarioStop {
+            name: format!("Stop {}", node_id),
+            node_id: *node_id,
+        })
+        .collect()
+}
+
+#[coverage(off)]
+fn start() -> TrackLocation {
+    TrackLocation {
+        id: (0, 1),
+        distance: 0.0,
+        travel_direction: Direction::Forward,
+    }
+}
+
+#[test]
+fn plans_through_switches() {
+    let data = gen_data();
+
+    let route = Route::plan(&data, &start(), &gen_stops(&[0, 6]));
+
+    assert_eq!(
+        route.legs,
+        vec![
+            ((0, 1), Direction::Forward),
+            ((1, 2), Direction::Forward),
+            ((2, 4), Direction::Forward),
+            ((4, 6), Direction::Forward),
+        ]
+    );
+    assert_eq!(
+        route.switches,
+        vec![(((1, 2), Direction::Forward), ((2, 4), Direction::Forward))]
+    );
+    assert!(route.problems.is_empty());
+}
+
+#[test]
+fn reports_unreachable_stops() {
+    let data = gen_data();
+
+    let route = Route::plan(&data, &start(), &gen_stops(&[0, 5, 6, 42]));
+
+    assert_eq!(route.legs.last(), Some(&((3, 5), Direction::Forward)));
+    assert_eq!(
+        route.problems,
+        vec![(2, StopProblem::Unreachable), (3, StopProblem::Unreachable)]
+    );
+}
+
+#[test]
+fn reports_stops_out_of_order() {
+    let data = gen_data();
+
+    let route = Route::plan(&data, &start(), &gen_stops(&[0, 3, 2]));
+
+    assert_eq!(route.legs.last(), Some(&((2, 3), Direction::Forward)));
+    assert_eq!(route.problems, vec![(2, StopProblem::OutOfOrder)]);
+}
+
+// a loop of four rails through the nodes 0, 1, 2 and 3
+#[coverage(off)]
+fn gen_loop_data() -> OSMData {
+    let forward = Direction::Forward;
+    let mut rails = std::collections::HashMap::default();
+
+    let mut a = rail((0, 1), (0.0, 0.0), (100.0, 0.0));
+    a.forward_connections = vec![((1, 2), forward)];
+    let mut b = rail((1, 2), (100.0, 0.0), (100.0, 100.0));
+    b.forward_connections = vec![((2, 3), forward)];
+    let mut c = rail((2, 3), (100.0, 100.0), (0.0, 100.0));
+    c.forward_connections = vec![((3, 0), forward)];
+    let mut d = rail((3, 0), (0.0, 100.0), (0.0, 0.0));
+    d.forward_connections = vec![((0, 1), forward)];
+
+    for path in [a, b, c, d] {
+        rails.insert(path.id(), path);
+    }
+
+    OSMData { rails, ..default() }
+}
+
+#[test]
+fn reports_stops_passed_before_their_turn() {
+    let data = gen_loop_data();
+
+    // node 1 is passed on the way to node 2, even though the loop leads back
+    let route = Route::plan(&data, &start(), &gen_stops(&[0, 2, 1, 3]));
+
+    assert_eq!(route.legs.last(), Some(&((2, 3), Direction::Forward)));
+    assert_eq!(route.problems, vec![(2, StopProblem::OutOfOrder)]);
+}
+
+#[test]
+fn reports_start_off_the_track() {
+    let mut app = App::new();
+    app.insert_resource(gen_data())
+        .insert_resource(ScenarioData {
+            stops: gen_stops(&[42, 6]),
+            ..default()
+        })
+        .add_systems(Update, plan_route);
+
+    app.update();
+
+    let route = app.world().resource::<Route>();
+    assert!(route.legs.is_empty());
+    assert_eq!(route.problems, vec![(0, StopProblem::NotOnTrack)]);
+}
+
+#[test]
+fn sets_switches_on_load() {
+    let mut app = App::new();
+    app.insert_resource(gen_data())
+        .insert_resource(ScenarioData {
+            info: ScenarioInfo {
+                starting_direction: Direction::Forward,
+                ..default()
+            },
+            stops: gen_stops(&[0, 6]),
+            ..default()
+        })
+        .add_systems(Update, (plan_route, set_route_switches).chain());
+
+    app.world_mut().spawn(Switch {
+        leg: ((1, 2), Direction::Forward),
+        branches: vec![((2, 3), Direction::Forward), ((2, 4), Direction::Forward)],
+        position: 0,
+    });
+
+    app.update();
+
+    let switch = app
+        .world_mut()
+        .query::<&Switch>()
+        .single(app.world())
+        .clone();
+    assert_eq!(switch.branch(), ((2, 4), Direction::Forward));
+    assert!(app.world().get_resource::<Route>().is_some());
+}
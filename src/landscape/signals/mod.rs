@@ -0,0 +1,370 @@
+#[cfg(test)]
+mod tests;
+
+use super::{
+    track_magnets::{covered, spawn_track_magnets},
+    OSMData, PathId, SignalKind, SwitchLeg, SwitchPositions,
+};
+use crate::train::{Dimension, Direction, PhysicsSet, TrackLocation};
+use bevy::{prelude::*, time::common_conditions::on_timer, utils::HashMap};
+use std::time::Duration;
+
+// m, blocks longer than this are cut off
+const MAX_BLOCK_LENGTH: f64 = 5000.0;
+// s, aspects follow the trains with this delay
+const ASPECT_UPDATE_INTERVAL: f32 = 0.25;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum Aspect {
+    #[default]
+    Stop,
+    Caution,
+    Clear,
+}
+
+#[derive(Component, Debug, Clone)]
+pub struct Signal {
+    // index into the signals of the OpenStreetMap data
+    pub index: usize,
+    pub kind: SignalKind,
+    // trains travelling along the location's travel direction obey the signal
+    pub location: TrackLocation,
+    pub aspect: Aspect,
+}
+
+// how the track ahead of a signal ends
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BlockEnd {
+    // the next main signal
+    Signal(Entity),
+    // a buffer stop
+    TrackEnd,
+    // neither within the maximum block length
+    Open,
+}
+
+// m from the start of the rail
+pub(super) fn offset(data: &OSMData, location: &TrackLocation) -> f64 {
+    match location.travel_direction {
+        Direction::Forward => location.distance,
+        Direction::Backward => data
+            .rails
+            .get(&location.id)
+            .map_or(0.0, |rail| rail.length() - location.distance),
+    }
+}
+
+// a signal facing along its rail at the given offset
+fn signal_location(
+    data: &OSMData,
+    rail: PathId,
+    distance: f64,
+    direction: Direction,
+) -> TrackLocation {
+    let length = data.rails.get(&rail).map_or(0.0, |rail| rail.length());
+
+    TrackLocation {
+        id: rail,
+        distance: match direction {
+            Direction::Forward => distance,
+            Direction::Backward => length - distance,
+        },
+        travel_direction: direction,
+    }
+}
+
+// how the movement authority of a train ends
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AuthorityEnd {
+    // a main signal showing stop
+    Signal(Entity),
+    // a buffer stop
+    TrackEnd,
+    // the rail the planned route ends on
+    RouteEnd,
+}
+
+// signals by rail with their offset and the direction they face
+pub type SignalIndex = HashMap<PathId, Vec<(f64, Direction, Entity)>>;
+
+pub fn index_signals<'a>(
+    data: &OSMData,
+    signals: impl IntoIterator<Item = (Entity, &'a Signal)>,
+) -> SignalIndex {
+    let mut index: SignalIndex = HashMap::new();
+    for (entity, signal) in signals {
+        index.entry(signal.location.id).or_default().push((
+            offset(data, &signal.location),
+            signal.location.travel_direction,
+            entity,
+        ));
+    }
+
+    index
+}
+
+// parts of the rails the vehicles stand on, from offset to offset
+pub type Occupancy = HashMap<PathId, Vec<(f64, f64)>>;
+
+// follows the track ahead of `location` until the next main signal and
+// reports whether a vehicle stands in between
+pub fn walk_block(
+    data: &OSMData,
+    switches: &SwitchPositions,
+    location: &TrackLocation,
+    main_signals: &SignalIndex,
+    occupied: &Occupancy,
+) -> (bool, BlockEnd) {
+    let mut id = location.id;
+    let mut leaving_direction = location.travel_direction;
+    let mut from = Some(offset(data, location));
+    let mut walked = 0.0;
+
+    while walked < MAX_BLOCK_LENGTH {
+        let Some(rail) = data.rails.get(&id) else {
+            return (false, BlockEnd::TrackEnd);
+        };
+        let length = rail.length();
+
+        // the part of the rail ahead, in offsets from its start
+        let (mut start, mut end) = match leaving_direction {
+            Direction::Forward => (from.unwrap_or(0.0), length),
+            Direction::Backward => (0.0, from.unwrap_or(length)),
+        };
+        let is_ahead = |position: f64, start: f64, end: f64| match (leaving_direction, from) {
+            (Direction::Forward, Some(_)) => position > start && position <= end,
+            (Direction::Backward, Some(_)) => position >= start && position < end,
+            (_, None) => position >= start && position <= end,
+        };
+
+        let next_signal = main_signals
+            .get(&id)
+            .into_iter()
+            .flatten()
+            .filter(|(position, direction, _)| {
+                *direction == leaving_direction && is_ahead(*position, start, end)
+            })
+            .min_by(|a, b| match leaving_direction {
+                Direction::Forward => a.0.total_cmp(&b.0),
+                Direction::Backward => b.0.total_cmp(&a.0),
+            });
+
+        if let Some((position, _, _)) = next_signal {
+            match leaving_direction {
+                Direction::Forward => end = *position,
+                Direction::Backward => start = *position,
+            }
+        }
+
+        let is_occupied = occupied
+            .get(&id)
+            .into_iter()
+            .flatten()
+            .any(|(from, to)| *from <= end && *to >= start);
+
+        if let Some((_, _, entity)) = next_signal {
+            return (is_occupied, BlockEnd::Signal(*entity));
+        }
+        if is_occupied {
+            return (true, BlockEnd::Open);
+        }
+
+        walked += end - start;
+
+        let possible = rail.possible_connections_by_direction(leaving_direction);
+        let Some((next_id, next_direction)) = possible
+            .get(switches.position(&(id, leaving_direction)))
+            .or(possible.first())
+        else {
+            return (false, BlockEnd::TrackEnd);
+        };
+
+        id = *next_id;
+        leaving_direction = *next_direction;
+        from = None;
+    }
+
+    (false, BlockEnd::Open)
+}
+
+// m from `location` along its travel direction to where the train has to
+// stop: the first of `stop_signals`, a buffer stop or the end of the route.
+// none if the way is clear for max_distance
+pub fn movement_authority(
+    data: &OSMData,
+    switches: &SwitchPositions,
+    location: &TrackLocation,
+    stop_signals: &SignalIndex,
+    route_end: Option<SwitchLeg>,
+    max_distance: f64,
+) -> Option<(f64, AuthorityEnd)> {
+    let mut id = location.id;
+    let mut leaving_direction = location.travel_direction;
+    let mut from = Some(offset(data, location));
+    let mut walked = 0.0;
+
+    while walked < max_distance {
+        let Some(rail) = data.rails.get(&id) else {
+            return Some((walked, AuthorityEnd::TrackEnd));
+        };
+        let length = rail.length();
+        let start = from.unwrap_or(match leaving_direction {
+            Direction::Forward => 0.0,
+            Direction::Backward => length,
+        });
+        // m from start in the direction the rail is left
+        let ahead = |position: f64| match leaving_direction {
+            Direction::Forward => position - start,
+            Direction::Backward => start - position,
+        };
+
+        // a train standing at a signal has already passed it
+        let next_signal = stop_signals
+            .get(&id)
+            .into_iter()
+            .flatten()
+            .filter(|(_, direction, _)| *direction == leaving_direction)
+            .map(|(position, _, entity)| (ahead(*position), *entity))
+            .filter(|(distance, _)| *distance > 0.0 || (from.is_none() && *distance == 0.0))
+            .min_by(|a, b| a.0.total_cmp(&b.0));
+
+        if let Some((distance, entity)) = next_signal {
+            return Some((walked + distance, AuthorityEnd::Signal(entity)));
+        }
+
+        walked += ahead(match leaving_direction {
+            Direction::Forward => length,
+            Direction::Backward => 0.0,
+        });
+
+        if route_end == Some((id, leaving_direction)) {
+            return Some((walked, AuthorityEnd::RouteEnd));
+        }
+
+        let possible = rail.possible_connections_by_direction(leaving_direction);
+        let Some((next_id, next_direction)) = possible
+            .get(switches.position(&(id, leaving_direction)))
+            .or(possible.first())
+        else {
+            return Some((walked, AuthorityEnd::TrackEnd));
+        };
+
+        id = *next_id;
+        leaving_direction = *next_direction;
+        from = None;
+    }
+
+    None
+}
+
+pub fn spawn_signals(
+    mut commands: Commands,
+    data: Res<OSMData>,
+    existing: Query<Entity, With<Signal>>,
+) {
+    for entity in existing.iter() {
+        commands.entity(entity).despawn();
+    }
+
+    for (index, signal) in data.signals.iter().enumerate() {
+        commands.spawn(Signal {
+            index,
+            kind: signal.kind,
+            location: signal_location(&data, signal.rail, signal.distance, signal.direction),
+            aspect: Aspect::default(),
+        });
+    }
+}
+
+pub fn update_signal_aspects(
+    data: Res<OSMData>,
+    switches: Res<SwitchPositions>,
+    mut signals: Query<(Entity, &mut Signal)>,
+    vehicles: Query<(&TrackLocation, Option<&Dimension>)>,
+) {
+    // each vehicle occupies its whole length around its centre
+    let mut occupied: Occupancy = HashMap::new();
+    for (location, dimension) in vehicles.iter() {
+        let length = dimension.map_or(0.0, |dimension| dimension.length as f64);
+        let mut rear = location.clone();
+        rear.add_distance(&data, &switches, -length / 2.0);
+        let mut front = location.clone();
+        front.add_distance(&data, &switches, length / 2.0);
+
+        for (id, from, to) in covered(&data, &switches, &rear, &front, length) {
+            occupied
+                .entry(id)
+                .or_default()
+                .push((from.min(to), from.max(to)));
+        }
+    }
+
+    let main_signals = index_signals(
+        &data,
+        signals.iter().filter(|(_, signal)| signal.kind.is_main()),
+    );
+
+    let blocks: HashMap<Entity, (bool, BlockEnd)> = signals
+        .iter()
+        .map(|(entity, signal)| {
+            (
+                entity,
+                walk_block(&data, &switches, &signal.location, &main_signals, &occupied),
+            )
+        })
+        .collect();
+
+    // a block is free when nothing is in it, the aspect of the next signal
+    // tells whether the one after is free as well
+    let is_free = |entity: &Entity, signal: &Signal| {
+        !signal.kind.is_main() || !blocks.get(entity).is_some_and(|(occupied, _)| *occupied)
+    };
+    let free: HashMap<Entity, bool> = signals
+        .iter()
+        .map(|(entity, signal)| (entity, is_free(&entity, signal)))
+        .collect();
+
+    for (entity, mut signal) in signals.iter_mut() {
+        let (_, end) = blocks[&entity];
+        let next_is_free = match end {
+            BlockEnd::Signal(next) => free.get(&next).copied().unwrap_or(true),
+            BlockEnd::TrackEnd => false,
+            BlockEnd::Open => true,
+        };
+
+        let aspect = if !free[&entity] {
+            Aspect::Stop
+        } else if next_is_free {
+            Aspect::Clear
+        } else {
+            Aspect::Caution
+        };
+
+        if signal.aspect != aspect {
+            signal.aspect = aspect;
+        }
+    }
+}
+
+pub struct SignalPlugin;
+
+impl Plugin for SignalPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            FixedUpdate,
+            (spawn_signals, spawn_track_magnets)
+                .chain()
+                .run_if(resource_exists_and_changed::<OSMData>)
+                .before(PhysicsSet::Aggregate),
+        )
+        .add_systems(
+            FixedUpdate,
+            update_signal_aspects
+                .run_if(
+                    resource_exists::<OSMData>
+                        .and_then(on_timer(Duration::from_secs_f32(ASPECT_UPDATE_INTERVAL))),
+                )
+                .after(PhysicsSet::Locate),
+        );
+    }
+}
@@ -0,0 +1,252 @@
+use super::*;
+use crate::landscape::{CoordinatePoint, Path, SignalData};
+use coverage_helper::test;
+
+// three straight rails of 1 km in a row
+#[coverage(off)]
+fn gen_data() -> OSMData {
+    let mut rails = std::collections::HashMap::default();
+    for i in 0..3 {
+        let mut path = Path {
+            start_id: i,
+            end_id: i + 1,
+            start_coords: CoordinatePoint(i as f64 * 1000.0, 0.0),
+            end_coords: CoordinatePoint((i + 1) as f64 * 1000.0, 0.0),
+            ..default()
+        };
+        if i > 0 {
+            path.backward_connections = vec![((i - 1, i), Direction::Backward)];
+        }
+        if i < 2 {
+            path.forward_connections = vec![((i + 1, i + 2), Direction::Forward)];
+        }
+        rails.insert(path.id(), path);
+    }
+
+    let signal = |kind, rail, distance| SignalData {
+        node_id: 0,
+        kind,
+        coords: CoordinatePoint::default(),
+        rail,
+        distance,
+        direction: Direction::Forward,
+    };
+
+    OSMData {
+        rails,
+        signals: vec![
+            signal(SignalKind::Main, (0, 1), 100.0),
+            signal(SignalKind::Distant, (0, 1), 800.0),
+            signal(SignalKind::Main, (1, 2), 500.0),
+        ],
+        ..default()
+    }
+}
+
+#[coverage(off)]
+fn setup() -> App {
+    let mut app = App::new();
+    app.insert_resource(gen_data())
+        .init_resource::<SwitchPositions>()
+        .add_systems(
+            Update,
+            (
+                spawn_signals.run_if(resource_exists_and_changed::<OSMData>),
+                update_signal_aspects,
+            )
+                .chain(),
+        );
+    app
+}
+
+#[coverage(off)]
+fn aspects(app: &mut App) -> Vec<Aspect> {
+    let mut signals = app
+        .world_mut()
+        .query::<&Signal>()
+        .iter(app.world())
+        .map(|signal| (signal.index, signal.aspect))
+        .collect::<Vec<_>>();
+    signals.sort_by_key(|(index, _)| *index);
+    signals.into_iter().map(|(_, aspect)| aspect).collect()
+}
+
+#[coverage(off)]
+fn vehicle(id: PathId, distance: f64) -> TrackLocation {
+    TrackLocation {
+        id,
+        distance,
+        travel_direction: Direction::Forward,
+    }
+}
+
+#[test]
+fn blocks_end_at_the_next_main_signal() {
+    let data = gen_data();
+    let mut main_signals: SignalIndex = HashMap::new();
+    main_signals
+        .entry((1, 2))
+        .or_default()
+        .push((500.0, Direction::Forward, Entity::PLACEHOLDER));
+
+    let start = vehicle((0, 1), 100.0);
+    let switches = SwitchPositions::default();
+
+    let free = walk_block(&data, &switches, &start, &main_signals, &HashMap::new());
+    assert_eq!(free, (false, BlockEnd::Signal(Entity::PLACEHOLDER)));
+
+    let occupied: Occupancy = [((1, 2), vec![(300.0, 300.0)])].into_iter().collect();
+    let (is_occupied, _) = walk_block(&data, &switches, &start, &main_signals, &occupied);
+    assert!(is_occupied);
+
+    // behind the next signal is the next block
+    let beyond: Occupancy = [((1, 2), vec![(700.0, 720.0)])].into_iter().collect();
+    let (is_occupied, _) = walk_block(&data, &switches, &start, &main_signals, &beyond);
+    assert!(!is_occupied);
+
+    // without signals the block runs into the buffer stop
+    let (_, end) = walk_block(&data, &switches, &start, &HashMap::new(), &HashMap::new());
+    assert_eq!(end, BlockEnd::TrackEnd);
+
+    // signals facing the other way are passed
+    let mut opposite: SignalIndex = HashMap::new();
+    opposite
+        .entry((1, 2))
+        .or_default()
+        .push((500.0, Direction::Backward, Entity::PLACEHOLDER));
+    let (_, end) = walk_block(&data, &switches, &start, &opposite, &HashMap::new());
+    assert_eq!(end, BlockEnd::TrackEnd);
+}
+
+#[test]
+fn backward_blocks() {
+    let data = gen_data();
+    let mut main_signals: SignalIndex = HashMap::new();
+    main_signals
+        .entry((0, 1))
+        .or_default()
+        .push((200.0, Direction::Backward, Entity::PLACEHOLDER));
+
+    // 500 m from the end of the middle rail travelling backward
+    let start = TrackLocation {
+        id: (1, 2),
+        distance: 500.0,
+        travel_direction: Direction::Backward,
+    };
+    let occupied: Occupancy = [((0, 1), vec![(600.0, 600.0)])].into_iter().collect();
+
+    let block = walk_block(
+        &data,
+        &SwitchPositions::default(),
+        &start,
+        &main_signals,
+        &occupied,
+    );
+    assert_eq!(block, (true, BlockEnd::Signal(Entity::PLACEHOLDER)));
+}
+
+#[test]
+fn aspects_follow_occupancy() {
+    let mut app = setup();
+
+    // the last signal protects a block that runs into the buffer stop
+    app.update();
+    assert_eq!(
+        aspects(&mut app),
+        vec![Aspect::Clear, Aspect::Clear, Aspect::Caution]
+    );
+
+    // a train between the two main signals
+    let train = app.world_mut().spawn(vehicle((0, 1), 900.0)).id();
+    app.update();
+    assert_eq!(
+        aspects(&mut app),
+        vec![Aspect::Stop, Aspect::Clear, Aspect::Caution]
+    );
+
+    // the train passed the second main signal
+    *app.world_mut().get_mut::<TrackLocation>(train).unwrap() = vehicle((1, 2), 600.0);
+    app.update();
+    assert_eq!(
+        aspects(&mut app),
+        vec![Aspect::Caution, Aspect::Caution, Aspect::Stop]
+    );
+}
+
+#[test]
+fn vehicles_occupy_their_length() {
+    let mut app = setup();
+
+    // the centre is in front of the second main signal, the front behind it
+    app.world_mut()
+        .spawn((vehicle((1, 2), 480.0), Dimension { length: 60.0 }));
+    app.update();
+    assert_eq!(
+        aspects(&mut app),
+        vec![Aspect::Stop, Aspect::Caution, Aspect::Stop]
+    );
+}
+
+#[test]
+fn movement_authority_ends_at_the_first_stop() {
+    let data = gen_data();
+    let switches = SwitchPositions::default();
+    let mut stop_signals: SignalIndex = HashMap::new();
+    stop_signals
+        .entry((1, 2))
+        .or_default()
+        .push((500.0, Direction::Forward, Entity::PLACEHOLDER));
+    let start = vehicle((0, 1), 100.0);
+
+    let authority = movement_authority(&data, &switches, &start, &stop_signals, None, 5000.0);
+    assert_eq!(
+        authority,
+        Some((1400.0, AuthorityEnd::Signal(Entity::PLACEHOLDER)))
+    );
+
+    // a train standing at the signal is beyond it
+    let at_signal = vehicle((1, 2), 500.0);
+    let authority = movement_authority(&data, &switches, &at_signal, &stop_signals, None, 5000.0);
+    assert_eq!(authority, Some((1500.0, AuthorityEnd::TrackEnd)));
+
+    let empty = HashMap::new();
+    let authority = movement_authority(&data, &switches, &start, &empty, None, 5000.0);
+    assert_eq!(authority, Some((2900.0, AuthorityEnd::TrackEnd)));
+
+    let route_end = Some(((1, 2), Direction::Forward));
+    let authority = movement_authority(&data, &switches, &start, &empty, route_end, 5000.0);
+    assert_eq!(authority, Some((1900.0, AuthorityEnd::RouteEnd)));
+
+    // clear beyond the lookahead
+    let authority = movement_authority(&data, &switches, &start, &empty, None, 1000.0);
+    assert_eq!(authority, None);
+}
+
+#[test]
+fn backward_movement_authority() {
+    let data = gen_data();
+    let mut stop_signals: SignalIndex = HashMap::new();
+    stop_signals
+        .entry((0, 1))
+        .or_default()
+        .push((200.0, Direction::Backward, Entity::PLACEHOLDER));
+
+    let start = TrackLocation {
+        id: (1, 2),
+        distance: 500.0,
+        travel_direction: Direction::Backward,
+    };
+
+    let authority = movement_authority(
+        &data,
+        &SwitchPositions::default(),
+        &start,
+        &stop_signals,
+        None,
+        5000.0,
+    );
+    assert_eq!(
+        authority,
+        Some((1300.0, AuthorityEnd::Signal(Entity::PLACEHOLDER)))
+    );
+}
@@ -0,0 +1,142 @@
+use super::{
+    signals::{Aspect, Signal},
+    AssetData, HeightMap, Landscape, OSMData,
+};
+use crate::train::Direction;
+use bevy::{prelude::*, utils::HashMap};
+
+// m to the right of the track centre
+const SIGNAL_OFFSET: f64 = 2.5;
+// m
+pub const SIGNAL_POLE_HEIGHT: f32 = 4.0;
+pub const SIGNAL_HEAD_SIZE: f32 = 0.5;
+
+#[derive(Component)]
+pub struct SpawnedSignalModels;
+
+// the lit part of the model of a signal, by the index of the signal
+#[derive(Component)]
+pub struct SignalLamp(pub usize);
+
+#[coverage(off)]
+fn lamp_material(assets: &AssetData, aspect: Aspect) -> Handle<StandardMaterial> {
+    match aspect {
+        Aspect::Stop => assets.signal_stop_material.clone(),
+        Aspect::Caution => assets.signal_caution_material.clone(),
+        Aspect::Clear => assets.signal_clear_material.clone(),
+    }
+}
+
+#[coverage(off)]
+pub fn system(
+    assets: Res<AssetData>,
+    mut commands: Commands,
+    data: Res<OSMData>,
+    landscapes: Query<(Entity, &Landscape), Without<SpawnedSignalModels>>,
+    signals: Query<&Signal>,
+    height_map: Res<HeightMap>,
+) {
+    let aspects: HashMap<usize, Aspect> = signals
+        .iter()
+        .map(
+            #[coverage(off)]
+            |signal| (signal.index, signal.aspect),
+        )
+        .collect();
+
+    for (entity, landscape) in landscapes.iter() {
+        commands.entity(entity).insert(SpawnedSignalModels);
+
+        let Some(section) = data.sections.get(&landscape.position.sector_coordinates()) else {
+            continue;
+        };
+
+        for index in section.signals.iter() {
+            let signal = &data.signals[*index];
+            let Some(rail) = data.rails.get(&signal.rail) else {
+                continue;
+            };
+
+            // beside the track on the right hand side of the approaching train
+            let angle = rail.angle();
+            let side = match signal.direction {
+                Direction::Forward => 1.0,
+                Direction::Backward => -1.0,
+            };
+            let along = (rail.end_coords - rail.start_coords) / rail.length().max(f64::EPSILON);
+            let coords = rail.start_coords
+                + along * signal.distance
+                + super::CoordinatePoint(angle.sin(), -angle.cos()) * (SIGNAL_OFFSET * side);
+
+            let height = height_map.height_at_position(coords.0, coords.1);
+            let position = coords - landscape.position;
+            let facing = match signal.direction {
+                Direction::Forward => angle as f32 + std::f32::consts::PI,
+                Direction::Backward => angle as f32,
+            };
+
+            let aspect = aspects.get(index).copied().unwrap_or_default();
+
+            commands.entity(entity).with_children(
+                #[coverage(off)]
+                |parent| {
+                    parent
+                        .spawn(PbrBundle {
+                            mesh: assets.signal_pole_mesh.clone(),
+                            material: assets.signal_pole_material.clone(),
+                            transform: Transform::from_xyz(
+                                position.0 as f32,
+                                height + SIGNAL_POLE_HEIGHT / 2.0,
+                                -position.1 as f32,
+                            )
+                            .with_rotation(Quat::from_rotation_y(facing)),
+                            ..default()
+                        })
+                        .with_children(
+                            #[coverage(off)]
+                            |pole| {
+                                pole.spawn((
+                                    SignalLamp(*index),
+                                    PbrBundle {
+                                        mesh: assets.signal_head_mesh.clone(),
+                                        material: lamp_material(&assets, aspect),
+                                        transform: Transform::from_xyz(
+                                            0.0,
+                                            SIGNAL_POLE_HEIGHT / 2.0,
+                                            0.0,
+                                        ),
+                                        ..default()
+                                    },
+                                ));
+                            },
+                        );
+                },
+            );
+        }
+    }
+}
+
+#[coverage(off)]
+pub fn update_lamps(
+    assets: Res<AssetData>,
+    signals: Query<&Signal, Changed<Signal>>,
+    mut lamps: Query<(&SignalLamp, &mut Handle<StandardMaterial>)>,
+) {
+    let aspects: HashMap<usize, Aspect> = signals
+        .iter()
+        .map(
+            #[coverage(off)]
+            |signal| (signal.index, signal.aspect),
+        )
+        .collect();
+
+    if aspects.is_empty() {
+        return;
+    }
+
+    for (lamp, mut material) in lamps.iter_mut() {
+        if let Some(aspect) = aspects.get(&lamp.0) {
+            *material = lamp_material(&assets, *aspect);
+        }
+    }
+}
@@ -0,0 +1,305 @@
+#[cfg(test)]
+mod tests;
+
+use super::{
+    open_street_map::{BuildingType, Path},
+    signals::offset,
+    CoordinatePoint, OSMData, PathId, SwitchLeg, SwitchPositions,
+};
+use crate::{
+    scenario::ScenarioData,
+    train::{Direction, PhysicsSet, TrackLocation},
+};
+use bevy::{
+    prelude::*,
+    utils::{HashMap, HashSet},
+};
+use std::collections::VecDeque;
+
+// m, length of the window around a stop without a platform
+const DEFAULT_WINDOW_LENGTH: f64 = 200.0;
+// m, platforms this close to the stop node belong to the stop
+const PLATFORM_SEARCH_RADIUS: f64 = 50.0;
+// m, track this close to a platform runs alongside it
+const PLATFORM_CLEARANCE: f64 = 10.0;
+// m, how far from the stop node the track is checked for platforms
+const MAX_PLATFORM_LENGTH: f64 = 500.0;
+// m, the track is checked for platforms in steps of this
+const PLATFORM_SAMPLE_STEP: f64 = 5.0;
+// m, locations this close outside a window still count as inside
+const WINDOW_TOLERANCE: f64 = 0.01;
+
+// the stretch of track a train has to halt within to call at a stop
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct StopWindow {
+    // rails with the part of them that belongs to the window, in m from the
+    // start of the rail
+    pub segments: Vec<(PathId, f64, f64)>,
+}
+
+// the stopping windows of the scenario stops by index, none for stops that
+// are not on the track
+#[derive(Resource, Default, Debug)]
+pub struct StopWindows(pub Vec<Option<StopWindow>>);
+
+// m from `point` to the outline of a platform, 0 inside of it
+fn distance_to_outline(point: CoordinatePoint, outline: &[CoordinatePoint]) -> f64 {
+    let mut is_inside = false;
+    let mut distance = f64::INFINITY;
+
+    for (index, start) in outline.iter().enumerate() {
+        let end = outline[(index + 1) % outline.len()];
+        let edge = end - *start;
+        let relative = point - *start;
+
+        if (start.1 > point.1) != (end.1 > point.1)
+            && point.0 < start.0 + edge.0 * (point.1 - start.1) / edge.1
+        {
+            is_inside = !is_inside;
+        }
+
+        let length_squared = edge.0 * edge.0 + edge.1 * edge.1;
+        let along = if length_squared == 0.0 {
+            0.0
+        } else {
+            ((relative.0 * edge.0 + relative.1 * edge.1) / length_squared).clamp(0.0, 1.0)
+        };
+        distance = distance.min((point - (*start + edge * along)).length());
+    }
+
+    if is_inside {
+        0.0
+    } else {
+        distance
+    }
+}
+
+// the outlines of the platforms close to `point`
+fn platforms_near(data: &OSMData, point: CoordinatePoint) -> Vec<&[CoordinatePoint]> {
+    let (x, y) = point.sector_coordinates();
+
+    (x - 1..=x + 1)
+        .flat_map(|x| (y - 1..=y + 1).map(move |y| (x, y)))
+        .filter_map(|sector| data.sections.get(&sector))
+        .flat_map(|section| section.buildings.iter())
+        .filter(|building| building.building_type == BuildingType::Platform)
+        .map(|building| building.coordinates.0.as_slice())
+        .filter(|outline| {
+            !outline.is_empty() && distance_to_outline(point, outline) <= PLATFORM_SEARCH_RADIUS
+        })
+        .collect()
+}
+
+// the part of `rail` that runs alongside one of the platforms
+fn platform_segment(rail: &Path, platforms: &[&[CoordinatePoint]]) -> Option<(f64, f64)> {
+    let length = rail.length();
+    let steps = (length / PLATFORM_SAMPLE_STEP).ceil().max(1.0) as usize;
+
+    let alongside: Vec<f64> = (0..=steps)
+        .map(|step| step as f64 / steps as f64)
+        .filter(|share| {
+            let point = rail.start_coords + (rail.end_coords - rail.start_coords) * *share;
+            platforms
+                .iter()
+                .any(|outline| distance_to_outline(point, outline) <= PLATFORM_CLEARANCE)
+        })
+        .map(|share| share * length)
+        .collect();
+
+    Some((*alongside.first()?, *alongside.last()?))
+}
+
+impl StopWindow {
+    // the track alongside the platforms at `node_id` or, without any, the
+    // track around the node
+    pub fn resolve(data: &OSMData, node_id: i64) -> Option<Self> {
+        let mut node = None;
+        let mut leaving = vec![];
+        for rail in data.rails.values() {
+            if rail.start_id == node_id {
+                node = Some(rail.start_coords);
+                leaving.push((rail.id(), Direction::Forward));
+            } else if rail.end_id == node_id {
+                node = Some(rail.end_coords);
+                leaving.push((rail.id(), Direction::Backward));
+            }
+        }
+
+        let platforms = platforms_near(data, node?);
+        if !platforms.is_empty() {
+            let window = Self::around(data, &leaving, MAX_PLATFORM_LENGTH, |rail, _, _| {
+                platform_segment(rail, &platforms)
+            });
+            if !window.segments.is_empty() {
+                return Some(window);
+            }
+        }
+
+        Some(Self::around(
+            data,
+            &leaving,
+            DEFAULT_WINDOW_LENGTH / 2.0,
+            |rail, leaving_direction, walked| {
+                let length = rail.length();
+                let reach = (DEFAULT_WINDOW_LENGTH / 2.0 - walked).min(length);
+
+                Some(match leaving_direction {
+                    Direction::Forward => (0.0, reach),
+                    Direction::Backward => (length - reach, length),
+                })
+            },
+        ))
+    }
+
+    // walks the track from the stop node in all directions up to
+    // max_distance and collects the segments `segment` picks from each rail,
+    // given the direction the rail is left in and the m walked up to it
+    fn around(
+        data: &OSMData,
+        leaving: &[SwitchLeg],
+        max_distance: f64,
+        mut segment: impl FnMut(&Path, Direction, f64) -> Option<(f64, f64)>,
+    ) -> Self {
+        let mut segments = HashMap::new();
+        let mut visited = HashSet::new();
+        let mut queue: VecDeque<(SwitchLeg, f64)> = leaving.iter().map(|leg| (*leg, 0.0)).collect();
+
+        while let Some(((id, leaving_direction), walked)) = queue.pop_front() {
+            let Some(rail) = data.rails.get(&id) else {
+                continue;
+            };
+            if !visited.insert(id) {
+                continue;
+            }
+
+            if let Some(part) = segment(rail, leaving_direction, walked) {
+                segments.insert(id, part);
+            }
+
+            let walked = walked + rail.length();
+            if walked < max_distance {
+                for next in rail.possible_connections_by_direction(leaving_direction) {
+                    queue.push_back((*next, walked));
+                }
+            }
+        }
+
+        let mut segments: Vec<(PathId, f64, f64)> = segments
+            .into_iter()
+            .map(|(id, (start, end))| (id, start, end))
+            .collect();
+        segments.sort_by_key(|(id, ..)| *id);
+
+        Self { segments }
+    }
+
+    pub fn contains(&self, data: &OSMData, location: &TrackLocation) -> bool {
+        let position = offset(data, location);
+
+        self.segments.iter().any(|(id, start, end)| {
+            *id == location.id
+                && position >= start - WINDOW_TOLERANCE
+                && position <= end + WINDOW_TOLERANCE
+        })
+    }
+}
+
+// m from `location` along its travel direction to the first part of
+// `window`, 0 inside of it. none if it is not found within max_distance
+pub fn distance_to_window(
+    data: &OSMData,
+    switches: &SwitchPositions,
+    location: &TrackLocation,
+    window: &StopWindow,
+    max_distance: f64,
+) -> Option<f64> {
+    let mut id = location.id;
+    let mut leaving_direction = location.travel_direction;
+    let mut from = Some(offset(data, location));
+    let mut walked = 0.0;
+
+    while walked < max_distance {
+        let rail = data.rails.get(&id)?;
+        let length = rail.length();
+        let start = from.unwrap_or(match leaving_direction {
+            Direction::Forward => 0.0,
+            Direction::Backward => length,
+        });
+        // m from start in the direction the rail is left
+        let ahead = |position: f64| match leaving_direction {
+            Direction::Forward => position - start,
+            Direction::Backward => start - position,
+        };
+
+        let nearest = window
+            .segments
+            .iter()
+            .filter(|(segment_id, ..)| *segment_id == id)
+            .map(|(_, segment_start, segment_end)| match leaving_direction {
+                Direction::Forward => (ahead(*segment_start), ahead(*segment_end)),
+                Direction::Backward => (ahead(*segment_end), ahead(*segment_start)),
+            })
+            .filter(|(_, exit)| *exit >= -WINDOW_TOLERANCE)
+            .map(|(entry, _)| entry.max(0.0))
+            .min_by(|a, b| a.total_cmp(b));
+
+        if let Some(distance) = nearest {
+            return Some(walked + distance);
+        }
+
+        walked += ahead(match leaving_direction {
+            Direction::Forward => length,
+            Direction::Backward => 0.0,
+        });
+
+        let possible = rail.possible_connections_by_direction(leaving_direction);
+        let (next_id, next_direction) = possible
+            .get(switches.position(&(id, leaving_direction)))
+            .or(possible.first())?;
+
+        id = *next_id;
+        leaving_direction = *next_direction;
+        from = None;
+    }
+
+    None
+}
+
+pub fn resolve_stop_windows(
+    mut commands: Commands,
+    data: Res<OSMData>,
+    scenario_data: Res<ScenarioData>,
+) {
+    let windows = scenario_data
+        .stops
+        .iter()
+        .map(|stop| {
+            let window = StopWindow::resolve(&data, stop.node_id);
+            if window.is_none() {
+                log::warn!("stop {} ({}) is not on the track", stop.name, stop.node_id);
+            }
+            window
+        })
+        .collect();
+
+    commands.insert_resource(StopWindows(windows));
+}
+
+pub struct StopPlugin;
+
+impl Plugin for StopPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<StopWindows>().add_systems(
+            FixedUpdate,
+            resolve_stop_windows
+                .run_if(
+                    resource_exists::<OSMData>
+                        .and_then(resource_exists::<ScenarioData>)
+                        .and_then(
+                            resource_changed::<OSMData>.or_else(resource_changed::<ScenarioData>),
+                        ),
+                )
+                .before(PhysicsSet::Aggregate),
+        );
+    }
+}
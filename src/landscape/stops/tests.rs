@@ -0,0 +1,147 @@
+use super::*;
+use crate::landscape::{
+    coordinate_point::Coordinates,
+    open_street_map::{BuildingData, SectionData},
+};
+use coverage_helper::test;
+
+// a line of three rails of 500 m from node 0 to node 3
+#[coverage(off)]
+fn gen_data() -> OSMData {
+    let mut rails = std::collections::HashMap::default();
+    for i in 0..3 {
+        let mut path = Path {
+            start_id: i,
+            end_id: i + 1,
+            start_coords: CoordinatePoint(i as f64 * 500.0, 0.0),
+            end_coords: CoordinatePoint((i + 1) as f64 * 500.0, 0.0),
+            ..default()
+        };
+        if i > 0 {
+            path.backward_connections = vec![((i - 1, i), Direction::Backward)];
+        }
+        if i < 2 {
+            path.forward_connections = vec![((i + 1, i + 2), Direction::Forward)];
+        }
+        rails.insert(path.id(), path);
+    }
+    OSMData { rails, ..default() }
+}
+
+// a 200 m platform beside the track, starting 50 m before node 1
+#[coverage(off)]
+fn with_platform(mut data: OSMData) -> OSMData {
+    let outline = vec![
+        CoordinatePoint(450.0, 3.0),
+        CoordinatePoint(650.0, 3.0),
+        CoordinatePoint(650.0, 8.0),
+        CoordinatePoint(450.0, 8.0),
+    ];
+    data.sections.insert(
+        outline[0].sector_coordinates(),
+        SectionData {
+            buildings: vec![BuildingData {
+                building_type: BuildingType::Platform,
+                coordinates: Coordinates(outline),
+                ..default()
+            }],
+            ..default()
+        },
+    );
+    data
+}
+
+#[coverage(off)]
+fn location(id: PathId, distance: f64, travel_direction: Direction) -> TrackLocation {
+    TrackLocation {
+        id,
+        distance,
+        travel_direction,
+    }
+}
+
+#[test]
+fn default_window_around_the_node() {
+    let window = StopWindow::resolve(&gen_data(), 1).unwrap();
+
+    assert_eq!(
+        window.segments,
+        vec![((0, 1), 400.0, 500.0), ((1, 2), 0.0, 100.0)]
+    );
+    assert_eq!(StopWindow::resolve(&gen_data(), 42), None);
+}
+
+#[test]
+fn window_along_the_platform() {
+    let window = StopWindow::resolve(&with_platform(gen_data()), 1).unwrap();
+
+    assert_eq!(
+        window.segments,
+        vec![((0, 1), 445.0, 500.0), ((1, 2), 0.0, 155.0)]
+    );
+}
+
+#[test]
+fn platforms_far_away_are_ignored() {
+    let window = StopWindow::resolve(&with_platform(gen_data()), 2).unwrap();
+
+    assert_eq!(
+        window.segments,
+        vec![((1, 2), 400.0, 500.0), ((2, 3), 0.0, 100.0)]
+    );
+}
+
+#[test]
+fn contains_locations() {
+    let data = gen_data();
+    let window = StopWindow::resolve(&data, 1).unwrap();
+
+    assert!(window.contains(&data, &location((1, 2), 50.0, Direction::Forward)));
+    assert!(!window.contains(&data, &location((1, 2), 150.0, Direction::Forward)));
+    assert!(window.contains(&data, &location((0, 1), 50.0, Direction::Backward)));
+    assert!(!window.contains(&data, &location((0, 1), 50.0, Direction::Forward)));
+}
+
+#[test]
+fn distance_ahead() {
+    let data = gen_data();
+    let switches = SwitchPositions::default();
+    let window = StopWindow::resolve(&data, 1).unwrap();
+    let distance =
+        |location: TrackLocation| distance_to_window(&data, &switches, &location, &window, 5000.0);
+
+    assert_eq!(
+        distance(location((0, 1), 100.0, Direction::Forward)),
+        Some(300.0)
+    );
+    assert_eq!(
+        distance(location((1, 2), 50.0, Direction::Forward)),
+        Some(0.0)
+    );
+    assert_eq!(
+        distance(location((2, 3), 0.0, Direction::Backward)),
+        Some(900.0)
+    );
+    // already passed
+    assert_eq!(distance(location((1, 2), 200.0, Direction::Forward)), None);
+}
+
+#[test]
+fn outline_distance() {
+    let square = [
+        CoordinatePoint(0.0, 0.0),
+        CoordinatePoint(10.0, 0.0),
+        CoordinatePoint(10.0, 10.0),
+        CoordinatePoint(0.0, 10.0),
+    ];
+
+    assert_eq!(distance_to_outline(CoordinatePoint(5.0, 5.0), &square), 0.0);
+    assert_eq!(
+        distance_to_outline(CoordinatePoint(5.0, -3.0), &square),
+        3.0
+    );
+    assert_eq!(
+        distance_to_outline(CoordinatePoint(13.0, 14.0), &square),
+        5.0
+    );
+}
@@ -0,0 +1,149 @@
+#[cfg(test)]
+mod tests;
+
+use super::{
+    route::{plan_route, set_route_switches, Route},
+    OSMData, PathId,
+};
+use crate::{
+    scenario::ScenarioData,
+    train::{Direction, PhysicsSet, TrackLocation},
+};
+use bevy::{prelude::*, utils::HashMap};
+
+// a rail and the direction in which it is left. a leg with more than one
+// connection is the facing side of a switch
+pub type SwitchLeg = (PathId, Direction);
+
+#[derive(Component, Debug, Clone)]
+pub struct Switch {
+    pub leg: SwitchLeg,
+    // the connections of the leg, the position selects one of them
+    pub branches: Vec<SwitchLeg>,
+    pub position: usize,
+}
+
+impl Switch {
+    pub fn branch(&self) -> SwitchLeg {
+        self.branches[self.position]
+    }
+
+    // whether a vehicle stands on the leg or one of the branches
+    pub fn is_occupied<'a>(&self, locations: impl IntoIterator<Item = &'a TrackLocation>) -> bool {
+        locations.into_iter().any(|location| {
+            location.id == self.leg.0 || self.branches.iter().any(|(id, _)| location.id == *id)
+        })
+    }
+
+    // the blades can not move under a train, returns whether they did
+    pub fn throw<'a>(&mut self, locations: impl IntoIterator<Item = &'a TrackLocation>) -> bool {
+        if self.is_occupied(locations) {
+            return false;
+        }
+
+        self.position = (self.position + 1) % self.branches.len();
+        true
+    }
+
+    pub fn set_to(&mut self, branch: SwitchLeg) {
+        if let Some(position) = self.branches.iter().position(|b| *b == branch) {
+            self.position = position;
+        }
+    }
+}
+
+// positions of all switches by their leg, read by the trains moving over
+// them. legs without an entry lead to their first connection
+#[derive(Resource, Default, Debug)]
+pub struct SwitchPositions(pub HashMap<SwitchLeg, usize>);
+
+impl SwitchPositions {
+    pub fn position(&self, leg: &SwitchLeg) -> usize {
+        self.0.get(leg).copied().unwrap_or_default()
+    }
+}
+
+// a train ran through a switch from one of its branches while the switch was
+// set to another one
+#[derive(Event, Debug, Clone, PartialEq)]
+pub struct SwitchTrailed {
+    pub train: Entity,
+    pub leg: SwitchLeg,
+    // the branch the train came from
+    pub branch: SwitchLeg,
+}
+
+pub fn spawn_switches(
+    mut commands: Commands,
+    data: Res<OSMData>,
+    existing: Query<Entity, With<Switch>>,
+    mut positions: ResMut<SwitchPositions>,
+) {
+    for entity in existing.iter() {
+        commands.entity(entity).despawn();
+    }
+    positions.0.clear();
+
+    for rail in data.rails.values() {
+        for direction in [Direction::Forward, Direction::Backward] {
+            let branches = rail.possible_connections_by_direction(direction);
+
+            if branches.len() > 1 {
+                commands.spawn(Switch {
+                    leg: (rail.id(), direction),
+                    branches: branches.clone(),
+                    position: 0,
+                });
+            }
+        }
+    }
+}
+
+pub fn update_switch_positions(
+    switches: Query<&Switch, Changed<Switch>>,
+    mut positions: ResMut<SwitchPositions>,
+) {
+    for switch in switches.iter() {
+        positions.0.insert(switch.leg, switch.position);
+    }
+}
+
+// trailing forces the blades over to the branch the train came from
+pub fn trail_switches(mut events: EventReader<SwitchTrailed>, mut switches: Query<&mut Switch>) {
+    for event in events.read() {
+        for mut switch in switches.iter_mut() {
+            if switch.leg == event.leg {
+                log::warn!("switch {:?} trailed by {:?}", event.leg, event.train);
+                switch.set_to(event.branch);
+            }
+        }
+    }
+}
+
+pub struct SwitchPlugin;
+
+impl Plugin for SwitchPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SwitchPositions>()
+            .add_event::<SwitchTrailed>()
+            .add_systems(
+                FixedUpdate,
+                (
+                    spawn_switches.run_if(resource_exists_and_changed::<OSMData>),
+                    plan_route.run_if(
+                        resource_exists::<OSMData>
+                            .and_then(resource_exists::<ScenarioData>)
+                            .and_then(
+                                resource_changed::<OSMData>
+                                    .or_else(resource_changed::<ScenarioData>),
+                            ),
+                    ),
+                    set_route_switches.run_if(resource_exists_and_changed::<Route>),
+                    update_switch_positions,
+                )
+                    .chain()
+                    .before(PhysicsSet::Aggregate),
+            )
+            .add_systems(FixedUpdate, trail_switches.after(PhysicsSet::Locate));
+    }
+}
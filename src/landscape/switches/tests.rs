@@ -0,0 +1,134 @@
+use super::*;
+use crate::landscape::{CoordinatePoint, Path};
+use coverage_helper::test;
+
+#[coverage(off)]
+fn gen_data() -> OSMData {
+    let mut rails = std::collections::HashMap::default();
+    rails.insert(
+        (0, 1),
+        Path {
+            start_id: 0,
+            end_id: 1,
+            start_coords: CoordinatePoint(0.0, 0.0),
+            end_coords: CoordinatePoint(100.0, 0.0),
+            forward_connections: vec![((1, 2), Direction::Forward), ((1, 3), Direction::Forward)],
+            ..default()
+        },
+    );
+    for id in [(1, 2), (1, 3)] {
+        rails.insert(
+            id,
+            Path {
+                start_id: id.0,
+                end_id: id.1,
+                start_coords: CoordinatePoint(100.0, 0.0),
+                end_coords: CoordinatePoint(200.0, id.1 as f64),
+                backward_connections: vec![((0, 1), Direction::Backward)],
+                ..default()
+            },
+        );
+    }
+    OSMData { rails, ..default() }
+}
+
+#[coverage(off)]
+fn setup() -> App {
+    let mut app = App::new();
+    app.insert_resource(gen_data())
+        .init_resource::<SwitchPositions>()
+        .add_event::<SwitchTrailed>()
+        .add_systems(
+            Update,
+            (
+                spawn_switches.run_if(resource_exists_and_changed::<OSMData>),
+                update_switch_positions,
+                trail_switches,
+            )
+                .chain(),
+        );
+    app.update();
+    app
+}
+
+#[coverage(off)]
+fn switch(app: &mut App) -> Switch {
+    app.world_mut()
+        .query::<&Switch>()
+        .single(app.world())
+        .clone()
+}
+
+#[test]
+fn spawns_a_switch_per_facing_leg() {
+    let mut app = setup();
+
+    let switch = switch(&mut app);
+    assert_eq!(switch.leg, ((0, 1), Direction::Forward));
+    assert_eq!(switch.branch(), ((1, 2), Direction::Forward));
+
+    // reloading the map replaces the switches
+    app.world_mut().resource_mut::<OSMData>().set_changed();
+    app.update();
+    assert_eq!(
+        app.world_mut().query::<&Switch>().iter(app.world()).count(),
+        1
+    );
+}
+
+#[test]
+fn throwing_updates_positions() {
+    let mut app = setup();
+
+    let mut switch = app.world_mut().query::<&mut Switch>();
+    assert!(switch.single_mut(app.world_mut()).throw([]));
+    app.update();
+
+    let leg = ((0, 1), Direction::Forward);
+    assert_eq!(app.world().resource::<SwitchPositions>().position(&leg), 1);
+
+    let mut switch = app.world_mut().query::<&mut Switch>();
+    assert!(switch.single_mut(app.world_mut()).throw([]));
+    app.update();
+
+    assert_eq!(app.world().resource::<SwitchPositions>().position(&leg), 0);
+}
+
+#[test]
+fn occupied_switches_can_not_be_thrown() {
+    let mut switch = switch(&mut setup());
+    let on_branch = TrackLocation {
+        id: (1, 3),
+        distance: 50.0,
+        travel_direction: Direction::Forward,
+    };
+    let elsewhere = TrackLocation {
+        id: (4, 5),
+        ..on_branch.clone()
+    };
+
+    assert!(!switch.throw([&elsewhere, &on_branch]));
+    assert_eq!(switch.position, 0);
+
+    assert!(switch.throw([&elsewhere]));
+    assert_eq!(switch.position, 1);
+}
+
+#[test]
+fn trailing_forces_the_switch() {
+    let mut app = setup();
+
+    app.world_mut().send_event(SwitchTrailed {
+        train: Entity::PLACEHOLDER,
+        leg: ((0, 1), Direction::Forward),
+        branch: ((1, 3), Direction::Forward),
+    });
+    app.update();
+
+    assert_eq!(switch(&mut app).branch(), ((1, 3), Direction::Forward));
+
+    // the blades only move to branches of the switch
+    let mut unknown = switch(&mut app);
+    unknown.set_to(((4, 5), Direction::Forward));
+    assert_eq!(unknown.position, 1);
+}
@@ -0,0 +1,276 @@
+#[cfg(test)]
+mod tests;
+
+use super::{
+    signals::{offset, Aspect, Signal},
+    OSMData, PathId, SignalKind, SwitchPositions,
+};
+use crate::train::{Direction, TrackLocation};
+use bevy::prelude::*;
+
+// m, the 500 Hz magnet lies this far before its signal or restriction
+const MAGNET_500_HZ_DISTANCE: f64 = 250.0;
+// m, the 1000 Hz magnet lies this far before a speed restriction
+const MAGNET_1000_HZ_DISTANCE: f64 = 1000.0;
+// km/h, only restrictions to this speed or below are protected
+const PROTECTED_RESTRICTION: f32 = 100.0;
+// km/h, smaller reductions of the line speed are not protected
+const MIN_PROTECTED_REDUCTION: f32 = 20.0;
+// m, steps a walk along the track over a rail joint
+const JOINT_STEP: f64 = 1e-6;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MagnetFrequency {
+    // restrictive, 250 m before the danger point
+    Hz500,
+    // warning, at the distant signal
+    Hz1000,
+    // stop, at the main signal
+    Hz2000,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MagnetSource {
+    // active while the signal shows the aspect
+    Signal { entity: Entity, aspect: Aspect },
+    // always active
+    SpeedRestriction,
+}
+
+// an intermittent train protection magnet beside the track
+#[derive(Component, Debug, Clone, PartialEq)]
+pub struct TrackMagnet {
+    pub rail: PathId,
+    // m from the start of the rail
+    pub offset: f64,
+    // trains passing in this direction along the rail are affected
+    pub direction: Direction,
+    pub frequency: MagnetFrequency,
+    pub source: MagnetSource,
+}
+
+impl TrackMagnet {
+    fn at(
+        data: &OSMData,
+        location: &TrackLocation,
+        frequency: MagnetFrequency,
+        source: MagnetSource,
+    ) -> Self {
+        Self {
+            rail: location.id,
+            offset: offset(data, location),
+            direction: location.travel_direction,
+            frequency,
+            source,
+        }
+    }
+
+    pub fn is_active(&self, aspect_of: impl Fn(Entity) -> Option<Aspect>) -> bool {
+        match self.source {
+            MagnetSource::Signal { entity, aspect } => aspect_of(entity) == Some(aspect),
+            MagnetSource::SpeedRestriction => true,
+        }
+    }
+}
+
+// `distance` m before the location, following the switches as they are set
+// when the map loads
+fn before(
+    data: &OSMData,
+    switches: &SwitchPositions,
+    location: &TrackLocation,
+    distance: f64,
+) -> TrackLocation {
+    let mut location = location.clone();
+    location.add_distance(data, switches, -distance);
+    location
+}
+
+pub fn signal_magnets(
+    data: &OSMData,
+    switches: &SwitchPositions,
+    entity: Entity,
+    signal: &Signal,
+) -> Vec<TrackMagnet> {
+    let mut magnets = vec![];
+    let source = |aspect| MagnetSource::Signal { entity, aspect };
+
+    if signal.kind != SignalKind::Main {
+        magnets.push(TrackMagnet::at(
+            data,
+            &signal.location,
+            MagnetFrequency::Hz1000,
+            source(Aspect::Caution),
+        ));
+    }
+
+    if signal.kind.is_main() {
+        let approach = before(data, switches, &signal.location, MAGNET_500_HZ_DISTANCE);
+        magnets.push(TrackMagnet::at(
+            data,
+            &approach,
+            MagnetFrequency::Hz500,
+            source(Aspect::Stop),
+        ));
+        magnets.push(TrackMagnet::at(
+            data,
+            &signal.location,
+            MagnetFrequency::Hz2000,
+            source(Aspect::Stop),
+        ));
+    }
+
+    magnets
+}
+
+// magnets in front of every tagged speed limit that is considerably lower
+// than the line speed before it
+pub fn restriction_magnets(data: &OSMData, switches: &SwitchPositions) -> Vec<TrackMagnet> {
+    let mut magnets = vec![];
+
+    for rail in data.rails.values() {
+        let Some(limit) = rail.tags.max_speed else {
+            continue;
+        };
+        if limit > PROTECTED_RESTRICTION {
+            continue;
+        }
+
+        for direction in [Direction::Forward, Direction::Backward] {
+            let is_reduction = rail
+                .possible_connections_by_direction(direction.opposite())
+                .iter()
+                .filter_map(|(id, _)| data.rails.get(id))
+                .any(|previous| previous.speed_limit() - limit >= MIN_PROTECTED_REDUCTION);
+            if !is_reduction {
+                continue;
+            }
+
+            let start = TrackLocation {
+                id: rail.id(),
+                distance: 0.0,
+                travel_direction: direction,
+            };
+
+            for (frequency, distance) in [
+                (MagnetFrequency::Hz1000, MAGNET_1000_HZ_DISTANCE),
+                (MagnetFrequency::Hz500, MAGNET_500_HZ_DISTANCE),
+            ] {
+                magnets.push(TrackMagnet::at(
+                    data,
+                    &before(data, switches, &start, distance),
+                    frequency,
+                    MagnetSource::SpeedRestriction,
+                ));
+            }
+        }
+    }
+
+    magnets
+}
+
+// the parts of the rails a vehicle moved over from `previous` to `current`,
+// from offset to offset. the distance (m) is along the travel direction of
+// `previous` and bounds the walk
+pub(super) fn covered(
+    data: &OSMData,
+    switches: &SwitchPositions,
+    previous: &TrackLocation,
+    current: &TrackLocation,
+    distance: f64,
+) -> Vec<(PathId, f64, f64)> {
+    let mut location = previous.clone();
+    let mut left = distance;
+    let mut parts = vec![];
+
+    while let Some(rail) = data.rails.get(&location.id) {
+        let from = offset(data, &location);
+        if location.id == current.id {
+            parts.push((location.id, from, offset(data, current)));
+            break;
+        }
+
+        // m along the travel direction to the end of the rail ahead
+        let to_end = if left >= 0.0 {
+            rail.length() - location.distance
+        } else {
+            -location.distance
+        };
+        if left.abs() <= to_end.abs() {
+            location.add_distance(data, switches, left);
+            parts.push((location.id, from, offset(data, &location)));
+            break;
+        }
+
+        location.add_distance(data, switches, to_end);
+        parts.push((location.id, from, offset(data, &location)));
+
+        // over the joint onto the next rail, unless it is a buffer stop
+        let step = JOINT_STEP.copysign(left);
+        if location.add_distance(data, switches, step).overrun > 0.0 {
+            break;
+        }
+        left -= to_end + step;
+    }
+
+    parts
+}
+
+// magnets a vehicle passed over in their direction between two ticks, while
+// it moved the given distance (m) along the travel direction of `previous`
+pub fn passed_magnets(
+    data: &OSMData,
+    switches: &SwitchPositions,
+    magnets: &[(Entity, &TrackMagnet)],
+    previous: &TrackLocation,
+    current: &TrackLocation,
+    distance: f64,
+) -> Vec<Entity> {
+    let mut passed = vec![];
+
+    for (rail, from, to) in covered(data, switches, previous, current, distance) {
+        if from == to {
+            continue;
+        }
+        let direction = if to > from {
+            Direction::Forward
+        } else {
+            Direction::Backward
+        };
+
+        passed.extend(
+            magnets
+                .iter()
+                .filter(|(_, magnet)| magnet.rail == rail && magnet.direction == direction)
+                .filter(|(_, magnet)| match direction {
+                    Direction::Forward => magnet.offset > from && magnet.offset <= to,
+                    Direction::Backward => magnet.offset < from && magnet.offset >= to,
+                })
+                .map(|(entity, _)| *entity),
+        );
+    }
+
+    passed
+}
+
+pub fn spawn_track_magnets(
+    mut commands: Commands,
+    data: Res<OSMData>,
+    switches: Res<SwitchPositions>,
+    signals: Query<(Entity, &Signal)>,
+    existing: Query<Entity, With<TrackMagnet>>,
+) {
+    for entity in existing.iter() {
+        commands.entity(entity).despawn();
+    }
+
+    for (entity, signal) in signals.iter() {
+        for magnet in signal_magnets(&data, &switches, entity, signal) {
+            commands.spawn(magnet);
+        }
+    }
+
+    for magnet in restriction_magnets(&data, &switches) {
+        commands.spawn(magnet);
+    }
+}
@@ -0,0 +1,193 @@
+use super::*;
+use crate::landscape::{CoordinatePoint, Path};
+use coverage_helper::test;
+
+// a line of three rails of 1 km, the last one limited to 60 km/h
+#[coverage(off)]
+fn gen_data() -> OSMData {
+    let mut rails = std::collections::HashMap::default();
+    for i in 0..3 {
+        let mut path = Path {
+            start_id: i,
+            end_id: i + 1,
+            start_coords: CoordinatePoint(i as f64 * 1000.0, 0.0),
+            end_coords: CoordinatePoint((i + 1) as f64 * 1000.0, 0.0),
+            ..default()
+        };
+        if i > 0 {
+            path.backward_connections = vec![((i - 1, i), Direction::Backward)];
+        }
+        if i < 2 {
+            path.forward_connections = vec![((i + 1, i + 2), Direction::Forward)];
+        } else {
+            path.tags.max_speed = Some(60.0);
+        }
+        rails.insert(path.id(), path);
+    }
+    OSMData { rails, ..default() }
+}
+
+#[coverage(off)]
+fn location(id: PathId, distance: f64) -> TrackLocation {
+    TrackLocation {
+        id,
+        distance,
+        travel_direction: Direction::Forward,
+    }
+}
+
+#[coverage(off)]
+fn signal(kind: SignalKind) -> Signal {
+    Signal {
+        index: 0,
+        kind,
+        location: location((1, 2), 100.0),
+        aspect: Aspect::Stop,
+    }
+}
+
+#[test]
+fn magnets_of_signals() {
+    let data = gen_data();
+    let switches = SwitchPositions::default();
+    let entity = Entity::PLACEHOLDER;
+
+    let magnets = signal_magnets(&data, &switches, entity, &signal(SignalKind::Distant));
+    assert_eq!(magnets.len(), 1);
+    assert_eq!(magnets[0].frequency, MagnetFrequency::Hz1000);
+    assert_eq!(magnets[0].offset, 100.0);
+    assert_eq!(
+        magnets[0].source,
+        MagnetSource::Signal {
+            entity,
+            aspect: Aspect::Caution
+        }
+    );
+
+    let magnets = signal_magnets(&data, &switches, entity, &signal(SignalKind::Main));
+    assert_eq!(magnets.len(), 2);
+    // 250 m before the signal on the previous rail
+    assert_eq!(magnets[0].frequency, MagnetFrequency::Hz500);
+    assert_eq!(magnets[0].rail, (0, 1));
+    assert_eq!(magnets[0].offset, 850.0);
+    assert_eq!(magnets[1].frequency, MagnetFrequency::Hz2000);
+
+    let magnets = signal_magnets(&data, &switches, entity, &signal(SignalKind::Combined));
+    assert_eq!(magnets.len(), 3);
+}
+
+#[test]
+fn activity_follows_the_aspect() {
+    let data = gen_data();
+    let magnets = signal_magnets(
+        &data,
+        &SwitchPositions::default(),
+        Entity::PLACEHOLDER,
+        &signal(SignalKind::Main),
+    );
+
+    assert!(magnets[1].is_active(|_| Some(Aspect::Stop)));
+    assert!(!magnets[1].is_active(|_| Some(Aspect::Clear)));
+    assert!(!magnets[1].is_active(|_| None));
+}
+
+#[test]
+fn magnets_of_speed_restrictions() {
+    let data = gen_data();
+    let mut magnets = restriction_magnets(&data, &SwitchPositions::default());
+    magnets.sort_by(|a, b| a.offset.total_cmp(&b.offset));
+
+    assert_eq!(magnets.len(), 2);
+    assert_eq!(magnets[0].frequency, MagnetFrequency::Hz1000);
+    assert_eq!((magnets[0].rail, magnets[0].offset), ((1, 2), 0.0));
+    assert_eq!(magnets[1].frequency, MagnetFrequency::Hz500);
+    assert_eq!((magnets[1].rail, magnets[1].offset), ((1, 2), 750.0));
+    assert!(magnets[1].is_active(|_| None));
+    assert!(magnets
+        .iter()
+        .all(|magnet| magnet.direction == Direction::Forward));
+}
+
+#[test]
+fn passing_magnets() {
+    let data = gen_data();
+    let magnet = |rail, offset, direction| TrackMagnet {
+        rail,
+        offset,
+        direction,
+        frequency: MagnetFrequency::Hz1000,
+        source: MagnetSource::SpeedRestriction,
+    };
+    let magnets = [
+        magnet((0, 1), 990.0, Direction::Forward),
+        magnet((1, 2), 5.0, Direction::Forward),
+        magnet((1, 2), 5.0, Direction::Backward),
+    ];
+    let entities = [
+        Entity::from_raw(1),
+        Entity::from_raw(2),
+        Entity::from_raw(3),
+    ];
+    let magnets: Vec<_> = entities.into_iter().zip(magnets.iter()).collect();
+
+    // across the rail joint
+    let switches = SwitchPositions::default();
+    let passed = passed_magnets(
+        &data,
+        &switches,
+        &magnets,
+        &location((0, 1), 980.0),
+        &location((1, 2), 10.0),
+        30.0,
+    );
+    assert_eq!(passed, vec![entities[0], entities[1]]);
+
+    // and back
+    let passed = passed_magnets(
+        &data,
+        &switches,
+        &magnets,
+        &location((1, 2), 10.0),
+        &location((0, 1), 980.0),
+        -30.0,
+    );
+    assert_eq!(passed, vec![entities[2]]);
+
+    // standing on a magnet does not trigger it again
+    let passed = passed_magnets(
+        &data,
+        &switches,
+        &magnets,
+        &location((1, 2), 5.0),
+        &location((1, 2), 5.0),
+        0.0,
+    );
+    assert!(passed.is_empty());
+
+    // travelling backward along the rail
+    let backward = |distance| TrackLocation {
+        id: (1, 2),
+        distance,
+        travel_direction: Direction::Backward,
+    };
+    let passed = passed_magnets(
+        &data,
+        &switches,
+        &magnets,
+        &backward(990.0),
+        &backward(996.0),
+        6.0,
+    );
+    assert_eq!(passed, vec![entities[2]]);
+
+    // over a whole rail within one tick
+    let passed = passed_magnets(
+        &data,
+        &switches,
+        &magnets,
+        &location((0, 1), 980.0),
+        &location((2, 3), 10.0),
+        1030.0,
+    );
+    assert_eq!(passed, vec![entities[0], entities[1]]);
+}
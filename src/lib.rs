@@ -0,0 +1,18 @@
+#![feature(coverage_attribute)]
+
+#[cfg(feature = "graphics")]
+pub mod camera;
+pub mod headless;
+pub mod landscape;
+#[cfg(feature = "graphics")]
+mod mesh;
+pub mod scenario;
+pub mod train;
+
+use landscape::{BALLAST_HEIGHT, RAIL_HEIGHT};
+
+pub const TRAIN_HEIGHT_OFFSET: f32 = BALLAST_HEIGHT + RAIL_HEIGHT;
+
+// marker methods for system ordering
+#[coverage(off)]
+pub fn moving_things() {}
@@ -1,10 +1,5 @@
 #![feature(coverage_attribute)]
 
-mod camera;
-mod landscape;
-mod mesh;
-mod scenario;
-mod train;
 mod ui;
 
 use bevy::{
@@ -17,13 +12,7 @@ use bevy::{
     },
 };
 use bevy_egui::EguiPlugin;
-use landscape::{BALLAST_HEIGHT, RAIL_HEIGHT};
-
-const TRAIN_HEIGHT_OFFSET: f32 = BALLAST_HEIGHT + RAIL_HEIGHT;
-
-// marker methods for system ordering
-#[coverage(off)]
-fn moving_things() {}
+use rustrail::{camera, landscape, moving_things, scenario, train};
 
 #[coverage(off)]
 fn main() {
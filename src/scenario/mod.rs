@@ -1,7 +1,7 @@
 #[cfg(test)]
 mod tests;
 
-use crate::train::Direction;
+use crate::train::{Direction, RailCondition, Vigilance};
 use bevy::prelude::*;
 use serde::Deserialize;
 
@@ -11,6 +11,10 @@ use serde::Deserialize;
 pub struct ScenarioInfo {
     pub name: String,
     pub starting_direction: Direction,
+    #[serde(default)]
+    pub rail_condition: RailCondition,
+    #[serde(default)]
+    pub vigilance: Vigilance,
 }
 
 #[derive(Default, Debug, Deserialize)]
@@ -21,8 +25,6 @@ pub struct ScenarioMap {
 
 #[derive(Default, Debug, Deserialize)]
 pub struct ScenarioStop {
-    // TODO: remove when used
-    #[allow(dead_code)]
     pub name: String,
     pub node_id: i64,
 }
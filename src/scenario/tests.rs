@@ -9,3 +9,22 @@ fn loading_data() {
     assert_eq!(data.map.osm_data, "assets/rheinland-pfalz-latest.osm.pbf");
     assert_eq!(data.stops.len(), 16);
 }
+
+#[test]
+fn default_rail_condition() {
+    let data = ScenarioData::load_from_file("assets/scenarios/rb35.toml");
+
+    assert_eq!(data.info.rail_condition, RailCondition::Dry);
+}
+
+#[test]
+fn vigilance_setting() {
+    let data = ScenarioData::load_from_file("assets/scenarios/rb35.toml");
+    assert_eq!(data.info.vigilance, Vigilance::Enabled);
+
+    let info: ScenarioInfo = toml::from_str(
+        "name = \"Test\"\nstarting_direction = \"Forward\"\nvigilance = \"Disabled\"",
+    )
+    .unwrap();
+    assert_eq!(info.vigilance, Vigilance::Disabled);
+}
@@ -12,14 +12,22 @@ pub struct TrainBundle {
     composition: TrainComposition,
     speed: Speed,
     max_speed: MaxSpeed,
+    speed_limit: SpeedLimit,
+    overspeed: Overspeed,
+    cab_signalling: CabSignalling,
+    station_stops: StationStops,
     mass: Mass,
+    rotating_mass: RotatingMass,
     acceleration: Acceleration,
     distance: Distance,
     force_driving: ForceDriving,
     force_braking: ForceBraking,
     force_friction: ForceFriction,
     force_air_resistance: ForceAirResistance,
-    air_pressure: AirPressure,
+    force_curve_resistance: ForceCurveResistance,
+    force_gradient: ForceGradient,
+    energy_meter: EnergyMeter,
+    brake_pipe: BrakePipe,
 }
 
 impl TrainBundle {
@@ -37,19 +45,40 @@ pub struct EngineBundle {
     engine: Engine,
     name: Name,
     mass: Mass,
+    rotating_mass: RotatingMass,
+    adhesive_mass: AdhesiveMass,
     max_power: MaxPower,
+    tractive_effort: TractiveEffort,
+    dynamic_brake: DynamicBrake,
+    energy_meter: EnergyMeter,
+    steam_engine: SteamEngine,
+    steam_controls: SteamControls,
+    boiler: Boiler,
+    supplies: Supplies,
+    diesel_engine: DieselEngine,
+    diesel_state: DieselState,
     max_speed: MaxSpeed,
     speed: Speed,
     dimension: Dimension,
+    resistance: ResistanceCoefficients,
     throttle_lever: ThrottleLever,
     brake_lever: BrakeLever,
+    pzb: Pzb,
+    sifa: Sifa,
+    wheel_slip: WheelSlip,
+    sanding: Sanding,
     force_driving: ForceDriving,
     force_braking: ForceBraking,
     force_friction: ForceFriction,
     force_air_resistance: ForceAirResistance,
+    force_curve_resistance: ForceCurveResistance,
+    force_gradient: ForceGradient,
     load_model_file: LoadModelFile,
     air_pressure: AirPressure,
     air_pressure_delta: AirPressureDelta,
+    auxiliary_reservoir: AuxiliaryReservoir,
+    brake_cylinder: BrakeCylinder,
+    independent_brake_cylinder: IndependentBrakeCylinder,
 }
 
 #[derive(Deserialize)]
@@ -57,21 +86,75 @@ struct EngineData {
     file_name: String,
     max_speed: f32,
     mass: Mass,
+    // defaults to mass, i.e. all axles are driven
+    adhesive_mass: Option<AdhesiveMass>,
+    rotating_mass_factor: Option<f32>,
     max_power: MaxPower,
+    #[serde(default)]
+    tractive_effort: TractiveEffort,
+    #[serde(default)]
+    dynamic_brake: DynamicBrake,
+    #[serde(default)]
+    steam: SteamEngine,
+    // bunkers of tank engines
+    #[serde(default)]
+    supplies: Supplies,
+    #[serde(default)]
+    diesel: DieselEngine,
     dimension: Dimension,
+    #[serde(default)]
+    resistance: ResistanceCoefficients,
+    #[serde(default)]
+    sifa: SifaTiming,
 }
 
 impl EngineBundle {
     pub fn from_file(file_name: &str) -> Self {
         let data = std::fs::read_to_string(file_name).expect("file to be readable");
-        let data: EngineData = toml::from_str(&data).expect("engine to be loadable");
+        let mut data: EngineData = toml::from_str(&data).expect("engine to be loadable");
+        data.tractive_effort.sort();
+        data.dynamic_brake.sort();
+
+        let notches = if data.diesel.is_available() {
+            data.diesel.notches
+        } else {
+            data.tractive_effort.notches.len() as u8
+        };
 
         Self {
             load_model_file: LoadModelFile(format!("models/{}", data.file_name)),
             max_speed: MaxSpeed::from_kmh(data.max_speed),
+            adhesive_mass: data.adhesive_mass.unwrap_or(AdhesiveMass(data.mass.0)),
+            rotating_mass: RotatingMass::from_factor(
+                &data.mass,
+                data.rotating_mass_factor
+                    .unwrap_or(ENGINE_ROTATING_MASS_FACTOR),
+            ),
             mass: data.mass,
             max_power: data.max_power,
+            tractive_effort: data.tractive_effort,
+            dynamic_brake: data.dynamic_brake,
+            boiler: Boiler {
+                pressure: data.steam.max_boiler_pressure,
+            },
+            steam_engine: data.steam,
+            supplies: data.supplies,
+            throttle_lever: ThrottleLever {
+                notches,
+                ..default()
+            },
+            diesel_state: DieselState {
+                rpm: data.diesel.idle_rpm,
+                fuel: data.diesel.fuel_tank,
+                ..default()
+            },
+            diesel_engine: data.diesel,
             dimension: data.dimension,
+            resistance: data.resistance,
+            sifa: Sifa {
+                timing: data.sifa,
+                ..default()
+            },
             ..default()
         }
     }
@@ -81,21 +164,41 @@ impl EngineBundle {
 pub struct WagonBundle {
     wagon: Wagon,
     mass: Mass,
+    rotating_mass: RotatingMass,
+    payload: Payload,
     max_speed: MaxSpeed,
     speed: Speed,
     dimension: Dimension,
+    resistance: ResistanceCoefficients,
+    supplies: Supplies,
+    wheel_slip: WheelSlip,
+    force_braking: ForceBraking,
     force_friction: ForceFriction,
     force_air_resistance: ForceAirResistance,
+    force_curve_resistance: ForceCurveResistance,
+    force_gradient: ForceGradient,
     load_model_file: LoadModelFile,
     air_pressure: AirPressure,
+    auxiliary_reservoir: AuxiliaryReservoir,
+    brake_cylinder: BrakeCylinder,
 }
 
 #[derive(Deserialize)]
 struct WagonData {
     file_name: String,
     max_speed: f32,
-    mass: Mass,
+    // empty wagon, supplies and payload come on top
+    #[serde(alias = "mass")]
+    tare: Mass,
+    rotating_mass_factor: Option<f32>,
+    #[serde(default)]
+    payload: Payload,
     dimension: Dimension,
+    #[serde(default)]
+    resistance: ResistanceCoefficients,
+    // coal and water of tenders
+    #[serde(default)]
+    supplies: Supplies,
 }
 
 impl WagonBundle {
@@ -106,9 +209,23 @@ impl WagonBundle {
         Self {
             load_model_file: LoadModelFile(format!("models/{}", data.file_name)),
             max_speed: MaxSpeed::from_kmh(data.max_speed),
-            mass: data.mass,
+            rotating_mass: RotatingMass::from_factor(
+                &data.tare,
+                data.rotating_mass_factor
+                    .unwrap_or(WAGON_ROTATING_MASS_FACTOR),
+            ),
+            mass: Mass(data.tare.0 + data.supplies.coal + data.supplies.water),
+            payload: data.payload,
             dimension: data.dimension,
+            resistance: data.resistance,
+            supplies: data.supplies,
             ..default()
         }
     }
+
+    // share of the payload capacity, 0 is empty and 1 fully loaded
+    pub fn with_load(mut self, share: f32) -> Self {
+        self.payload.set_share(share, &mut self.mass);
+        self
+    }
 }
@@ -51,3 +51,129 @@ fn train_bundle_initializer() {
 
     app.world_mut().spawn(bundle);
 }
+
+#[test]
+fn resistance_coefficients() {
+    let engine = EngineBundle::from_file("assets/models/BR52.toml");
+    let wagon = WagonBundle::from_file("assets/models/eanos.toml");
+
+    assert!(engine.resistance.drag_coefficient > wagon.resistance.drag_coefficient);
+    assert!(engine.resistance.frontal_area > wagon.resistance.frontal_area);
+
+    let bundle = EngineBundle::default();
+    assert_eq!(bundle.resistance.rolling, 0.002);
+    assert_eq!(bundle.resistance.drag_coefficient, 0.8);
+    assert_eq!(bundle.resistance.frontal_area, 10.0);
+}
+
+#[test]
+fn adhesive_mass() {
+    let engine = EngineBundle::from_file("assets/models/BR111.toml");
+    assert_eq!(engine.adhesive_mass.0, engine.mass.0);
+
+    let engine = EngineBundle::from_file("assets/models/BR52.toml");
+    assert!(engine.adhesive_mass.0 < engine.mass.0);
+}
+
+#[test]
+fn tractive_effort_curve() {
+    let engine = EngineBundle::from_file("assets/models/BR147.toml");
+    assert_eq!(engine.tractive_effort.force(0.0, 1.0), Some(300_000.0));
+
+    let engine = EngineBundle::from_file("assets/models/BR52.toml");
+    assert_eq!(engine.tractive_effort.force(0.0, 1.0), None);
+}
+
+#[test]
+fn dynamic_brake() {
+    let engine = EngineBundle::from_file("assets/models/BR186.toml");
+    assert!(engine.dynamic_brake.is_available());
+    assert!(engine.dynamic_brake.regenerative);
+    assert_eq!(engine.dynamic_brake.force(0.0, 1.0), 0.0);
+
+    let engine = EngineBundle::from_file("assets/models/DGH500C.toml");
+    assert!(engine.dynamic_brake.is_available());
+    assert!(!engine.dynamic_brake.regenerative);
+
+    let engine = EngineBundle::from_file("assets/models/BR52.toml");
+    assert!(!engine.dynamic_brake.is_available());
+}
+
+#[test]
+fn steam_engine() {
+    let engine = EngineBundle::from_file("assets/models/BR52.toml");
+    assert!(engine.steam_engine.is_available());
+    assert_eq!(
+        engine.boiler.pressure,
+        engine.steam_engine.max_boiler_pressure
+    );
+    assert!(engine.supplies.is_empty());
+
+    let tender = WagonBundle::from_file("assets/models/BR52_tender.toml");
+    assert!(!tender.supplies.is_empty());
+    assert!(tender.mass.0 > tender.supplies.coal + tender.supplies.water);
+
+    let engine = EngineBundle::from_file("assets/models/BadenVIc.toml");
+    assert!(engine.steam_engine.is_available());
+    assert!(!engine.supplies.is_empty());
+
+    let engine = EngineBundle::from_file("assets/models/BR111.toml");
+    assert!(!engine.steam_engine.is_available());
+}
+
+#[test]
+fn diesel_engine() {
+    let engine = EngineBundle::from_file("assets/models/VT98.toml");
+    assert!(engine.diesel_engine.is_available());
+    assert_eq!(
+        engine.diesel_engine.transmission.kind,
+        TransmissionKind::Mechanical
+    );
+    assert_eq!(engine.diesel_engine.transmission.stages.len(), 6);
+    assert_eq!(engine.throttle_lever.notches, 6);
+    assert_eq!(engine.diesel_state.rpm, engine.diesel_engine.idle_rpm);
+    assert_eq!(engine.diesel_state.fuel, engine.diesel_engine.fuel_tank);
+
+    let engine = EngineBundle::from_file("assets/models/DGH500C.toml");
+    assert_eq!(
+        engine.diesel_engine.transmission.kind,
+        TransmissionKind::Hydraulic
+    );
+
+    let engine = EngineBundle::from_file("assets/models/BR111.toml");
+    assert!(!engine.diesel_engine.is_available());
+    assert_eq!(engine.throttle_lever.notches, 0);
+}
+
+#[test]
+fn wagon_load() {
+    let empty = WagonBundle::from_file("assets/models/eanos.toml");
+    let loaded = WagonBundle::from_file("assets/models/eanos.toml").with_load(1.0);
+    let tender = WagonBundle::from_file("assets/models/BR52_tender.toml").with_load(1.0);
+
+    assert_eq!(empty.mass.0, 24_450.0);
+    assert_eq!(loaded.mass.0, 90_000.0);
+    assert_eq!(tender.mass.0, 58_700.0);
+    // coal and water do not spin either
+    assert!((tender.rotating_mass.0 - 18_700.0 * 0.06).abs() < 0.1);
+
+    // the load does not spin
+    assert_eq!(empty.rotating_mass.0, loaded.rotating_mass.0);
+    assert!((empty.rotating_mass.0 - 24_450.0 * 0.06).abs() < 0.1);
+
+    let engine = EngineBundle::from_file("assets/models/BR52.toml");
+    assert!((engine.rotating_mass.0 - 84_000.0 * 0.08).abs() < 0.1);
+}
+
+#[test]
+fn sifa_timing() {
+    let engine = EngineBundle::from_file("assets/models/BR52.toml");
+    assert_eq!(engine.sifa.timing.interval, 60.0);
+    assert_eq!(
+        engine.sifa.timing.visual_warning,
+        SifaTiming::default().visual_warning
+    );
+
+    let engine = EngineBundle::from_file("assets/models/BR111.toml");
+    assert_eq!(engine.sifa.timing, SifaTiming::default());
+}
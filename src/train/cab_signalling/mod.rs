@@ -0,0 +1,117 @@
+#[cfg(test)]
+mod tests;
+
+use bevy::prelude::*;
+
+// continuous cab signalling after LZB and ETCS
+// m, how far ahead the movement authority and targets are looked up
+pub const CAB_SIGNALLING_LOOKAHEAD: f64 = 5000.0;
+// share of the full braking capability the permitted speed plans with
+const CAB_SERVICE_BRAKING_SHARE: f32 = 0.7;
+// km/h above the permitted speed
+const CAB_WARNING_MARGIN: f32 = 5.0;
+const CAB_INTERVENTION_MARGIN: f32 = 10.0;
+
+// m/s from which braking with the given deceleration after reaction_time
+// seconds gets down to target_speed within distance meters
+pub fn braking_curve_speed(
+    target_speed: f32,
+    distance: f64,
+    deceleration: f32,
+    reaction_time: f32,
+) -> f32 {
+    let reaction = deceleration * reaction_time;
+    let squared = reaction.powi(2) + target_speed.powi(2) + 2.0 * deceleration * distance as f32;
+
+    (squared.max(0.0).sqrt() - reaction).max(target_speed)
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum SupervisionStatus {
+    #[default]
+    Normal,
+    // above the permitted speed
+    Overspeed,
+    // above the warning speed, the driver hears a horn
+    Warning,
+    // the brakes were applied and hold until the train stands
+    Intervention,
+}
+
+// a speed the train has to be down to at some point ahead
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CabTarget {
+    // m/s
+    pub speed: f32,
+    // m
+    pub distance: f64,
+}
+
+#[derive(Component, Default, Debug)]
+pub struct CabSignalling {
+    // switched on by the driver, intermittent protection is suppressed then
+    pub active: bool,
+    // m to the end of the movement authority, none while the way is clear
+    pub authority: Option<f64>,
+    // the most restrictive target ahead
+    pub target: Option<CabTarget>,
+    // m/s
+    pub permitted: f32,
+    // m/s
+    pub warning: f32,
+    // m/s
+    pub intervention: f32,
+    pub status: SupervisionStatus,
+}
+
+impl CabSignalling {
+    // derives the curves from the line speed (m/s), the targets ahead, the
+    // deceleration (m/s²) the train brakes with in an emergency and the s
+    // from applying the brakes until they take effect
+    pub fn supervise(
+        &mut self,
+        ceiling: f32,
+        targets: &[CabTarget],
+        deceleration: f32,
+        build_up_time: f32,
+    ) {
+        let service = deceleration * CAB_SERVICE_BRAKING_SHARE;
+        let mut permitted = ceiling;
+        let mut emergency = ceiling + CAB_INTERVENTION_MARGIN / 3.6;
+        let mut target: Option<(f32, CabTarget)> = None;
+
+        for candidate in targets.iter().filter(|target| target.speed < ceiling) {
+            let curve =
+                braking_curve_speed(candidate.speed, candidate.distance, service, build_up_time);
+            permitted = permitted.min(curve);
+            emergency = emergency.min(braking_curve_speed(
+                candidate.speed,
+                candidate.distance,
+                deceleration,
+                build_up_time,
+            ));
+
+            if !target.is_some_and(|(restrictive, _)| restrictive <= curve) {
+                target = Some((curve, *candidate));
+            }
+        }
+
+        self.permitted = permitted;
+        self.warning = permitted + CAB_WARNING_MARGIN / 3.6;
+        self.intervention = emergency.max(permitted + CAB_INTERVENTION_MARGIN / 3.6);
+        self.target = target.map(|(_, target)| target);
+    }
+
+    // m/s
+    pub fn status_at(&self, speed: f32) -> SupervisionStatus {
+        if speed > self.intervention {
+            SupervisionStatus::Intervention
+        } else if speed > self.warning {
+            SupervisionStatus::Warning
+        } else if speed > self.permitted {
+            SupervisionStatus::Overspeed
+        } else {
+            SupervisionStatus::Normal
+        }
+    }
+}
@@ -0,0 +1,67 @@
+use super::*;
+use coverage_helper::test;
+
+#[test]
+fn braking_curves() {
+    // standing at a stop target
+    assert_eq!(braking_curve_speed(0.0, 0.0, 1.0, 4.0), 0.0);
+    assert!((braking_curve_speed(0.0, 100.0, 1.0, 0.0) - 200.0_f32.sqrt()).abs() < 1e-4);
+
+    // the reaction time is spent at the initial speed
+    let speed = braking_curve_speed(0.0, 100.0, 1.0, 4.0);
+    assert!((speed * 4.0 + speed.powi(2) / 2.0 - 100.0).abs() < 1e-3);
+
+    // never below the target speed
+    assert_eq!(braking_curve_speed(10.0, 0.0, 1.0, 4.0), 10.0);
+}
+
+#[test]
+fn cab_signalling_curves() {
+    let mut cab_signalling = CabSignalling::default();
+    let stop = |distance| CabTarget {
+        speed: 0.0,
+        distance,
+    };
+
+    // far away the line speed holds
+    cab_signalling.supervise(30.0, &[stop(1000.0)], 1.0, 4.0);
+    assert_eq!(cab_signalling.permitted, 30.0);
+    assert_eq!(cab_signalling.target, Some(stop(1000.0)));
+    assert!((cab_signalling.intervention - (30.0 + 10.0 / 3.6)).abs() < 1e-4);
+
+    cab_signalling.supervise(30.0, &[stop(100.0)], 1.0, 4.0);
+    assert!(cab_signalling.permitted < 10.0);
+    assert!(cab_signalling.warning > cab_signalling.permitted);
+    assert!(cab_signalling.intervention > cab_signalling.warning);
+
+    assert_eq!(cab_signalling.status_at(5.0), SupervisionStatus::Normal);
+    assert_eq!(
+        cab_signalling.status_at(cab_signalling.permitted + 0.5),
+        SupervisionStatus::Overspeed
+    );
+    assert_eq!(
+        cab_signalling.status_at(cab_signalling.warning + 0.5),
+        SupervisionStatus::Warning
+    );
+    assert_eq!(
+        cab_signalling.status_at(cab_signalling.intervention + 0.5),
+        SupervisionStatus::Intervention
+    );
+
+    // higher speeds ahead are no targets
+    let faster = CabTarget {
+        speed: 40.0,
+        distance: 100.0,
+    };
+    cab_signalling.supervise(30.0, &[faster], 1.0, 4.0);
+    assert_eq!(cab_signalling.target, None);
+    assert_eq!(cab_signalling.permitted, 30.0);
+
+    // the most restrictive target is shown
+    let slow = CabTarget {
+        speed: 10.0,
+        distance: 200.0,
+    };
+    cab_signalling.supervise(30.0, &[stop(2000.0), slow], 1.0, 4.0);
+    assert_eq!(cab_signalling.target, Some(slow));
+}
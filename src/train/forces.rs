@@ -16,3 +16,11 @@ pub struct ForceFriction(pub f32);
 #[derive(Component, Default, Debug, WrappedValue)]
 // N
 pub struct ForceAirResistance(pub f32);
+
+#[derive(Component, Default, Debug, WrappedValue)]
+// N, signed: positive pushes along the travel direction
+pub struct ForceGradient(pub f32);
+
+#[derive(Component, Default, Debug, WrappedValue)]
+// N
+pub struct ForceCurveResistance(pub f32);
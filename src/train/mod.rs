@@ -2,18 +2,35 @@
 mod tests;
 
 mod bundles;
+mod cab_signalling;
 mod forces;
 mod physics;
+mod pzb;
+#[cfg(feature = "graphics")]
 mod render;
+mod sifa;
 mod track_location;
 
-use bevy::{app::PluginGroupBuilder, prelude::*};
+use bevy::{app::PluginGroupBuilder, prelude::*, utils::HashSet};
 use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
 use wrapped_value_derive_macro::WrappedValue;
 
 pub use bundles::{EngineBundle, TrainBundle, WagonBundle};
-pub use forces::{ForceAirResistance, ForceBraking, ForceDriving, ForceFriction};
-pub use track_location::TrackLocation;
+pub use cab_signalling::{
+    braking_curve_speed, CabSignalling, CabTarget, SupervisionStatus, CAB_SIGNALLING_LOOKAHEAD,
+};
+pub use forces::{
+    ForceAirResistance, ForceBraking, ForceCurveResistance, ForceDriving, ForceFriction,
+    ForceGradient,
+};
+pub use physics::{PhysicsSet, TrainPhysicsPlugin};
+pub use pzb::{
+    Lamp, Pzb, PzbLamps, PzbMonitoring, PZB_1000_HZ_DISTANCE, PZB_500_HZ_DISTANCE,
+    PZB_ACKNOWLEDGE_TIME, PZB_CRAWLING_SPEED, PZB_CRAWLING_TIME,
+};
+pub use sifa::{Sifa, SifaStage, SifaTiming, Vigilance};
+pub use track_location::{PreviousTrackLocation, TrackLocation};
 
 #[derive(Component, Default)]
 pub struct Train;
@@ -61,7 +78,70 @@ impl MaxSpeed {
     }
 }
 
-#[derive(Debug, PartialEq, Serialize, Deserialize, Clone, Copy)]
+// km/h above the permitted speed before the penalty brake applies
+pub const OVERSPEED_TOLERANCE: f32 = 10.0;
+// m/s, below this a train counts as standing
+pub const STANDSTILL: f32 = 0.1;
+
+// permitted speed of a train, the line speed under all of its vehicles
+// capped by the slowest vehicle
+#[derive(Component, Default, Debug, PartialEq)]
+pub struct SpeedLimit {
+    // m/s, none while the train is off the track
+    pub current: Option<f32>,
+    // m/s, the next different limit ahead and the distance to it in m
+    pub next: Option<(f32, f64)>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct OverspeedViolation {
+    // m/s
+    pub limit: f32,
+    // m/s, highest speed reached
+    pub max_speed: f32,
+    // m driven above the limit
+    pub distance: f32,
+    pub penalty: bool,
+}
+
+#[derive(Component, Default, Debug)]
+pub struct Overspeed {
+    pub violations: Vec<OverspeedViolation>,
+    // whether the last violation is still going on
+    pub is_active: bool,
+}
+
+// a train calling at one of the scenario stops
+#[derive(Debug, Clone, PartialEq)]
+pub struct StopCall {
+    // index into the scenario stops
+    pub stop: usize,
+    // s since the start of the simulation
+    pub arrival: f32,
+    pub departure: Option<f32>,
+}
+
+#[derive(Component, Default, Debug)]
+pub struct StationStops {
+    pub calls: Vec<StopCall>,
+    // index of the stop the train is heading for or calling at
+    pub next: usize,
+    // part of the train is inside the window of the next stop
+    pub is_entered: bool,
+    // m from the leading vehicle to the window of the next stop, none if it
+    // is not found ahead
+    pub distance: Option<f64>,
+}
+
+impl StationStops {
+    pub fn is_calling(&self) -> bool {
+        self.calls
+            .last()
+            .is_some_and(|call| call.stop == self.next && call.departure.is_none())
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Hash, Serialize, Deserialize, Clone, Copy)]
 pub enum Direction {
     Forward,
     Backward,
@@ -87,14 +167,112 @@ pub struct ThrottleLever {
     // 0..1
     pub percentage: f32,
     pub direction: Direction,
+    // 0 for a stepless lever
+    pub notches: u8,
+}
+
+impl ThrottleLever {
+    pub fn notch(&self) -> u8 {
+        (self.percentage * self.notches as f32).round() as u8
+    }
+}
+
+const SERVICE_STEPS: u8 = 5;
+
+// positions of the driver's brake valve, from releasing to braking
+#[derive(Debug, Default, PartialEq, Clone, Copy, Deserialize)]
+pub enum BrakeValvePosition {
+    // fill stroke, feeds the brake pipe quickly
+    Release,
+    #[default]
+    Running,
+    // holds the brake pipe pressure
+    Lap,
+    // 1..=SERVICE_STEPS
+    Service(u8),
+    Emergency,
+}
+
+impl BrakeValvePosition {
+    pub fn all() -> Vec<Self> {
+        let mut positions = vec![Self::Release, Self::Running, Self::Lap];
+        positions.extend((1..=SERVICE_STEPS).map(Self::Service));
+        positions.push(Self::Emergency);
+        positions
+    }
+
+    fn index(&self) -> usize {
+        Self::all()
+            .iter()
+            .position(|position| position == self)
+            .expect("brake valve position to be valid")
+    }
+
+    // one step towards braking
+    pub fn next(&self) -> Self {
+        let positions = Self::all();
+        positions[(self.index() + 1).min(positions.len() - 1)]
+    }
+
+    // one step towards releasing
+    pub fn previous(&self) -> Self {
+        Self::all()[self.index().saturating_sub(1)]
+    }
+
+    pub fn is_braking(&self) -> bool {
+        matches!(self, Self::Service(_) | Self::Emergency)
+    }
+}
+
+impl std::fmt::Display for BrakeValvePosition {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Release => write!(f, "Release"),
+            Self::Running => write!(f, "Running"),
+            Self::Lap => write!(f, "Lap"),
+            Self::Service(step) => write!(f, "Service {}", step),
+            Self::Emergency => write!(f, "Emergency"),
+        }
+    }
+}
+
+// safety systems that can take over the brakes from the driver
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BrakeIntervention {
+    Overspeed,
+    Pzb,
+    CabSignalling,
+    Sifa,
 }
 
 #[derive(Component, Default)]
 pub struct BrakeLever {
-    // 0..1
-    pub release_valve: f32,
-    // 0..1
+    pub valve: BrakeValvePosition,
+    // 0..1, independent straight air brake of the engine
     pub engine_brake: f32,
+    // 0..1, electric or hydrodynamic brake of the engine
+    pub dynamic_brake: f32,
+    // each vents the brake pipe until the system that applied it releases it
+    pub interventions: HashSet<BrakeIntervention>,
+}
+
+impl BrakeLever {
+    // the valve position the brake pipe follows
+    pub fn effective_valve(&self) -> BrakeValvePosition {
+        if self.interventions.is_empty() {
+            self.valve
+        } else {
+            BrakeValvePosition::Emergency
+        }
+    }
+
+    pub fn set_intervention(&mut self, intervention: BrakeIntervention, active: bool) {
+        if active {
+            self.interventions.insert(intervention);
+        } else {
+            self.interventions.remove(&intervention);
+        }
+    }
 }
 
 #[derive(Component, Default, Deserialize)]
@@ -105,11 +283,487 @@ pub struct MaxPower(pub f32);
 // kg
 pub struct Mass(pub f32);
 
+// typical supplements for the inertia of wheelsets, gears and traction
+// motors when the model file does not state one
+const ENGINE_ROTATING_MASS_FACTOR: f32 = 1.2;
+const WAGON_ROTATING_MASS_FACTOR: f32 = 1.06;
+
+// the rotating parts have to be spun up along with the vehicle. this adds to
+// the inertia but not to the weight, so gravity and adhesion ignore it
+#[derive(Component, Default, Debug, WrappedValue)]
+// kg
+pub struct RotatingMass(pub f32);
+
+impl RotatingMass {
+    // the factor relates to the empty vehicle, loading it does not add any
+    // rotating parts
+    pub fn from_factor(mass: &Mass, factor: f32) -> Self {
+        Self(mass.0 * (factor - 1.0).max(0.0))
+    }
+}
+
+// freight or passengers a wagon can carry
+#[derive(Component, Default, Debug, Clone, Deserialize)]
+pub struct Payload {
+    // kg
+    pub capacity: f32,
+    // kg, currently loaded and part of the mass
+    #[serde(skip)]
+    pub load: f32,
+}
+
+impl Payload {
+    pub fn share(&self) -> f32 {
+        if self.capacity == 0.0 {
+            return 0.0;
+        }
+
+        self.load / self.capacity
+    }
+
+    // loads or unloads the wagon and keeps its mass in line
+    pub fn set_share(&mut self, share: f32, mass: &mut Mass) {
+        let load = self.capacity * share.clamp(0.0, 1.0);
+
+        mass.0 += load - self.load;
+        self.load = load;
+    }
+}
+
+// loads or unloads a wagon that is already on the track
+#[derive(Event, Debug, Clone, PartialEq)]
+pub struct LoadChange {
+    pub wagon: Entity,
+    // share of the payload capacity, 0 is empty and 1 fully loaded
+    pub share: f32,
+}
+
+// tractive effort over speed as published for many locomotives. points
+// are (km/h, kN) and get linearly interpolated
+#[derive(Component, Default, Clone, Deserialize)]
+#[serde(default)]
+pub struct TractiveEffort {
+    // curve at full throttle, scaled by the throttle percentage
+    pub curve: Vec<(f32, f32)>,
+    // curves per throttle notch (lowest first), used instead of curve
+    pub notches: Vec<Vec<(f32, f32)>>,
+}
+
+// tables in model files do not have to be in order
+fn sort_by_speed(points: &mut [(f32, f32)]) {
+    points.sort_by(|a, b| a.0.total_cmp(&b.0));
+}
+
+impl TractiveEffort {
+    pub fn sort(&mut self) {
+        sort_by_speed(&mut self.curve);
+        for points in self.notches.iter_mut() {
+            sort_by_speed(points);
+        }
+    }
+
+    // points have to be sorted by speed. beyond the last point the force
+    // falls off at the power of that point
+    fn interpolate(points: &[(f32, f32)], speed_kmh: f32) -> f32 {
+        let (Some(first), Some(last)) = (points.first(), points.last()) else {
+            return 0.0;
+        };
+
+        if speed_kmh <= first.0 {
+            return first.1;
+        }
+
+        for window in points.windows(2) {
+            let (a, b) = (window[0], window[1]);
+
+            if speed_kmh <= b.0 {
+                let t = (speed_kmh - a.0) / (b.0 - a.0);
+                return a.1 + (b.1 - a.1) * t;
+            }
+        }
+
+        // a table ending at standstill has no power to fall off with
+        if last.0 > 0.0 {
+            last.1 * last.0 / speed_kmh
+        } else {
+            last.1
+        }
+    }
+
+    // N, None if no table is given and constant power applies
+    pub fn force(&self, speed: f32, throttle: f32) -> Option<f32> {
+        let speed_kmh = speed.abs() * 3.6;
+
+        if !self.notches.is_empty() {
+            let notch = (throttle * self.notches.len() as f32).round() as usize;
+
+            return Some(match notch {
+                0 => 0.0,
+                notch => {
+                    let points = &self.notches[notch.min(self.notches.len()) - 1];
+                    Self::interpolate(points, speed_kmh) * 1000.0
+                }
+            });
+        }
+
+        if !self.curve.is_empty() {
+            return Some(Self::interpolate(&self.curve, speed_kmh) * 1000.0 * throttle);
+        }
+
+        None
+    }
+}
+
+// brake of the engine acting without air, either by feeding the traction
+// motors' current back into the overhead line (regenerative) or by burning
+// it in resistors. points are the maximum braking effort (km/h, kN)
+#[derive(Component, Default, Clone, Deserialize)]
+#[serde(default)]
+pub struct DynamicBrake {
+    pub regenerative: bool,
+    pub curve: Vec<(f32, f32)>,
+}
+
+impl DynamicBrake {
+    pub fn sort(&mut self) {
+        sort_by_speed(&mut self.curve);
+    }
+
+    pub fn is_available(&self) -> bool {
+        !self.curve.is_empty()
+    }
+
+    // N
+    pub fn force(&self, speed: f32, percentage: f32) -> f32 {
+        TractiveEffort::interpolate(&self.curve, speed.abs() * 3.6) * 1000.0 * percentage
+    }
+}
+
+// time without traction while a mechanical gearbox shifts
+pub const SHIFT_TIME: f32 = 1.0; // s
+
+// downshift once the speed falls this far below the shift point
+const DOWNSHIFT_RATIO: f32 = 0.85;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Deserialize)]
+pub enum TransmissionKind {
+    // torque converters shift without interrupting the traction
+    #[default]
+    Hydraulic,
+    Mechanical,
+}
+
+#[derive(Debug, Default, Clone, Deserialize)]
+pub struct TransmissionStage {
+    // km/h up to which this stage is used
+    pub max_speed: f32,
+    pub efficiency: f32,
+}
+
+#[derive(Debug, Default, Clone, Deserialize)]
+pub struct Transmission {
+    pub kind: TransmissionKind,
+    pub stages: Vec<TransmissionStage>,
+}
+
+impl Transmission {
+    // stage to use at the given speed, shifting at most as far as needed
+    // from the current one
+    pub fn stage_for(&self, current: usize, speed: f32) -> usize {
+        let speed_kmh = speed.abs() * 3.6;
+        let mut stage = current.min(self.stages.len().saturating_sub(1));
+
+        while stage + 1 < self.stages.len() && speed_kmh > self.stages[stage].max_speed {
+            stage += 1;
+        }
+
+        while stage > 0 && speed_kmh < self.stages[stage - 1].max_speed * DOWNSHIFT_RATIO {
+            stage -= 1;
+        }
+
+        stage
+    }
+}
+
+// diesel engine driving through a transmission. engines without notches are
+// not diesel driven
+#[derive(Component, Default, Clone, Deserialize)]
+pub struct DieselEngine {
+    pub notches: u8,
+    pub idle_rpm: f32,
+    pub max_rpm: f32,
+    // rpm/s
+    pub spool_up: f32,
+    // rpm/s
+    pub spool_down: f32,
+    // l/h
+    pub idle_fuel: f32,
+    // l/h
+    pub full_fuel: f32,
+    // l
+    pub fuel_tank: f32,
+    pub transmission: Transmission,
+}
+
+impl DieselEngine {
+    pub fn is_available(&self) -> bool {
+        self.notches > 0
+    }
+
+    pub fn target_rpm(&self, notch: u8) -> f32 {
+        let notch = notch.min(self.notches) as f32 / self.notches as f32;
+        self.idle_rpm + (self.max_rpm - self.idle_rpm) * notch
+    }
+
+    // share of the maximum power the engine delivers at the given rpm
+    pub fn power_share(&self, rpm: f32) -> f32 {
+        ((rpm - self.idle_rpm) / (self.max_rpm - self.idle_rpm)).clamp(0.0, 1.0)
+    }
+
+    // l/s
+    pub fn fuel_consumption(&self, rpm: f32) -> f32 {
+        if rpm <= 0.0 {
+            return 0.0;
+        }
+
+        (self.idle_fuel + (self.full_fuel - self.idle_fuel) * self.power_share(rpm)) / 3600.0
+    }
+
+    // N, max power in kW
+    pub fn force(&self, max_power: f32, state: &DieselState, speed: f32) -> f32 {
+        if state.shift_time > 0.0 {
+            return 0.0;
+        }
+
+        let efficiency = match self.transmission.stages.get(state.stage) {
+            // beyond the top stage the engine would overspeed
+            Some(stage) if speed.abs() * 3.6 > stage.max_speed => return 0.0,
+            Some(stage) => stage.efficiency,
+            None => 1.0,
+        };
+
+        max_power * 1000.0 * self.power_share(state.rpm) * efficiency / speed.abs().max(1.0)
+    }
+}
+
+#[derive(Component, Default)]
+pub struct DieselState {
+    pub rpm: f32,
+    // index into the transmission stages
+    pub stage: usize,
+    // s left until a gear shift is done
+    pub shift_time: f32,
+    // l
+    pub fuel: f32,
+}
+
+pub const MAX_CUT_OFF: f32 = 0.75;
+// share of the steam chest pressure lost to back pressure in the exhaust
+const BACK_PRESSURE_RATIO: f32 = 0.05;
+// kg/m³ per bar, approximation for saturated steam
+const STEAM_DENSITY_PER_BAR: f32 = 0.5;
+
+// cylinders and boiler of a steam engine. engines without cylinders are
+// not driven by steam
+#[derive(Component, Default, Clone, Deserialize)]
+pub struct SteamEngine {
+    pub cylinders: u8,
+    // m
+    pub cylinder_diameter: f32,
+    // m
+    pub piston_stroke: f32,
+    // m
+    pub wheel_diameter: f32,
+    // bar
+    pub max_boiler_pressure: f32,
+    // kg of steam it takes to raise the boiler pressure by one bar
+    pub boiler_capacity: f32,
+    // kg/h of coal at full firing
+    pub max_firing_rate: f32,
+    // kg of water evaporated per kg of coal
+    pub evaporation: f32,
+}
+
+impl SteamEngine {
+    pub fn is_available(&self) -> bool {
+        self.cylinders > 0
+    }
+
+    // share of the steam chest pressure that acts on the piston over a full
+    // stroke when admission stops at the given cut-off
+    fn mean_pressure_ratio(cut_off: f32) -> f32 {
+        if cut_off <= 0.0 {
+            return 0.0;
+        }
+
+        (cut_off * (1.0 + (1.0 / cut_off).ln()) - BACK_PRESSURE_RATIO).max(0.0)
+    }
+
+    // N, steam chest pressure in bar
+    pub fn tractive_effort(&self, steam_chest_pressure: f32, cut_off: f32) -> f32 {
+        self.cylinders as f32 / 2.0
+            * steam_chest_pressure
+            * 100_000.0
+            * Self::mean_pressure_ratio(cut_off)
+            * self.cylinder_diameter.powi(2)
+            * self.piston_stroke
+            / self.wheel_diameter
+    }
+
+    // kg/s of steam the cylinders take in, steam chest pressure in bar
+    pub fn steam_consumption(&self, steam_chest_pressure: f32, cut_off: f32, speed: f32) -> f32 {
+        if steam_chest_pressure <= 0.0 {
+            return 0.0;
+        }
+
+        let revolutions = speed.abs() / (std::f32::consts::PI * self.wheel_diameter);
+        let cylinder_volume =
+            std::f32::consts::PI / 4.0 * self.cylinder_diameter.powi(2) * self.piston_stroke;
+        // double acting cylinders are filled twice per revolution
+        let admitted_volume = self.cylinders as f32 * 2.0 * cylinder_volume * cut_off;
+
+        admitted_volume * revolutions * STEAM_DENSITY_PER_BAR * (steam_chest_pressure + 1.0)
+    }
+
+    // kg/s
+    pub fn coal_consumption(&self, firing_rate: f32) -> f32 {
+        self.max_firing_rate / 3600.0 * firing_rate
+    }
+}
+
+#[derive(Component, Default)]
+pub struct SteamControls {
+    // 0..1
+    pub regulator: f32,
+    // 0..MAX_CUT_OFF, the direction is set by the throttle lever
+    pub cut_off: f32,
+    // 0..1
+    pub firing_rate: f32,
+}
+
+#[derive(Component, Default)]
+pub struct Boiler {
+    // bar
+    pub pressure: f32,
+}
+
+// coal and water carried by a tender or the bunkers of a tank engine. they
+// are part of the vehicle's mass
+#[derive(Component, Default, Clone, Deserialize)]
+pub struct Supplies {
+    // kg
+    pub coal: f32,
+    // kg
+    pub water: f32,
+}
+
+impl Supplies {
+    pub fn is_empty(&self) -> bool {
+        self.coal <= 0.0 || self.water <= 0.0
+    }
+}
+
+// steam engines draw from their own bunkers or from the tender next to them
+pub fn supplies_for(
+    entities: &[Entity],
+    engine: Entity,
+    has_supplies: impl Fn(Entity) -> bool,
+) -> Option<Entity> {
+    let index = entities.iter().position(|entity| *entity == engine)?;
+
+    [Some(index), index.checked_add(1), index.checked_sub(1)]
+        .into_iter()
+        .flatten()
+        .filter_map(|index| entities.get(index))
+        .find(|entity| has_supplies(**entity))
+        .copied()
+}
+
+const JOULES_PER_KWH: f32 = 3_600_000.0;
+// share of the energy drawn from the catenary that arrives at the wheels
+const ELECTRIC_EFFICIENCY: f32 = 0.85;
+// kWh/l
+const DIESEL_ENERGY_DENSITY: f32 = 9.8;
+// kWh/kg
+const COAL_ENERGY_DENSITY: f32 = 8.0;
+
+// energy accounting of an engine or a whole train, all values in kWh
+#[derive(Component, Default, Debug, Clone, PartialEq)]
+pub struct EnergyMeter {
+    // work done at the wheels
+    pub traction: f32,
+    // taken from the catenary or contained in the burnt fuel
+    pub drawn: f32,
+    // fed back into the catenary by the dynamic brake
+    pub regenerated: f32,
+    // kinetic energy taken out by all brakes, including regeneration
+    pub braking: f32,
+    // lost to friction, air and curve resistance
+    pub resistance: f32,
+    // l of diesel burnt
+    pub diesel: f32,
+    // kg of coal burnt
+    pub coal: f32,
+}
+
+impl EnergyMeter {
+    // kWh turned into heat by the brakes
+    pub fn braking_losses(&self) -> f32 {
+        self.braking - self.regenerated
+    }
+}
+
+#[derive(Component, Default, Deserialize)]
+// kg, share of the mass resting on driven axles
+pub struct AdhesiveMass(pub f32);
+
+#[derive(Component, Default, Debug, Clone, Copy, PartialEq)]
+pub enum WheelSlip {
+    #[default]
+    Gripping,
+    // driving force exceeds adhesion
+    Slipping,
+    // braking force exceeds adhesion
+    Sliding,
+}
+
+#[derive(Component, Default)]
+pub struct Sanding(pub bool);
+
+#[derive(Resource, Default, Debug, Deserialize, Clone, Copy, PartialEq)]
+pub enum RailCondition {
+    #[default]
+    Dry,
+    Wet,
+    Slippery,
+}
+
+const SANDING_FACTOR: f32 = 1.25;
+
+impl RailCondition {
+    // after Curtius & Kniffler, speed in m/s
+    pub fn adhesion_coefficient(&self, speed: f32, sanding: bool) -> f32 {
+        let dry = 0.161 + 7.5 / (speed.abs() * 3.6 + 44.0);
+
+        let condition_factor = match self {
+            Self::Dry => 1.0,
+            Self::Wet => 0.7,
+            Self::Slippery => 0.45,
+        };
+
+        let sanding_factor = if sanding { SANDING_FACTOR } else { 1.0 };
+
+        dry * condition_factor * sanding_factor
+    }
+}
+
 #[derive(Component, Default)]
 // m
 pub struct Distance(pub f32);
 
 const MAX_AIR_PRESSURE: f32 = 5.0;
+// brake pipe pressure drop for a full service application
+const FULL_APPLICATION_DROP: f32 = 1.5;
 
 #[derive(Component)]
 // bar
@@ -134,6 +788,36 @@ impl WrappedValue for AirPressure {
 #[derive(Component, Default, WrappedValue)]
 pub struct AirPressureDelta(pub f32);
 
+// pressures the driver's brake valve set the pipe to, oldest first. each
+// vehicle follows them once a change has travelled along the pipe to it
+#[derive(Component, Default)]
+pub struct BrakePipe {
+    // s
+    pub time: f32,
+    // s and bar
+    pub history: VecDeque<(f32, f32)>,
+}
+
+const MAX_BRAKE_CYLINDER_PRESSURE: f32 = 3.8;
+
+#[derive(Component)]
+// bar, refilled from the brake pipe and feeding the brake cylinder
+pub struct AuxiliaryReservoir(pub f32);
+
+impl Default for AuxiliaryReservoir {
+    fn default() -> Self {
+        Self(MAX_AIR_PRESSURE)
+    }
+}
+
+#[derive(Component, Default)]
+// bar
+pub struct BrakeCylinder(pub f32);
+
+#[derive(Component, Default)]
+// bar, straight air circuit of the engine brake
+pub struct IndependentBrakeCylinder(pub f32);
+
 pub enum TrainComponent {
     Engine(Entity),
     Wagon(Entity),
@@ -145,13 +829,40 @@ pub struct Dimension {
     pub length: f32,
 }
 
+// davis-style resistance coefficients of a single engine or wagon
+#[derive(Component, Clone, Deserialize)]
+#[serde(default)]
+pub struct ResistanceCoefficients {
+    // share of the normal force
+    pub rolling: f32,
+    // N
+    pub bearing: f32,
+    pub drag_coefficient: f32,
+    // used instead of drag_coefficient when not leading the train
+    pub trailing_drag_coefficient: f32,
+    // m^2
+    pub frontal_area: f32,
+}
+
+impl Default for ResistanceCoefficients {
+    fn default() -> Self {
+        Self {
+            rolling: 0.002,
+            bearing: 0.0,
+            drag_coefficient: 0.8,
+            trailing_drag_coefficient: 0.2,
+            frontal_area: 10.0,
+        }
+    }
+}
+
 #[derive(Component, Default)]
 pub struct TrainComposition {
     pub components: Vec<TrainComponent>,
 }
 
 impl TrainComposition {
-    fn entities(&self) -> Vec<Entity> {
+    pub fn entities(&self) -> Vec<Entity> {
         self.components
             .iter()
             .map(|component| match component {
@@ -160,14 +871,67 @@ impl TrainComposition {
             })
             .collect()
     }
+
+    // the engine whose driver's brake valve works the brake pipe, the valves
+    // of the other engines are cut out
+    pub fn controlling_engine(&self) -> Option<Entity> {
+        self.components
+            .iter()
+            .find_map(|component| match component {
+                TrainComponent::Engine(entity) => Some(*entity),
+                TrainComponent::Wagon(_) => None,
+            })
+    }
+}
+
+// km/h, slower contacts only lean against the buffer stop
+const MIN_IMPACT_SPEED: f32 = 1.0;
+// km/h, buffer stops absorb impacts up to this speed
+const LIGHT_IMPACT_SPEED: f32 = 5.0;
+// km/h, above this the buffer stop gets overrun
+const SEVERE_IMPACT_SPEED: f32 = 15.0;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CollisionSeverity {
+    Light,
+    Moderate,
+    Severe,
+}
+
+impl CollisionSeverity {
+    // m/s
+    pub fn from_speed(speed: f32) -> Option<Self> {
+        let kmh = speed.abs() * 3.6;
+
+        if kmh < MIN_IMPACT_SPEED {
+            None
+        } else if kmh < LIGHT_IMPACT_SPEED {
+            Some(Self::Light)
+        } else if kmh < SEVERE_IMPACT_SPEED {
+            Some(Self::Moderate)
+        } else {
+            Some(Self::Severe)
+        }
+    }
+}
+
+// a train ran into a buffer stop
+#[derive(Event, Debug, Clone, PartialEq)]
+pub struct Collision {
+    pub train: Entity,
+    // m/s
+    pub speed: f32,
+    pub severity: CollisionSeverity,
 }
 
 pub struct TrainPlugins;
 
 impl PluginGroup for TrainPlugins {
     fn build(self) -> bevy::app::PluginGroupBuilder {
-        PluginGroupBuilder::start::<Self>()
-            .add(physics::TrainPhysicsPlugin)
-            .add(render::TrainRenderPlugin)
+        let group = PluginGroupBuilder::start::<Self>().add(physics::TrainPhysicsPlugin);
+        #[cfg(feature = "graphics")]
+        let group = group.add(render::TrainRenderPlugin);
+
+        group
     }
 }
@@ -1,60 +1,151 @@
 #[cfg(test)]
 mod tests;
 
-mod add_sum_component_values_to_train;
-mod apply_first_component_value_to_train;
 mod apply_min_component_value_to_train;
 mod apply_sum_component_values_to_train;
 mod apply_train_value_to_components;
+mod store_previous_location;
 mod update_acceleration;
 mod update_air_pressure_delta;
 mod update_air_pressure_engine_brake;
 mod update_air_resistance;
+mod update_boiler;
+mod update_brake_cylinder;
+mod update_brake_pipe;
 mod update_braking_force;
+mod update_cab_signalling;
+mod update_curve_resistance;
+mod update_diesel_engine;
 mod update_distance;
 mod update_drive_force;
+mod update_dynamic_brake;
+mod update_energy_meter;
 mod update_friction;
+mod update_gradient;
+mod update_overspeed;
+mod update_payload;
+mod update_pzb;
+mod update_sifa;
 mod update_speed;
+mod update_speed_limit;
+mod update_station_stops;
+mod update_train_energy_meter;
 mod update_train_location;
 
 use super::*;
-use crate::landscape::OSMData;
+use crate::landscape::{HeightMap, OSMData, SignalPlugin, StopPlugin, SwitchPlugin};
 use bevy::prelude::*;
 
+// physics runs at a fixed rate so that results do not depend on the frame
+// rate and two runs with the same inputs end up at the same positions
+const PHYSICS_HZ: f64 = 60.0;
+
+// every physics tick runs through the sets in this order
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub enum PhysicsSet {
+    // shares the train speed with its vehicles and sums up their masses
+    Aggregate,
+    // forces acting on each vehicle, including the brakes
+    Forces,
+    // sums up the forces per train and integrates acceleration, speed and distance
+    Integrate,
+    // moves the trains along the track, supervises their speed and the
+    // driver's vigilance and notes their calls at stops
+    Locate,
+}
+
 pub struct TrainPhysicsPlugin;
 
 impl Plugin for TrainPhysicsPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(
-            Update,
+        app.configure_sets(
+            FixedUpdate,
+            (
+                PhysicsSet::Aggregate,
+                PhysicsSet::Forces,
+                PhysicsSet::Integrate,
+                PhysicsSet::Locate,
+            )
+                .chain(),
+        )
+        .add_systems(
+            FixedUpdate,
             (
-                add_sum_component_values_to_train::system::<AirPressureDelta, AirPressure>,
-                apply_train_value_to_components::system::<AirPressure>,
                 apply_train_value_to_components::system::<Speed>,
                 apply_min_component_value_to_train::system::<MaxSpeed>,
+                update_payload::system.before(apply_sum_component_values_to_train::system::<Mass>),
                 apply_sum_component_values_to_train::system::<Mass>,
-                apply_sum_component_values_to_train::system::<ForceDriving>,
-                apply_sum_component_values_to_train::system::<ForceBraking>,
-                apply_sum_component_values_to_train::system::<ForceFriction>,
-                // TODO: should not be first in list if driving backwards should be last
-                apply_first_component_value_to_train::system::<ForceAirResistance>,
-            ),
+                apply_sum_component_values_to_train::system::<RotatingMass>,
+            )
+                .in_set(PhysicsSet::Aggregate),
         )
         .add_systems(
-            Update,
+            FixedUpdate,
             (
+                update_boiler::system.before(update_drive_force::system),
+                update_diesel_engine::system.before(update_drive_force::system),
                 update_drive_force::system,
                 update_friction::system,
                 update_air_resistance::system,
+                update_curve_resistance::system.run_if(resource_exists::<OSMData>),
+                update_gradient::system
+                    .run_if(resource_exists::<OSMData>.and_then(resource_exists::<HeightMap>)),
+                (
+                    update_air_pressure_delta::system,
+                    update_brake_pipe::system,
+                    update_brake_cylinder::system,
+                    update_air_pressure_engine_brake::system,
+                    update_braking_force::system,
+                    update_dynamic_brake::system,
+                )
+                    .chain(),
+            )
+                .in_set(PhysicsSet::Forces),
+        )
+        .add_systems(
+            FixedUpdate,
+            (
+                (
+                    apply_sum_component_values_to_train::system::<ForceDriving>,
+                    apply_sum_component_values_to_train::system::<ForceBraking>,
+                    apply_sum_component_values_to_train::system::<ForceFriction>,
+                    apply_sum_component_values_to_train::system::<ForceAirResistance>,
+                    apply_sum_component_values_to_train::system::<ForceCurveResistance>,
+                    apply_sum_component_values_to_train::system::<ForceGradient>,
+                ),
                 update_acceleration::system,
                 update_speed::system,
                 update_distance::system,
-                update_air_pressure_delta::system,
-                update_air_pressure_engine_brake::system
-                    .after(apply_train_value_to_components::system::<AirPressure>),
-                update_braking_force::system.after(update_air_pressure_engine_brake::system),
-                update_train_location::system.run_if(resource_exists::<OSMData>),
-            ),
-        );
+                update_energy_meter::system,
+                update_train_energy_meter::system,
+            )
+                .chain()
+                .in_set(PhysicsSet::Integrate),
+        )
+        .add_systems(
+            FixedUpdate,
+            (
+                store_previous_location::system,
+                (
+                    update_train_location::system,
+                    update_speed_limit::system,
+                    update_cab_signalling::system,
+                    update_overspeed::system,
+                    update_pzb::system,
+                    update_station_stops::system,
+                )
+                    .chain()
+                    .run_if(resource_exists::<OSMData>),
+                update_sifa::system,
+            )
+                .chain()
+                .in_set(PhysicsSet::Locate),
+        )
+        .add_plugins((SwitchPlugin, SignalPlugin, StopPlugin))
+        .add_event::<Collision>()
+        .add_event::<LoadChange>()
+        .insert_resource(Time::<Fixed>::from_hz(PHYSICS_HZ))
+        .init_resource::<RailCondition>()
+        .init_resource::<Vigilance>();
     }
 }
@@ -0,0 +1,12 @@
+#[cfg(test)]
+mod tests;
+
+use crate::train::{PreviousTrackLocation, TrackLocation};
+use bevy::prelude::*;
+
+// keeps the location of the last tick so rendering can interpolate
+pub fn system(mut entries: Query<(&TrackLocation, &mut PreviousTrackLocation)>) {
+    for (location, mut previous_location) in entries.iter_mut() {
+        previous_location.0 = location.clone();
+    }
+}
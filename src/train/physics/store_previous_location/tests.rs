@@ -0,0 +1,25 @@
+use super::*;
+use coverage_helper::test;
+
+#[test]
+fn copies_location() {
+    let mut app = App::new();
+    app.add_systems(Update, system);
+
+    let location = TrackLocation {
+        id: (1, 2),
+        distance: 12.5,
+        ..default()
+    };
+
+    let entity = app
+        .world_mut()
+        .spawn((location, PreviousTrackLocation::default()))
+        .id();
+
+    app.update();
+
+    let previous_location = &app.world().get::<PreviousTrackLocation>(entity).unwrap().0;
+    assert_eq!(previous_location.id, (1, 2));
+    assert_eq!(previous_location.distance, 12.5);
+}
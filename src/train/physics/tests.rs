@@ -1,5 +1,11 @@
 use super::*;
+use crate::{
+    landscape::{CoordinatePoint, Path, SwitchPositions},
+    train::Direction,
+};
+use bevy::time::{TimePlugin, TimeUpdateStrategy};
 use coverage_helper::test;
+use std::time::Duration;
 
 #[test]
 fn plugin() {
@@ -7,3 +13,183 @@ fn plugin() {
     app.add_plugins(TrainPhysicsPlugin);
     assert!(app.is_plugin_added::<TrainPhysicsPlugin>());
 }
+
+#[coverage(off)]
+fn run_scenario(frame_duration: u64, frames: u32) -> (f32, f32) {
+    let mut app = App::new();
+    app.add_plugins((TimePlugin, TrainPhysicsPlugin));
+    app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+        frame_duration,
+    )));
+
+    let engine = app
+        .world_mut()
+        .spawn(EngineBundle::from_file("assets/models/BR111.toml"))
+        .id();
+    let wagon = app
+        .world_mut()
+        .spawn(WagonBundle::from_file("assets/models/eanos.toml"))
+        .id();
+    app.world_mut()
+        .get_mut::<ThrottleLever>(engine)
+        .unwrap()
+        .percentage = 0.8;
+
+    let train = app
+        .world_mut()
+        .spawn(TrainBundle::new(
+            "Test",
+            vec![TrainComponent::Engine(engine), TrainComponent::Wagon(wagon)],
+        ))
+        .id();
+
+    for _ in 0..frames {
+        app.update();
+    }
+
+    (
+        app.world().get::<Distance>(train).unwrap().0,
+        app.world().get::<Speed>(train).unwrap().0,
+    )
+}
+
+#[test]
+fn deterministic() {
+    let (distance, speed) = run_scenario(50, 201);
+
+    assert!(distance > 0.0);
+    assert!(speed > 0.0);
+
+    // same simulated time at a different frame rate, the first frame has
+    // no time passing
+    let (other_distance, other_speed) = run_scenario(20, 501);
+    assert_eq!(distance.to_bits(), other_distance.to_bits());
+    assert_eq!(speed.to_bits(), other_speed.to_bits());
+}
+
+#[test]
+fn cab_signalling_stops_a_long_train_before_the_track_end() {
+    let mut app = App::new();
+    app.add_plugins((TimePlugin, TrainPhysicsPlugin));
+    app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+        100,
+    )));
+
+    // a single straight rail of 6 km that ends in a buffer stop
+    let mut rails = std::collections::HashMap::default();
+    let rail = Path {
+        start_id: 0,
+        end_id: 1,
+        start_coords: CoordinatePoint(0.0, 0.0),
+        end_coords: CoordinatePoint(6000.0, 0.0),
+        ..default()
+    };
+    rails.insert(rail.id(), rail);
+    let data = OSMData { rails, ..default() };
+
+    let engine = app
+        .world_mut()
+        .spawn(EngineBundle::from_file("assets/models/BR111.toml"))
+        .id();
+    let mut components = vec![TrainComponent::Engine(engine)];
+    for _ in 0..20 {
+        components.push(TrainComponent::Wagon(
+            app.world_mut()
+                .spawn(WagonBundle::from_file("assets/models/eanos.toml"))
+                .id(),
+        ));
+    }
+    let train = app
+        .world_mut()
+        .spawn(TrainBundle::new("Test", components))
+        .id();
+    app.world_mut().get_mut::<Speed>(train).unwrap().0 = 27.0;
+
+    let entities = app
+        .world()
+        .get::<TrainComposition>(train)
+        .unwrap()
+        .entities();
+    let lengths: Vec<f32> = entities
+        .iter()
+        .map(|entity| app.world().get::<Dimension>(*entity).unwrap().length)
+        .collect();
+    let start = TrackLocation {
+        id: (0, 1),
+        distance: 1500.0,
+        travel_direction: Direction::Forward,
+    };
+    let locations = start.consist_locations(&data, &SwitchPositions::default(), &lengths);
+    app.world_mut().entity_mut(train).insert(start);
+    for (entity, location) in entities.into_iter().zip(locations) {
+        app.world_mut()
+            .entity_mut(entity)
+            .insert((PreviousTrackLocation(location.clone()), location));
+    }
+    app.world_mut()
+        .get_mut::<CabSignalling>(train)
+        .unwrap()
+        .active = true;
+    app.insert_resource(data)
+        .insert_resource(Vigilance::Disabled);
+
+    // the train coasts towards the track end until the supervision stops it
+    let mut intervened = false;
+    for _ in 0..3000 {
+        app.update();
+        intervened |= app.world().get::<CabSignalling>(train).unwrap().status
+            == SupervisionStatus::Intervention;
+        if app.world().get::<Speed>(train).unwrap().0 < STANDSTILL {
+            break;
+        }
+    }
+
+    assert!(intervened);
+    assert!(app.world().get::<Speed>(train).unwrap().0 < STANDSTILL);
+    let head = app.world().get::<TrackLocation>(engine).unwrap().distance;
+    assert!(head + lengths[0] as f64 / 2.0 < 6000.0);
+}
+
+#[test]
+fn service_application_with_two_engines() {
+    let mut app = App::new();
+    app.add_plugins((TimePlugin, TrainPhysicsPlugin));
+    app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+        100,
+    )));
+
+    let leading = app
+        .world_mut()
+        .spawn(EngineBundle::from_file("assets/models/BR111.toml"))
+        .id();
+    let trailing = app
+        .world_mut()
+        .spawn(EngineBundle::from_file("assets/models/BR111.toml"))
+        .id();
+    let wagon = app
+        .world_mut()
+        .spawn(WagonBundle::from_file("assets/models/eanos.toml"))
+        .id();
+    app.world_mut().spawn(TrainBundle::new(
+        "Test",
+        vec![
+            TrainComponent::Engine(leading),
+            TrainComponent::Engine(trailing),
+            TrainComponent::Wagon(wagon),
+        ],
+    ));
+
+    // the trailing engine's valve stays in running
+    app.world_mut()
+        .get_mut::<BrakeLever>(leading)
+        .unwrap()
+        .valve = BrakeValvePosition::Service(SERVICE_STEPS);
+    for _ in 0..50 {
+        app.update();
+    }
+
+    for entity in [leading, trailing, wagon] {
+        let pressure = app.world().get::<AirPressure>(entity).unwrap().0;
+        assert!(pressure < MAX_AIR_PRESSURE - FULL_APPLICATION_DROP / 2.0);
+    }
+}
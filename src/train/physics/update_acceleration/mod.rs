@@ -2,29 +2,36 @@
 mod tests;
 
 use crate::train::{
-    Acceleration, ForceAirResistance, ForceBraking, ForceDriving, ForceFriction, Mass, Speed,
+    Acceleration, ForceAirResistance, ForceBraking, ForceCurveResistance, ForceDriving,
+    ForceFriction, ForceGradient, Mass, RotatingMass, Speed,
 };
 use bevy::prelude::*;
 
-pub fn system(
-    mut entries: Query<(
-        &mut Acceleration,
-        &Speed,
-        &ForceDriving,
-        &ForceFriction,
-        &ForceAirResistance,
-        &ForceBraking,
-        &Mass,
-    )>,
-) {
+type AccelerationQuery<'a> = (
+    &'a mut Acceleration,
+    &'a Speed,
+    &'a ForceDriving,
+    &'a ForceFriction,
+    &'a ForceAirResistance,
+    &'a ForceCurveResistance,
+    &'a ForceBraking,
+    &'a ForceGradient,
+    &'a Mass,
+    Option<&'a RotatingMass>,
+);
+
+pub fn system(mut entries: Query<AccelerationQuery>) {
     for (
         mut acceleration,
         speed,
         force_driving,
         force_friction,
         force_air_resistance,
+        force_curve_resistance,
         force_braking,
+        force_gradient,
         mass,
+        rotating_mass,
     ) in entries.iter_mut()
     {
         if mass.0 == 0.0 {
@@ -32,21 +39,23 @@ pub fn system(
             return;
         }
 
-        let negative_force = force_friction.0 + force_air_resistance.0 + force_braking.0;
-        let positive_force = force_driving.0.abs();
+        let negative_force =
+            force_friction.0 + force_air_resistance.0 + force_curve_resistance.0 + force_braking.0;
+        let positive_force = force_driving.0 + force_gradient.0;
 
-        // currently the driving force can be + or - and all other forces
-        // are always positive (and against driving force). We derive the
-        // direction here from the sign of driving force or speed to figure
-        // out in which direction the other forces go
-        let direction = if positive_force != 0.0 {
-            force_driving.0.signum()
-        } else {
+        // driving and gradient forces are signed, all other forces are always
+        // positive and work against the direction of movement. We derive that
+        // direction from the speed or, when standing still, from the sign of
+        // the forces pushing the train
+        let direction = if speed.0 != 0.0 {
             speed.0.signum()
+        } else {
+            positive_force.signum()
         };
 
-        let force = (positive_force - negative_force) * direction;
-        acceleration.0 = force / mass.0;
+        let force = positive_force - negative_force * direction;
+        let inertia = mass.0 + rotating_mass.map_or(0.0, |rotating_mass| rotating_mass.0);
+        acceleration.0 = force / inertia;
 
         let sign = direction.signum();
         if sign * -acceleration.0 > sign * speed.0 {
@@ -20,11 +20,13 @@ fn gen_train(app: &mut App, weight: f32, mode: GenTrainMode) -> Entity {
             }),
             ForceFriction(10.0),
             ForceAirResistance(10.0),
+            ForceCurveResistance(0.0),
             ForceBraking(if mode == GenTrainMode::Breaking {
                 100.0
             } else {
                 0.0
             }),
+            ForceGradient(0.0),
             Mass(weight),
         ))
         .id()
@@ -52,7 +54,9 @@ fn positive_force_balance() {
             ForceDriving(-100.0),
             ForceFriction(10.0),
             ForceAirResistance(10.0),
+            ForceCurveResistance(0.0),
             ForceBraking(0.0),
+            ForceGradient(0.0),
             Mass(7000.0),
         ))
         .id();
@@ -85,7 +89,9 @@ fn negative_force_balance() {
             ForceDriving(0.0),
             ForceFriction(10.0),
             ForceAirResistance(10.0),
+            ForceCurveResistance(0.0),
             ForceBraking(0.0),
+            ForceGradient(0.0),
             Mass(7000.0),
         ))
         .id();
@@ -109,7 +115,9 @@ fn clamp_to_speed_if_deceleration_is_more() {
             ForceDriving(0.0),
             ForceFriction(10000.0),
             ForceAirResistance(10000.0),
+            ForceCurveResistance(0.0),
             ForceBraking(20000.0),
+            ForceGradient(0.0),
             Mass(7000.0),
         ))
         .id();
@@ -129,7 +137,9 @@ fn clamp_to_speed_if_deceleration_is_more() {
             ForceDriving(0.0),
             ForceFriction(10000.0),
             ForceAirResistance(10000.0),
+            ForceCurveResistance(0.0),
             ForceBraking(20000.0),
+            ForceGradient(0.0),
             Mass(7000.0),
         ))
         .id();
@@ -188,3 +198,112 @@ fn no_mass() {
     assert!(app.world().get::<Acceleration>(train_id).is_some());
     assert_eq!(app.world().get::<Acceleration>(train_id).unwrap().0, 0.0);
 }
+
+#[test]
+fn downhill_accelerates() {
+    let mut app = App::new();
+    app.add_systems(Update, system);
+
+    let train_id = app
+        .world_mut()
+        .spawn((
+            Acceleration(0.0),
+            Speed(0.0),
+            ForceDriving(0.0),
+            ForceFriction(10.0),
+            ForceAirResistance(0.0),
+            ForceCurveResistance(0.0),
+            ForceBraking(0.0),
+            ForceGradient(-1000.0),
+            Mass(7000.0),
+        ))
+        .id();
+
+    app.update();
+
+    assert!(app.world().get::<Acceleration>(train_id).unwrap().0 < 0.0);
+
+    let train_id = app
+        .world_mut()
+        .spawn((
+            Acceleration(0.0),
+            Speed(10.0),
+            ForceDriving(0.0),
+            ForceFriction(10.0),
+            ForceAirResistance(10.0),
+            ForceCurveResistance(0.0),
+            ForceBraking(0.0),
+            ForceGradient(1000.0),
+            Mass(7000.0),
+        ))
+        .id();
+
+    app.update();
+
+    assert!(app.world().get::<Acceleration>(train_id).unwrap().0 > 0.0);
+}
+
+#[test]
+fn brakes_hold_on_gradient() {
+    let mut app = App::new();
+    app.add_systems(Update, system);
+
+    let train_id = app
+        .world_mut()
+        .spawn((
+            Acceleration(0.0),
+            Speed(0.0),
+            ForceDriving(0.0),
+            ForceFriction(10.0),
+            ForceAirResistance(0.0),
+            ForceCurveResistance(0.0),
+            ForceBraking(5000.0),
+            ForceGradient(-1000.0),
+            Mass(7000.0),
+        ))
+        .id();
+
+    app.update();
+
+    assert_eq!(app.world().get::<Acceleration>(train_id).unwrap().0, 0.0);
+}
+
+#[test]
+fn curve_resistance_slows() {
+    let mut app = App::new();
+    app.add_systems(Update, system);
+
+    let straight = gen_train(&mut app, 7000.0, GenTrainMode::Driving);
+    let curve = gen_train(&mut app, 7000.0, GenTrainMode::Driving);
+
+    app.world_mut()
+        .get_mut::<ForceCurveResistance>(curve)
+        .unwrap()
+        .0 = 50.0;
+
+    app.update();
+
+    assert!(
+        app.world().get::<Acceleration>(curve).unwrap().0
+            < app.world().get::<Acceleration>(straight).unwrap().0
+    );
+}
+
+#[test]
+fn rotating_mass_adds_inertia() {
+    let mut app = App::new();
+    app.add_systems(Update, system);
+
+    let plain = gen_train(&mut app, 7000.0, GenTrainMode::Driving);
+    let rotating = gen_train(&mut app, 7000.0, GenTrainMode::Driving);
+
+    app.world_mut()
+        .entity_mut(rotating)
+        .insert(RotatingMass(1400.0));
+
+    app.update();
+
+    let acceleration = |entity| app.world().get::<Acceleration>(entity).unwrap().0;
+    assert!((acceleration(plain) - 80.0 / 7000.0).abs() < 0.00001);
+    assert!((acceleration(rotating) - 80.0 / 8400.0).abs() < 0.00001);
+}
@@ -1,22 +1,63 @@
 #[cfg(test)]
 mod tests;
 
-use crate::train::{AirPressure, AirPressureDelta, BrakeLever, MAX_AIR_PRESSURE};
-use bevy::prelude::*;
+use crate::train::{
+    AirPressure, AirPressureDelta, BrakeLever, BrakeValvePosition, TrainComponent,
+    TrainComposition, FULL_APPLICATION_DROP, MAX_AIR_PRESSURE, SERVICE_STEPS,
+};
+use bevy::{prelude::*, utils::HashSet};
 
-const COMPRESSOR_SPEED: f32 = 0.1;
+// bar/s, only feeds the engine's end of the brake pipe. the wagons fill
+// through the pipe behind it
+const COMPRESSOR_SPEED: f32 = 0.5;
+const FILL_STROKE_SPEED: f32 = 2.0;
+const SERVICE_SPEED: f32 = 0.5;
+const EMERGENCY_SPEED: f32 = 10.0;
 
 pub fn system(
-    mut entries: Query<(&mut AirPressureDelta, &AirPressure, &BrakeLever)>,
+    mut entries: Query<(Entity, &mut AirPressureDelta, &AirPressure, &BrakeLever)>,
+    trains: Query<&TrainComposition>,
     time: Res<Time>,
 ) {
-    for (mut air_pressure_delta, air_pressure, brake_lever) in entries.iter_mut() {
-        air_pressure_delta.0 = if brake_lever.release_valve > 0.0 {
-            let value = brake_lever.release_valve.powi(2);
-            let target = MAX_AIR_PRESSURE * (1.0 - value);
-            (target - air_pressure.0).min(COMPRESSOR_SPEED * time.delta_seconds())
-        } else {
-            COMPRESSOR_SPEED * time.delta_seconds()
+    let delta_seconds = time.delta_seconds();
+
+    // only the controlling engine of a train works the brake pipe, the
+    // valves of the other engines are cut out
+    let cut_out: HashSet<Entity> = trains
+        .iter()
+        .flat_map(|composition| {
+            let controlling = composition.controlling_engine();
+            composition
+                .components
+                .iter()
+                .filter_map(move |component| match component {
+                    TrainComponent::Engine(entity) if Some(*entity) != controlling => Some(*entity),
+                    _ => None,
+                })
+        })
+        .collect();
+
+    for (entity, mut air_pressure_delta, air_pressure, brake_lever) in entries.iter_mut() {
+        if cut_out.contains(&entity) {
+            air_pressure_delta.0 = 0.0;
+            continue;
+        }
+
+        // moves the brake pipe pressure towards target, feeding and venting
+        // at most at the given speeds (bar/s)
+        let towards = |target: f32, feed_speed: f32, vent_speed: f32| {
+            (target - air_pressure.0).clamp(-vent_speed * delta_seconds, feed_speed * delta_seconds)
+        };
+
+        air_pressure_delta.0 = match brake_lever.effective_valve() {
+            BrakeValvePosition::Release => towards(MAX_AIR_PRESSURE, FILL_STROKE_SPEED, 0.0),
+            BrakeValvePosition::Running => towards(MAX_AIR_PRESSURE, COMPRESSOR_SPEED, 0.0),
+            BrakeValvePosition::Lap => 0.0,
+            BrakeValvePosition::Service(step) => {
+                let drop = FULL_APPLICATION_DROP * step as f32 / SERVICE_STEPS as f32;
+                towards(MAX_AIR_PRESSURE - drop, COMPRESSOR_SPEED, SERVICE_SPEED)
+            }
+            BrakeValvePosition::Emergency => towards(0.0, 0.0, EMERGENCY_SPEED),
         };
     }
 }
@@ -1,86 +1,205 @@
 use super::*;
 use coverage_helper::test;
+use std::time::Duration;
 
 #[coverage(off)]
-fn spawn_train(app: &mut App, air_pressure: f32, release_valve: f32) -> Entity {
+fn spawn_engine(app: &mut App, air_pressure: f32, valve: BrakeValvePosition) -> Entity {
     app.world_mut()
         .spawn((
             AirPressure(air_pressure),
             AirPressureDelta(0.0),
-            BrakeLever {
-                release_valve,
-                engine_brake: 0.0,
-            },
+            BrakeLever { valve, ..default() },
         ))
         .id()
 }
 
+#[coverage(off)]
+fn update(app: &mut App) -> f32 {
+    let mut time = app.world_mut().resource_mut::<Time>();
+    time.advance_by(Duration::from_millis(100));
+    app.update();
+    app.world().resource::<Time>().delta_seconds()
+}
+
 #[test]
-fn no_brake_pressure_increases() {
+fn running_pressure_increases() {
     let mut app = App::new();
     app.add_systems(Update, system);
+    app.init_resource::<Time>();
 
-    let train_id = spawn_train(&mut app, MAX_AIR_PRESSURE - 1.0, 0.0);
+    let engine_id = spawn_engine(
+        &mut app,
+        MAX_AIR_PRESSURE - 1.0,
+        BrakeValvePosition::Running,
+    );
+
+    let delta_seconds = update(&mut app);
+
+    let air_pressure_delta = app.world().get::<AirPressureDelta>(engine_id).unwrap().0;
+    assert_eq!(air_pressure_delta, COMPRESSOR_SPEED * delta_seconds);
+}
+
+#[test]
+fn release_fills_faster() {
+    let mut app = App::new();
+    app.add_systems(Update, system);
     app.init_resource::<Time>();
 
-    app.update();
+    let running_id = spawn_engine(
+        &mut app,
+        MAX_AIR_PRESSURE - 1.0,
+        BrakeValvePosition::Running,
+    );
+    let release_id = spawn_engine(
+        &mut app,
+        MAX_AIR_PRESSURE - 1.0,
+        BrakeValvePosition::Release,
+    );
 
-    let air_pressure_delta = app.world().get::<AirPressureDelta>(train_id).unwrap().0;
-    assert_eq!(
-        air_pressure_delta,
-        COMPRESSOR_SPEED * app.world().resource::<Time>().delta_seconds()
+    update(&mut app);
+
+    assert!(
+        app.world().get::<AirPressureDelta>(running_id).unwrap().0
+            < app.world().get::<AirPressureDelta>(release_id).unwrap().0
     );
 }
 
 #[test]
-fn brake_applies_pressure_decrease() {
+fn full_pipe_no_change() {
     let mut app = App::new();
     app.add_systems(Update, system);
+    app.init_resource::<Time>();
+
+    let engine_id = spawn_engine(&mut app, MAX_AIR_PRESSURE, BrakeValvePosition::Release);
 
-    let release_valve = 0.5;
-    let train_id = spawn_train(&mut app, MAX_AIR_PRESSURE, release_valve);
+    update(&mut app);
+
+    assert_eq!(
+        app.world().get::<AirPressureDelta>(engine_id).unwrap().0,
+        0.0
+    );
+}
+
+#[test]
+fn lap_holds_pressure() {
+    let mut app = App::new();
+    app.add_systems(Update, system);
     app.init_resource::<Time>();
 
-    app.update();
+    let engine_id = spawn_engine(&mut app, MAX_AIR_PRESSURE - 1.0, BrakeValvePosition::Lap);
+
+    update(&mut app);
 
-    let air_pressure_delta = app.world().get::<AirPressureDelta>(train_id).unwrap().0;
-    let target = MAX_AIR_PRESSURE * (1.0 - release_valve.powi(2));
-    let expected_delta = (target - MAX_AIR_PRESSURE)
-        .min(COMPRESSOR_SPEED * app.world().resource::<Time>().delta_seconds());
-    assert_eq!(air_pressure_delta, expected_delta);
+    assert_eq!(
+        app.world().get::<AirPressureDelta>(engine_id).unwrap().0,
+        0.0
+    );
 }
 
 #[test]
-fn full_brake_pressure_decreases() {
+fn service_steps_vent_to_target() {
     let mut app = App::new();
     app.add_systems(Update, system);
+    app.init_resource::<Time>();
+
+    let first_step = spawn_engine(&mut app, MAX_AIR_PRESSURE, BrakeValvePosition::Service(1));
+    let full_service = spawn_engine(
+        &mut app,
+        MAX_AIR_PRESSURE,
+        BrakeValvePosition::Service(SERVICE_STEPS),
+    );
+    let reached_target = spawn_engine(
+        &mut app,
+        MAX_AIR_PRESSURE - FULL_APPLICATION_DROP,
+        BrakeValvePosition::Service(SERVICE_STEPS),
+    );
+
+    let delta_seconds = update(&mut app);
 
-    let train_id = spawn_train(&mut app, MAX_AIR_PRESSURE, 1.0);
+    let delta = |entity: Entity| app.world().get::<AirPressureDelta>(entity).unwrap().0;
+
+    let first_step_drop = FULL_APPLICATION_DROP / SERVICE_STEPS as f32;
+    assert_eq!(
+        delta(first_step),
+        (-first_step_drop).max(-SERVICE_SPEED * delta_seconds)
+    );
+    assert_eq!(delta(full_service), -SERVICE_SPEED * delta_seconds);
+    assert_eq!(delta(reached_target), 0.0);
+}
+
+#[test]
+fn emergency_vents_fast() {
+    let mut app = App::new();
+    app.add_systems(Update, system);
     app.init_resource::<Time>();
 
-    app.update();
+    let service_id = spawn_engine(
+        &mut app,
+        MAX_AIR_PRESSURE,
+        BrakeValvePosition::Service(SERVICE_STEPS),
+    );
+    let emergency_id = spawn_engine(&mut app, MAX_AIR_PRESSURE, BrakeValvePosition::Emergency);
 
-    let air_pressure_delta = app.world().get::<AirPressureDelta>(train_id).unwrap().0;
-    let target = MAX_AIR_PRESSURE * (1.0 - 1.0);
-    let expected_delta = (target - MAX_AIR_PRESSURE)
-        .min(COMPRESSOR_SPEED * app.world().resource::<Time>().delta_seconds());
-    assert_eq!(air_pressure_delta, expected_delta);
+    let delta_seconds = update(&mut app);
+
+    assert_eq!(
+        app.world().get::<AirPressureDelta>(emergency_id).unwrap().0,
+        -EMERGENCY_SPEED * delta_seconds
+    );
+    assert!(
+        app.world().get::<AirPressureDelta>(emergency_id).unwrap().0
+            < app.world().get::<AirPressureDelta>(service_id).unwrap().0
+    );
 }
 
 #[test]
-fn partial_brake_pressure_decreases() {
+fn interventions_override_the_valve() {
     let mut app = App::new();
     app.add_systems(Update, system);
+    app.init_resource::<Time>();
+
+    let engine_id = spawn_engine(&mut app, MAX_AIR_PRESSURE, BrakeValvePosition::Running);
+    app.world_mut()
+        .get_mut::<BrakeLever>(engine_id)
+        .unwrap()
+        .set_intervention(crate::train::BrakeIntervention::Overspeed, true);
 
-    let release_valve = 0.25;
-    let train_id = spawn_train(&mut app, MAX_AIR_PRESSURE, release_valve);
+    let delta_seconds = update(&mut app);
+
+    assert_eq!(
+        app.world().get::<AirPressureDelta>(engine_id).unwrap().0,
+        -EMERGENCY_SPEED * delta_seconds
+    );
+}
+
+#[test]
+fn trailing_engines_are_cut_out() {
+    let mut app = App::new();
+    app.add_systems(Update, system);
     app.init_resource::<Time>();
 
-    app.update();
+    let leading_id = spawn_engine(
+        &mut app,
+        MAX_AIR_PRESSURE - 1.0,
+        BrakeValvePosition::Running,
+    );
+    let trailing_id = spawn_engine(
+        &mut app,
+        MAX_AIR_PRESSURE - 1.0,
+        BrakeValvePosition::Running,
+    );
+    app.world_mut().spawn(TrainComposition {
+        components: vec![
+            TrainComponent::Engine(leading_id),
+            TrainComponent::Engine(trailing_id),
+        ],
+    });
+
+    update(&mut app);
 
-    let air_pressure_delta = app.world().get::<AirPressureDelta>(train_id).unwrap().0;
-    let target = MAX_AIR_PRESSURE * (1.0 - release_valve.powi(2));
-    let expected_delta = (target - MAX_AIR_PRESSURE)
-        .min(COMPRESSOR_SPEED * app.world().resource::<Time>().delta_seconds());
-    assert_eq!(air_pressure_delta, expected_delta);
+    assert!(app.world().get::<AirPressureDelta>(leading_id).unwrap().0 > 0.0);
+    assert_eq!(
+        app.world().get::<AirPressureDelta>(trailing_id).unwrap().0,
+        0.0
+    );
 }
@@ -1,12 +1,16 @@
 #[cfg(test)]
 mod tests;
 
-use crate::train::{AirPressure, BrakeLever};
+use crate::train::{BrakeLever, IndependentBrakeCylinder, MAX_BRAKE_CYLINDER_PRESSURE};
 use bevy::prelude::*;
 
-pub fn system(mut entries: Query<(&mut AirPressure, &BrakeLever)>) {
-    for (mut air_pressure, brake_lever) in entries.iter_mut() {
-        let value = brake_lever.engine_brake.powi(2);
-        air_pressure.0 *= 1.0 - value;
+const STRAIGHT_AIR_SPEED: f32 = 1.5; // bar/s
+
+pub fn system(mut entries: Query<(&mut IndependentBrakeCylinder, &BrakeLever)>, time: Res<Time>) {
+    let max_change = STRAIGHT_AIR_SPEED * time.delta_seconds();
+
+    for (mut brake_cylinder, brake_lever) in entries.iter_mut() {
+        let target = brake_lever.engine_brake.powi(2) * MAX_BRAKE_CYLINDER_PRESSURE;
+        brake_cylinder.0 += (target - brake_cylinder.0).clamp(-max_change, max_change);
     }
 }
@@ -1,11 +1,12 @@
 use super::*;
 use coverage_helper::test;
+use std::time::Duration;
 
 #[coverage(off)]
-fn spawn_engine(app: &mut App, air_pressure: f32, engine_brake: f32) -> Entity {
+fn spawn_engine(app: &mut App, brake_cylinder: f32, engine_brake: f32) -> Entity {
     app.world_mut()
         .spawn((
-            AirPressure(air_pressure),
+            IndependentBrakeCylinder(brake_cylinder),
             BrakeLever {
                 engine_brake,
                 ..default()
@@ -14,69 +15,102 @@ fn spawn_engine(app: &mut App, air_pressure: f32, engine_brake: f32) -> Entity {
         .id()
 }
 
+#[coverage(off)]
+fn run_for(app: &mut App, seconds: u64) {
+    for _ in 0..seconds * 10 {
+        let mut time = app.world_mut().resource_mut::<Time>();
+        time.advance_by(Duration::from_millis(100));
+        app.update();
+    }
+}
+
 #[test]
 fn no_brake_no_change() {
     let mut app = App::new();
     app.add_systems(Update, system);
+    app.init_resource::<Time>();
 
-    let initial_pressure = 5.0;
-    let engine_id = spawn_engine(&mut app, initial_pressure, 0.0);
+    let engine_id = spawn_engine(&mut app, 0.0, 0.0);
 
-    app.update();
+    run_for(&mut app, 1);
 
-    let air_pressure = app.world().get::<AirPressure>(engine_id).unwrap().0;
-    assert_eq!(air_pressure, initial_pressure);
+    let brake_cylinder = app
+        .world()
+        .get::<IndependentBrakeCylinder>(engine_id)
+        .unwrap()
+        .0;
+    assert_eq!(brake_cylinder, 0.0);
 }
 
 #[test]
-fn full_brake_zero_pressure() {
+fn full_brake_full_pressure() {
     let mut app = App::new();
     app.add_systems(Update, system);
+    app.init_resource::<Time>();
 
-    let initial_pressure = 5.0;
-    let engine_id = spawn_engine(&mut app, initial_pressure, 1.0);
+    let engine_id = spawn_engine(&mut app, 0.0, 1.0);
 
-    app.update();
+    run_for(&mut app, 1);
 
-    let air_pressure = app.world().get::<AirPressure>(engine_id).unwrap().0;
-    assert_eq!(air_pressure, 0.0);
+    // straight air takes a moment to fill the cylinder
+    let brake_cylinder = app
+        .world()
+        .get::<IndependentBrakeCylinder>(engine_id)
+        .unwrap()
+        .0;
+    assert!(brake_cylinder > 0.0);
+    assert!(brake_cylinder < MAX_BRAKE_CYLINDER_PRESSURE);
+
+    run_for(&mut app, 5);
+
+    let brake_cylinder = app
+        .world()
+        .get::<IndependentBrakeCylinder>(engine_id)
+        .unwrap()
+        .0;
+    assert_eq!(brake_cylinder, MAX_BRAKE_CYLINDER_PRESSURE);
 }
 
 #[test]
-fn partial_brake_reduces_pressure() {
+fn varying_brake_levels() {
     let mut app = App::new();
     app.add_systems(Update, system);
+    app.init_resource::<Time>();
 
-    let initial_pressure = 5.0;
-    let engine_brake = 0.5;
-    let engine_id = spawn_engine(&mut app, initial_pressure, engine_brake);
-
-    app.update();
-
-    let air_pressure = app.world().get::<AirPressure>(engine_id).unwrap().0;
-    assert_eq!(
-        air_pressure,
-        initial_pressure * (1.0 - engine_brake.powi(2))
-    );
+    let brake_levels = vec![0.0, 0.2, 0.5, 0.8, 1.0];
+    let engines: Vec<(f32, Entity)> = brake_levels
+        .into_iter()
+        .map(|engine_brake| (engine_brake, spawn_engine(&mut app, 0.0, engine_brake)))
+        .collect();
+
+    run_for(&mut app, 5);
+
+    for (engine_brake, engine_id) in engines {
+        let brake_cylinder = app
+            .world()
+            .get::<IndependentBrakeCylinder>(engine_id)
+            .unwrap()
+            .0;
+        assert!(
+            (brake_cylinder - MAX_BRAKE_CYLINDER_PRESSURE * engine_brake.powi(2)).abs() < 0.001
+        );
+    }
 }
 
 #[test]
-fn varying_brake_levels() {
+fn release_empties_cylinder() {
     let mut app = App::new();
     app.add_systems(Update, system);
+    app.init_resource::<Time>();
 
-    let initial_pressure = 5.0;
+    let engine_id = spawn_engine(&mut app, MAX_BRAKE_CYLINDER_PRESSURE, 0.0);
 
-    let brake_levels = vec![0.0, 0.2, 0.5, 0.8, 1.0];
-    for &engine_brake in &brake_levels {
-        let engine_id = spawn_engine(&mut app, initial_pressure, engine_brake);
+    run_for(&mut app, 5);
 
-        app.update();
-
-        let air_pressure = app.world().get::<AirPressure>(engine_id).unwrap().0;
-        assert_eq!(
-            air_pressure,
-            initial_pressure * (1.0 - engine_brake.powi(2))
-        );
-    }
+    let brake_cylinder = app
+        .world()
+        .get::<IndependentBrakeCylinder>(engine_id)
+        .unwrap()
+        .0;
+    assert_eq!(brake_cylinder, 0.0);
 }
@@ -1,15 +1,39 @@
 #[cfg(test)]
 mod tests;
 
-use super::{EngineOrWagons, ForceAirResistance, Speed};
+use super::{EngineOrWagons, ForceAirResistance, ResistanceCoefficients, Speed, TrainComposition};
 use bevy::prelude::*;
 
-pub fn system(mut entries: Query<(&mut ForceAirResistance, &Speed), EngineOrWagons>) {
+pub fn system(
+    trains: Query<(&TrainComposition, &Speed)>,
+    mut entries: Query<(&mut ForceAirResistance, &ResistanceCoefficients), EngineOrWagons>,
+) {
     let air_density = 1.225; // kg/m^3
-    let drag_coefficient = 0.8;
-    let frontal_area = 10.0; // m^2
 
-    for (mut air_resistance, speed) in entries.iter_mut() {
-        air_resistance.0 = 0.5 * air_density * speed.0.powi(2) * drag_coefficient * frontal_area;
+    for (composition, speed) in trains.iter() {
+        let components = composition.entities();
+
+        // the first component leads when driving forwards, the last one
+        // when driving backwards. everything behind it is in its slipstream
+        let leading_index = if speed.0 < 0.0 {
+            components.len().saturating_sub(1)
+        } else {
+            0
+        };
+
+        for (index, component_entity) in components.into_iter().enumerate() {
+            let Ok((mut air_resistance, resistance)) = entries.get_mut(component_entity) else {
+                continue;
+            };
+
+            let drag_coefficient = if index == leading_index {
+                resistance.drag_coefficient
+            } else {
+                resistance.trailing_drag_coefficient
+            };
+
+            air_resistance.0 =
+                0.5 * air_density * speed.0.powi(2) * drag_coefficient * resistance.frontal_area;
+        }
     }
 }
@@ -1,12 +1,59 @@
 use super::*;
-use crate::train::Engine;
+use crate::train::{Engine, TrainComponent, Wagon};
 use coverage_helper::test;
 
 #[coverage(off)]
 fn spawn_engine(app: &mut App, speed: f32) -> Entity {
-    app.world_mut()
-        .spawn((Engine, ForceAirResistance::default(), Speed(speed)))
-        .id()
+    let engine_id = app
+        .world_mut()
+        .spawn((
+            Engine,
+            ForceAirResistance::default(),
+            ResistanceCoefficients::default(),
+        ))
+        .id();
+
+    app.world_mut().spawn((
+        TrainComposition {
+            components: vec![TrainComponent::Engine(engine_id)],
+        },
+        Speed(speed),
+    ));
+
+    engine_id
+}
+
+#[coverage(off)]
+fn spawn_push_pull(app: &mut App, speed: f32) -> (Entity, Entity) {
+    let engine_id = app
+        .world_mut()
+        .spawn((
+            Engine,
+            ForceAirResistance::default(),
+            ResistanceCoefficients::default(),
+        ))
+        .id();
+
+    let control_car_id = app
+        .world_mut()
+        .spawn((
+            Wagon,
+            ForceAirResistance::default(),
+            ResistanceCoefficients::default(),
+        ))
+        .id();
+
+    app.world_mut().spawn((
+        TrainComposition {
+            components: vec![
+                TrainComponent::Engine(engine_id),
+                TrainComponent::Wagon(control_car_id),
+            ],
+        },
+        Speed(speed),
+    ));
+
+    (engine_id, control_car_id)
 }
 
 #[test]
@@ -72,3 +119,52 @@ fn negative_positive_speed_equal() {
             .0
     );
 }
+
+#[test]
+fn coefficients_per_vehicle() {
+    let mut app = App::new();
+    app.add_systems(Update, system);
+
+    let streamlined_train = spawn_engine(&mut app, 30.0);
+    let steam_train = spawn_engine(&mut app, 30.0);
+
+    *app.world_mut()
+        .get_mut::<ResistanceCoefficients>(steam_train)
+        .unwrap() = ResistanceCoefficients {
+        drag_coefficient: 1.2,
+        frontal_area: 11.0,
+        ..default()
+    };
+
+    app.update();
+
+    assert!(
+        app.world()
+            .get::<ForceAirResistance>(streamlined_train)
+            .unwrap()
+            .0
+            < app
+                .world()
+                .get::<ForceAirResistance>(steam_train)
+                .unwrap()
+                .0
+    );
+}
+
+#[test]
+fn leading_vehicle_by_direction() {
+    let mut app = App::new();
+    app.add_systems(Update, system);
+
+    let (forward_engine, forward_control_car) = spawn_push_pull(&mut app, 30.0);
+    let (backward_engine, backward_control_car) = spawn_push_pull(&mut app, -30.0);
+
+    app.update();
+
+    let resistance = |entity: Entity| app.world().get::<ForceAirResistance>(entity).unwrap().0;
+
+    assert!(resistance(forward_engine) > resistance(forward_control_car));
+    assert!(resistance(backward_engine) < resistance(backward_control_car));
+    assert_eq!(resistance(forward_engine), resistance(backward_control_car));
+    assert_eq!(resistance(forward_control_car), resistance(backward_engine));
+}
@@ -0,0 +1,78 @@
+#[cfg(test)]
+mod tests;
+
+use crate::train::{
+    supplies_for, Boiler, EnergyMeter, Mass, Speed, SteamControls, SteamEngine, Supplies,
+    TrainComposition, COAL_ENERGY_DENSITY,
+};
+use bevy::prelude::*;
+
+pub fn system(
+    trains: Query<&TrainComposition>,
+    mut engines: Query<(
+        &SteamEngine,
+        &SteamControls,
+        &mut Boiler,
+        &mut EnergyMeter,
+        &Speed,
+    )>,
+    mut supplies: Query<(&mut Supplies, &mut Mass)>,
+    time: Res<Time>,
+) {
+    let delta_seconds = time.delta_seconds();
+
+    for composition in trains.iter() {
+        let entities = composition.entities();
+
+        for engine in entities.iter() {
+            let Ok((steam_engine, steam_controls, mut boiler, mut energy_meter, speed)) =
+                engines.get_mut(*engine)
+            else {
+                continue;
+            };
+
+            if !steam_engine.is_available() {
+                continue;
+            }
+
+            let supplies_entity = supplies_for(&entities, *engine, |entity| {
+                supplies
+                    .get(entity)
+                    .is_ok_and(|(supplies, _)| !supplies.is_empty())
+            });
+
+            // the fire goes out once coal or water run out
+            let generated_steam = match supplies_entity {
+                Some(supplies_entity) => {
+                    let (mut supplies, mut mass) = supplies.get_mut(supplies_entity).unwrap();
+
+                    let coal = (steam_engine.coal_consumption(steam_controls.firing_rate)
+                        * delta_seconds)
+                        .min(supplies.coal);
+                    let water = (coal * steam_engine.evaporation).min(supplies.water);
+
+                    supplies.coal -= coal;
+                    supplies.water -= water;
+                    mass.0 -= coal + water;
+
+                    energy_meter.coal += coal;
+                    energy_meter.drawn += coal * COAL_ENERGY_DENSITY;
+
+                    water
+                }
+                None => 0.0,
+            };
+
+            let used_steam = steam_engine.steam_consumption(
+                boiler.pressure * steam_controls.regulator,
+                steam_controls.cut_off,
+                speed.0,
+            ) * delta_seconds;
+
+            // the safety valves blow off anything above the maximum pressure
+            boiler.pressure = (boiler.pressure
+                + (generated_steam - used_steam) / steam_engine.boiler_capacity)
+                .clamp(0.0, steam_engine.max_boiler_pressure);
+        }
+    }
+}
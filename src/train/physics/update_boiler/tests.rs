@@ -0,0 +1,184 @@
+use super::*;
+use crate::train::{TrainBundle, TrainComponent};
+use coverage_helper::test;
+use std::time::Duration;
+
+#[coverage(off)]
+fn steam_engine() -> SteamEngine {
+    SteamEngine {
+        cylinders: 2,
+        cylinder_diameter: 0.6,
+        piston_stroke: 0.66,
+        wheel_diameter: 1.4,
+        max_boiler_pressure: 16.0,
+        boiler_capacity: 250.0,
+        max_firing_rate: 1800.0,
+        evaporation: 6.5,
+    }
+}
+
+#[coverage(off)]
+fn spawn_train(
+    app: &mut App,
+    controls: SteamControls,
+    pressure: f32,
+    speed: f32,
+    supplies: Supplies,
+) -> (Entity, Entity) {
+    let engine = app
+        .world_mut()
+        .spawn((
+            steam_engine(),
+            controls,
+            Boiler { pressure },
+            EnergyMeter::default(),
+            Speed(speed),
+            Supplies::default(),
+            Mass(84_000.0),
+        ))
+        .id();
+
+    let tender = app.world_mut().spawn((supplies, Mass(58_700.0))).id();
+
+    app.world_mut().spawn(TrainBundle::new(
+        "Test",
+        vec![
+            TrainComponent::Engine(engine),
+            TrainComponent::Wagon(tender),
+        ],
+    ));
+
+    (engine, tender)
+}
+
+#[coverage(off)]
+fn setup() -> App {
+    let mut app = App::new();
+    app.add_systems(Update, system);
+    app.init_resource::<Time>();
+    app
+}
+
+#[coverage(off)]
+fn run_for(app: &mut App, seconds: u64) {
+    for _ in 0..seconds * 10 {
+        let mut time = app.world_mut().resource_mut::<Time>();
+        time.advance_by(Duration::from_millis(100));
+        app.update();
+    }
+}
+
+#[test]
+fn firing_raises_pressure() {
+    let mut app = setup();
+
+    let (engine, tender) = spawn_train(
+        &mut app,
+        SteamControls {
+            firing_rate: 1.0,
+            ..default()
+        },
+        10.0,
+        0.0,
+        Supplies {
+            coal: 10_000.0,
+            water: 30_000.0,
+        },
+    );
+
+    run_for(&mut app, 10);
+
+    assert!(app.world().get::<Boiler>(engine).unwrap().pressure > 10.0);
+
+    // 0.5 kg of coal per second evaporate 3.25 kg of water
+    let supplies = app.world().get::<Supplies>(tender).unwrap();
+    assert!((supplies.coal - 9_995.0).abs() < 0.1);
+    assert!((supplies.water - 29_967.5).abs() < 0.1);
+    assert!((app.world().get::<Mass>(tender).unwrap().0 - (58_700.0 - 37.5)).abs() < 0.1);
+    assert_eq!(app.world().get::<Mass>(engine).unwrap().0, 84_000.0);
+
+    let meter = app.world().get::<EnergyMeter>(engine).unwrap();
+    assert!((meter.coal - 5.0).abs() < 0.01);
+    assert!((meter.drawn - 5.0 * COAL_ENERGY_DENSITY).abs() < 0.1);
+
+    run_for(&mut app, 600);
+
+    assert_eq!(app.world().get::<Boiler>(engine).unwrap().pressure, 16.0);
+}
+
+#[test]
+fn driving_uses_steam() {
+    let mut app = setup();
+
+    let (engine, _) = spawn_train(
+        &mut app,
+        SteamControls {
+            regulator: 1.0,
+            cut_off: 0.5,
+            firing_rate: 0.2,
+        },
+        16.0,
+        15.0,
+        Supplies {
+            coal: 10_000.0,
+            water: 30_000.0,
+        },
+    );
+
+    run_for(&mut app, 10);
+
+    assert!(app.world().get::<Boiler>(engine).unwrap().pressure < 16.0);
+}
+
+#[test]
+fn no_supplies_no_steam() {
+    let mut app = setup();
+
+    let (engine, tender) = spawn_train(
+        &mut app,
+        SteamControls {
+            firing_rate: 1.0,
+            ..default()
+        },
+        10.0,
+        0.0,
+        Supplies {
+            coal: 10_000.0,
+            water: 0.0,
+        },
+    );
+
+    run_for(&mut app, 10);
+
+    assert_eq!(app.world().get::<Boiler>(engine).unwrap().pressure, 10.0);
+    assert_eq!(app.world().get::<Supplies>(tender).unwrap().coal, 10_000.0);
+}
+
+#[test]
+fn tank_engine_uses_own_bunkers() {
+    let mut app = setup();
+
+    let (engine, tender) = spawn_train(
+        &mut app,
+        SteamControls {
+            firing_rate: 1.0,
+            ..default()
+        },
+        10.0,
+        0.0,
+        Supplies {
+            coal: 10_000.0,
+            water: 30_000.0,
+        },
+    );
+    *app.world_mut().get_mut::<Supplies>(engine).unwrap() = Supplies {
+        coal: 3_000.0,
+        water: 9_000.0,
+    };
+
+    run_for(&mut app, 1);
+
+    assert!(app.world().get::<Supplies>(engine).unwrap().coal < 3_000.0);
+    assert!(app.world().get::<Mass>(engine).unwrap().0 < 84_000.0);
+    assert_eq!(app.world().get::<Supplies>(tender).unwrap().coal, 10_000.0);
+}
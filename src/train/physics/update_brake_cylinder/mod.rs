@@ -0,0 +1,49 @@
+#[cfg(test)]
+mod tests;
+
+use crate::train::{
+    AirPressure, AuxiliaryReservoir, BrakeCylinder, EngineOrWagons, FULL_APPLICATION_DROP,
+    MAX_AIR_PRESSURE, MAX_BRAKE_CYLINDER_PRESSURE,
+};
+use bevy::prelude::*;
+
+const APPLICATION_SPEED: f32 = 1.0; // bar/s
+const RELEASE_SPEED: f32 = 0.4; // bar/s
+const RECHARGE_SPEED: f32 = 0.2; // bar/s
+
+// reservoir pressure used per bar of brake cylinder pressure
+const RESERVOIR_RATIO: f32 = 0.3;
+
+// s for the distributor valve to fill an empty cylinder to full pressure
+pub fn full_application_time() -> f32 {
+    MAX_BRAKE_CYLINDER_PRESSURE / APPLICATION_SPEED
+}
+
+pub fn system(
+    mut entries: Query<(&AirPressure, &mut AuxiliaryReservoir, &mut BrakeCylinder), EngineOrWagons>,
+    time: Res<Time>,
+) {
+    let delta_seconds = time.delta_seconds();
+
+    for (air_pressure, mut reservoir, mut cylinder) in entries.iter_mut() {
+        // the distributor valve sets the cylinder according to the pipe pressure drop
+        let application =
+            ((MAX_AIR_PRESSURE - air_pressure.0) / FULL_APPLICATION_DROP).clamp(0.0, 1.0);
+        let target = application * MAX_BRAKE_CYLINDER_PRESSURE;
+
+        if target > cylinder.0 {
+            let amount = (target - cylinder.0)
+                .min(APPLICATION_SPEED * delta_seconds)
+                .min((reservoir.0 - cylinder.0).max(0.0));
+
+            cylinder.0 += amount;
+            reservoir.0 -= amount * RESERVOIR_RATIO;
+        } else {
+            cylinder.0 -= (cylinder.0 - target).min(RELEASE_SPEED * delta_seconds);
+        }
+
+        if air_pressure.0 > reservoir.0 {
+            reservoir.0 += (air_pressure.0 - reservoir.0).min(RECHARGE_SPEED * delta_seconds);
+        }
+    }
+}
@@ -0,0 +1,94 @@
+use super::*;
+use crate::train::Wagon;
+use coverage_helper::test;
+use std::time::Duration;
+
+#[coverage(off)]
+fn spawn_wagon(app: &mut App, air_pressure: f32) -> Entity {
+    app.world_mut()
+        .spawn((
+            Wagon,
+            AirPressure(air_pressure),
+            AuxiliaryReservoir::default(),
+            BrakeCylinder::default(),
+        ))
+        .id()
+}
+
+#[coverage(off)]
+fn run_for(app: &mut App, seconds: u64) {
+    for _ in 0..seconds * 10 {
+        let mut time = app.world_mut().resource_mut::<Time>();
+        time.advance_by(Duration::from_millis(100));
+        app.update();
+    }
+}
+
+#[test]
+fn full_pipe_no_brake() {
+    let mut app = App::new();
+    app.add_systems(Update, system);
+    app.init_resource::<Time>();
+
+    let wagon_id = spawn_wagon(&mut app, MAX_AIR_PRESSURE);
+
+    run_for(&mut app, 1);
+
+    assert_eq!(app.world().get::<BrakeCylinder>(wagon_id).unwrap().0, 0.0);
+    assert_eq!(
+        app.world().get::<AuxiliaryReservoir>(wagon_id).unwrap().0,
+        MAX_AIR_PRESSURE
+    );
+}
+
+#[test]
+fn pipe_drop_applies_brake() {
+    let mut app = App::new();
+    app.add_systems(Update, system);
+    app.init_resource::<Time>();
+
+    let service_id = spawn_wagon(&mut app, MAX_AIR_PRESSURE - 0.5);
+    let full_id = spawn_wagon(&mut app, MAX_AIR_PRESSURE - FULL_APPLICATION_DROP);
+    let emergency_id = spawn_wagon(&mut app, 0.0);
+
+    run_for(&mut app, 10);
+
+    let cylinder = |entity: Entity| app.world().get::<BrakeCylinder>(entity).unwrap().0;
+
+    assert!(cylinder(service_id) > 0.0);
+    assert!(cylinder(service_id) < cylinder(full_id));
+    assert!((cylinder(full_id) - MAX_BRAKE_CYLINDER_PRESSURE).abs() < 0.001);
+    assert_eq!(cylinder(full_id), cylinder(emergency_id));
+
+    // applying the brake drains the auxiliary reservoir
+    assert!(app.world().get::<AuxiliaryReservoir>(full_id).unwrap().0 < MAX_AIR_PRESSURE);
+}
+
+#[test]
+fn pipe_refill_releases_and_recharges() {
+    let mut app = App::new();
+    app.add_systems(Update, system);
+    app.init_resource::<Time>();
+
+    let wagon_id = spawn_wagon(&mut app, MAX_AIR_PRESSURE - FULL_APPLICATION_DROP);
+
+    run_for(&mut app, 10);
+
+    let drained_reservoir = app.world().get::<AuxiliaryReservoir>(wagon_id).unwrap().0;
+
+    app.world_mut().get_mut::<AirPressure>(wagon_id).unwrap().0 = MAX_AIR_PRESSURE;
+
+    run_for(&mut app, 1);
+
+    // release takes time
+    assert!(app.world().get::<BrakeCylinder>(wagon_id).unwrap().0 > 0.0);
+    assert!(app.world().get::<AuxiliaryReservoir>(wagon_id).unwrap().0 > drained_reservoir);
+
+    run_for(&mut app, 20);
+
+    assert_eq!(app.world().get::<BrakeCylinder>(wagon_id).unwrap().0, 0.0);
+    assert_eq!(
+        app.world().get::<AuxiliaryReservoir>(wagon_id).unwrap().0,
+        MAX_AIR_PRESSURE
+    );
+}
@@ -0,0 +1,128 @@
+#[cfg(test)]
+mod tests;
+
+use crate::train::{
+    AirPressure, AirPressureDelta, BrakePipe, Dimension, TrainComposition, WrappedValue,
+};
+use bevy::prelude::*;
+
+// speed at which pressure changes travel along the brake pipe
+const PROPAGATION_SPEED: f32 = 250.0; // m/s
+
+// m from the vehicle at `valve` to each vehicle of a consist with the given
+// lengths (m), measured between their centres
+pub fn valve_distances(lengths: &[f32], valve: usize) -> Vec<f32> {
+    let mut start = 0.0;
+    let centres: Vec<f32> = lengths
+        .iter()
+        .map(|length| {
+            let centre = start + length / 2.0;
+            start += length;
+            centre
+        })
+        .collect();
+
+    centres
+        .iter()
+        .map(|centre| (centre - centres[valve]).abs())
+        .collect()
+}
+
+// s until a pressure change at the driver's brake valve reaches a vehicle
+// the given distance (m) away
+pub fn propagation_time(distance: f32) -> f32 {
+    distance / PROPAGATION_SPEED
+}
+
+// s until a pressure change at the valve of the vehicle at `valve` has
+// reached every vehicle of a consist with the given lengths (m)
+pub fn arrival_time(lengths: &[f32], valve: usize) -> f32 {
+    propagation_time(
+        valve_distances(lengths, valve)
+            .into_iter()
+            .fold(0.0, f32::max),
+    )
+}
+
+// bar, what the valve had set the pipe to at the given time. none while
+// the first change has not arrived yet
+fn pressure_at(history: &[(f32, f32)], time: f32) -> Option<f32> {
+    let after = history.iter().position(|(at, _)| *at > time);
+
+    match after {
+        Some(0) => None,
+        Some(index) => {
+            let (from_time, from) = history[index - 1];
+            let (to_time, to) = history[index];
+            let share = (time - from_time) / (to_time - from_time);
+            Some(from + (to - from) * share)
+        }
+        None => history.last().map(|(_, pressure)| *pressure),
+    }
+}
+
+pub fn system(
+    mut trains: Query<(&TrainComposition, &mut BrakePipe)>,
+    deltas: Query<&AirPressureDelta>,
+    mut pipes: Query<(&mut AirPressure, &Dimension)>,
+    time: Res<Time>,
+) {
+    for (composition, mut pipe) in trains.iter_mut() {
+        let entities = composition.entities();
+        let Some(valve) = composition
+            .controlling_engine()
+            .and_then(|engine| entities.iter().position(|entity| *entity == engine))
+        else {
+            continue;
+        };
+
+        // the driver's brake valve feeds or vents the pipe at its engine
+        let Ok((mut air_pressure, _)) = pipes.get_mut(entities[valve]) else {
+            continue;
+        };
+        if let Ok(delta) = deltas.get(entities[valve]) {
+            let value = air_pressure.get() + delta.get();
+            air_pressure.set(value);
+        }
+        let pressure = air_pressure.0;
+
+        pipe.time += time.delta_seconds();
+        let now = pipe.time;
+        pipe.history.push_back((now, pressure));
+
+        let lengths: Vec<f32> = entities
+            .iter()
+            .map(|entity| {
+                pipes
+                    .get(*entity)
+                    .map_or(0.0, |(_, dimension)| dimension.length)
+            })
+            .collect();
+        let distances = valve_distances(&lengths, valve);
+
+        // only keep what the farthest vehicle still has to follow
+        let arrival = arrival_time(&lengths, valve);
+        while pipe
+            .history
+            .get(1)
+            .is_some_and(|(at, _)| *at <= now - arrival)
+        {
+            pipe.history.pop_front();
+        }
+
+        // every other vehicle's pipe follows once the change arrives there,
+        // venting or filling locally at the same rate as the valve
+        let history = pipe.history.make_contiguous();
+        for (index, (entity, distance)) in entities.iter().zip(distances).enumerate() {
+            if index == valve {
+                continue;
+            }
+            let Some(pressure) = pressure_at(history, now - propagation_time(distance)) else {
+                continue;
+            };
+            if let Ok((mut air_pressure, _)) = pipes.get_mut(*entity) {
+                air_pressure.set(pressure);
+            }
+        }
+    }
+}
@@ -0,0 +1,128 @@
+use super::*;
+use crate::train::{TrainComponent, FULL_APPLICATION_DROP, MAX_AIR_PRESSURE};
+use coverage_helper::test;
+use std::time::Duration;
+
+#[coverage(off)]
+fn spawn_train(app: &mut App, wagons: usize) -> Vec<Entity> {
+    let mut entities = vec![app
+        .world_mut()
+        .spawn((
+            AirPressure::default(),
+            AirPressureDelta(0.0),
+            Dimension { length: 16.0 },
+        ))
+        .id()];
+
+    for _ in 0..wagons {
+        entities.push(
+            app.world_mut()
+                .spawn((AirPressure::default(), Dimension { length: 16.0 }))
+                .id(),
+        );
+    }
+
+    let mut components = vec![TrainComponent::Engine(entities[0])];
+    for entity in entities.iter().skip(1) {
+        components.push(TrainComponent::Wagon(*entity));
+    }
+
+    app.world_mut()
+        .spawn((TrainComposition { components }, BrakePipe::default()));
+
+    entities
+}
+
+#[coverage(off)]
+fn advance_time(app: &mut App, millis: u64) {
+    let mut time = app.world_mut().resource_mut::<Time>();
+    time.advance_by(Duration::from_millis(millis));
+}
+
+#[coverage(off)]
+fn pressure(app: &App, entity: Entity) -> f32 {
+    app.world().get::<AirPressure>(entity).unwrap().0
+}
+
+#[test]
+fn no_change_without_delta() {
+    let mut app = App::new();
+    app.add_systems(Update, system);
+    app.init_resource::<Time>();
+
+    let entities = spawn_train(&mut app, 3);
+
+    advance_time(&mut app, 100);
+    app.update();
+
+    for entity in entities {
+        assert_eq!(pressure(&app, entity), MAX_AIR_PRESSURE);
+    }
+}
+
+#[test]
+fn pressure_drop_reaches_the_end_in_time() {
+    let mut app = App::new();
+    app.add_systems(Update, system);
+    app.init_resource::<Time>();
+
+    // 400 m between the centres of the engine and the last wagon
+    let entities = spawn_train(&mut app, 25);
+    let arrival = 400.0 / 250.0;
+    // s the valve needs to vent a full application at 10 bar/s
+    let venting = FULL_APPLICATION_DROP / 10.0;
+
+    let mut elapsed = 0.0;
+    while pressure(&app, entities[25]) > MAX_AIR_PRESSURE - FULL_APPLICATION_DROP {
+        // nothing arrives before the front could have got there
+        if elapsed < arrival {
+            assert_eq!(pressure(&app, entities[25]), MAX_AIR_PRESSURE);
+        }
+
+        app.world_mut()
+            .get_mut::<AirPressureDelta>(entities[0])
+            .unwrap()
+            .0 = -10.0 * 0.016;
+        advance_time(&mut app, 16);
+        app.update();
+        elapsed += 0.016;
+
+        assert!(elapsed < arrival + venting + 0.05);
+    }
+}
+
+#[test]
+fn distances_from_the_valve() {
+    assert_eq!(
+        valve_distances(&[20.0, 10.0, 10.0], 0),
+        vec![0.0, 15.0, 25.0]
+    );
+    assert_eq!(
+        valve_distances(&[10.0, 20.0, 10.0], 1),
+        vec![15.0, 0.0, 15.0]
+    );
+    assert_eq!(propagation_time(500.0), 2.0);
+    assert_eq!(arrival_time(&[10.0, 20.0, 10.0], 1), 15.0 / 250.0);
+}
+
+#[test]
+fn low_frame_rate_stays_stable() {
+    let mut app = App::new();
+    app.add_systems(Update, system);
+    app.init_resource::<Time>();
+
+    let entities = spawn_train(&mut app, 3);
+    app.world_mut()
+        .get_mut::<AirPressureDelta>(entities[0])
+        .unwrap()
+        .0 = -1.0;
+
+    advance_time(&mut app, 1000);
+    app.update();
+
+    for entity in entities {
+        let pressure = pressure(&app, entity);
+        assert!(pressure >= MAX_AIR_PRESSURE - 1.0);
+        assert!(pressure <= MAX_AIR_PRESSURE);
+    }
+}
@@ -1,16 +1,76 @@
 #[cfg(test)]
 mod tests;
 
-use super::{AirPressure, EngineOrWagons, ForceBraking, Mass, MAX_AIR_PRESSURE};
+use super::{
+    BrakeCylinder, EngineOrWagons, ForceBraking, IndependentBrakeCylinder, Mass, RailCondition,
+    Speed, WheelSlip, MAX_BRAKE_CYLINDER_PRESSURE,
+};
 use bevy::prelude::*;
 
-pub fn system(mut entries: Query<(&mut ForceBraking, &Mass, &AirPressure), EngineOrWagons>) {
-    let friction_coefficient = 0.3;
-    let g = 9.81;
+// share of the adhesion that remains once the wheels lock
+const SLIDING_ADHESION: f32 = 0.6;
+// brake shoes on the wheel at full cylinder pressure
+const BRAKE_FRICTION_COEFFICIENT: f32 = 0.3;
+const G: f32 = 9.81;
 
-    for (mut braking, mass, air_pressure) in entries.iter_mut() {
-        let n = mass.0 * g;
-        let pressure_percentage = (MAX_AIR_PRESSURE - air_pressure.0) / MAX_AIR_PRESSURE;
-        braking.0 = friction_coefficient * n * pressure_percentage;
+// N, what the brake shoes of a mass demand at the given cylinder pressure
+fn shoe_force(mass: f32, pressure: f32) -> f32 {
+    BRAKE_FRICTION_COEFFICIENT * mass * G * pressure / MAX_BRAKE_CYLINDER_PRESSURE
+}
+
+// N, what the brakes of a mass do at full cylinder pressure. where the shoes
+// demand more than the adhesion the wheels lock and only slide on. the force
+// is linear in the mass, so a whole train brakes like one vehicle of its mass
+pub fn max_braking_force(mass: f32, speed: f32, rail_condition: &RailCondition) -> f32 {
+    let demanded_force = shoe_force(mass, MAX_BRAKE_CYLINDER_PRESSURE);
+    let adhesion_force = mass * G * rail_condition.adhesion_coefficient(speed, false);
+    if demanded_force > adhesion_force {
+        adhesion_force * SLIDING_ADHESION
+    } else {
+        demanded_force
+    }
+}
+
+type BrakingForceQuery<'a> = (
+    &'a mut ForceBraking,
+    &'a mut WheelSlip,
+    &'a Mass,
+    &'a Speed,
+    &'a BrakeCylinder,
+    Option<&'a IndependentBrakeCylinder>,
+);
+
+pub fn system(
+    mut entries: Query<BrakingForceQuery, EngineOrWagons>,
+    rail_condition: Res<RailCondition>,
+) {
+    for (mut braking, mut wheel_slip, mass, speed, brake_cylinder, independent_brake_cylinder) in
+        entries.iter_mut()
+    {
+        let n = mass.0 * G;
+        // the engine brake acts on the same brake rigging via a double check valve
+        let pressure = independent_brake_cylinder.map_or(brake_cylinder.0, |independent| {
+            brake_cylinder.0.max(independent.0)
+        });
+        let demanded_force = shoe_force(mass.0, pressure);
+
+        let adhesion_force = n * rail_condition.adhesion_coefficient(speed.0, false);
+
+        let limit = if *wheel_slip == WheelSlip::Sliding {
+            adhesion_force * SLIDING_ADHESION
+        } else {
+            adhesion_force
+        };
+
+        // a standing train can not slide
+        braking.0 = if demanded_force > limit && speed.0 != 0.0 {
+            *wheel_slip = WheelSlip::Sliding;
+            adhesion_force * SLIDING_ADHESION
+        } else {
+            if *wheel_slip == WheelSlip::Sliding {
+                *wheel_slip = WheelSlip::Gripping;
+            }
+            demanded_force
+        };
     }
 }
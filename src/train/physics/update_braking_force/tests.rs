@@ -3,9 +3,16 @@ use crate::train::Engine;
 use coverage_helper::test;
 
 #[coverage(off)]
-fn spawn_engine(app: &mut App, air_pressure: AirPressure, mass: f32) -> Entity {
+fn spawn_engine(app: &mut App, brake_cylinder: BrakeCylinder, mass: f32) -> Entity {
     app.world_mut()
-        .spawn((Engine, ForceBraking::default(), Mass(mass), air_pressure))
+        .spawn((
+            Engine,
+            ForceBraking::default(),
+            WheelSlip::default(),
+            Mass(mass),
+            Speed(0.0),
+            brake_cylinder,
+        ))
         .id()
 }
 
@@ -13,8 +20,9 @@ fn spawn_engine(app: &mut App, air_pressure: AirPressure, mass: f32) -> Entity {
 fn no_brake_no_force() {
     let mut app = App::new();
     app.add_systems(Update, system);
+    app.init_resource::<RailCondition>();
 
-    let engine_id = spawn_engine(&mut app, AirPressure(MAX_AIR_PRESSURE), 7000.0);
+    let engine_id = spawn_engine(&mut app, BrakeCylinder(0.0), 7000.0);
 
     app.update();
 
@@ -26,8 +34,9 @@ fn no_brake_no_force() {
 fn brake_applies_force() {
     let mut app = App::new();
     app.add_systems(Update, system);
+    app.init_resource::<RailCondition>();
 
-    let engine_id = spawn_engine(&mut app, AirPressure(4.8), 7000.0);
+    let engine_id = spawn_engine(&mut app, BrakeCylinder(0.2), 7000.0);
 
     app.update();
 
@@ -39,10 +48,11 @@ fn brake_applies_force() {
 fn more_brake_more_force() {
     let mut app = App::new();
     app.add_systems(Update, system);
+    app.init_resource::<RailCondition>();
 
-    let low_brake = spawn_engine(&mut app, AirPressure(4.8), 7000.);
+    let low_brake = spawn_engine(&mut app, BrakeCylinder(0.2), 7000.);
 
-    let high_brake = spawn_engine(&mut app, AirPressure(0.0), 7000.);
+    let high_brake = spawn_engine(&mut app, BrakeCylinder(MAX_BRAKE_CYLINDER_PRESSURE), 7000.);
 
     app.update();
 
@@ -54,14 +64,36 @@ fn more_brake_more_force() {
     );
 }
 
+#[test]
+fn engine_brake_applies_force() {
+    let mut app = App::new();
+    app.add_systems(Update, system);
+    app.init_resource::<RailCondition>();
+
+    let train_brake = spawn_engine(&mut app, BrakeCylinder(0.2), 7000.);
+
+    let engine_brake = spawn_engine(&mut app, BrakeCylinder(0.2), 7000.);
+    app.world_mut()
+        .entity_mut(engine_brake)
+        .insert(IndependentBrakeCylinder(1.0));
+
+    app.update();
+
+    assert!(
+        app.world().get::<ForceBraking>(train_brake).unwrap().0
+            < app.world().get::<ForceBraking>(engine_brake).unwrap().0
+    );
+}
+
 #[test]
 fn more_weight_more_brake() {
     let mut app = App::new();
     app.add_systems(Update, system);
+    app.init_resource::<RailCondition>();
 
-    let low_weight = spawn_engine(&mut app, AirPressure(4.8), 7000.);
+    let low_weight = spawn_engine(&mut app, BrakeCylinder(0.2), 7000.);
 
-    let high_weight = spawn_engine(&mut app, AirPressure(4.8), 70000.);
+    let high_weight = spawn_engine(&mut app, BrakeCylinder(0.2), 70000.);
 
     app.update();
 
@@ -72,3 +104,60 @@ fn more_weight_more_brake() {
             < app.world().get::<ForceBraking>(high_weight).unwrap().0
     );
 }
+
+#[test]
+fn hard_braking_at_speed_slides() {
+    let mut app = App::new();
+    app.add_systems(Update, system);
+    app.insert_resource(RailCondition::Wet);
+
+    let engine_id = spawn_engine(
+        &mut app,
+        BrakeCylinder(MAX_BRAKE_CYLINDER_PRESSURE),
+        70000.0,
+    );
+    app.world_mut().get_mut::<Speed>(engine_id).unwrap().0 = 30.0;
+
+    app.update();
+
+    assert_eq!(
+        *app.world().get::<WheelSlip>(engine_id).unwrap(),
+        WheelSlip::Sliding
+    );
+    let sliding_force = app.world().get::<ForceBraking>(engine_id).unwrap().0;
+
+    // releasing the brake a bit is not enough to regain grip
+    *app.world_mut().get_mut::<BrakeCylinder>(engine_id).unwrap() = BrakeCylinder(2.66);
+
+    app.update();
+
+    assert_eq!(
+        *app.world().get::<WheelSlip>(engine_id).unwrap(),
+        WheelSlip::Sliding
+    );
+
+    *app.world_mut().get_mut::<BrakeCylinder>(engine_id).unwrap() = BrakeCylinder(0.76);
+
+    app.update();
+
+    assert_eq!(
+        *app.world().get::<WheelSlip>(engine_id).unwrap(),
+        WheelSlip::Gripping
+    );
+    assert!(sliding_force > 0.0);
+}
+
+#[test]
+fn max_force_is_limited_by_adhesion() {
+    // at low speeds the brake shoes limit the force
+    let dry = max_braking_force(10_000.0, 0.0, &RailCondition::Dry);
+    assert!((dry - 0.3 * 10_000.0 * 9.81).abs() < 0.1);
+
+    // fast or on wet rails the wheels lock and slide
+    let fast = max_braking_force(10_000.0, 50.0, &RailCondition::Dry);
+    let wet = max_braking_force(10_000.0, 0.0, &RailCondition::Wet);
+    assert!(fast < dry);
+    assert!(wet < dry);
+    let sliding = 10_000.0 * 9.81 * RailCondition::Dry.adhesion_coefficient(50.0, false) * 0.6;
+    assert!((fast - sliding).abs() < 0.1);
+}
@@ -0,0 +1,174 @@
+#[cfg(test)]
+mod tests;
+
+use super::{
+    update_brake_cylinder::full_application_time, update_brake_pipe::arrival_time,
+    update_braking_force::max_braking_force,
+};
+use crate::{
+    landscape::{
+        index_signals, movement_authority, Aspect, OSMData, Route, Signal, SwitchPositions,
+    },
+    train::{
+        BrakeIntervention, BrakeLever, CabSignalling, CabTarget, Dimension, Direction,
+        ForceGradient, Mass, MaxSpeed, RailCondition, RotatingMass, Speed, SpeedLimit,
+        SupervisionStatus, TrackLocation, TrainComposition, CAB_SIGNALLING_LOOKAHEAD, STANDSTILL,
+    },
+};
+use bevy::prelude::*;
+
+// m/s², what the curves assume at least, even on steep downhill grades
+const MIN_DECELERATION: f32 = 0.1;
+
+type CabSignallingQuery<'a> = (
+    &'a TrainComposition,
+    &'a Speed,
+    &'a MaxSpeed,
+    &'a Mass,
+    &'a RotatingMass,
+    &'a ForceGradient,
+    &'a SpeedLimit,
+    &'a mut CabSignalling,
+);
+
+pub fn system(
+    (data, switches, route, rail_condition): (
+        Res<OSMData>,
+        Res<SwitchPositions>,
+        Option<Res<Route>>,
+        Res<RailCondition>,
+    ),
+    mut trains: Query<CabSignallingQuery>,
+    (locations, dimensions): (Query<&TrackLocation>, Query<&Dimension>),
+    signals: Query<(Entity, &Signal)>,
+    mut brake_levers: Query<&mut BrakeLever>,
+    time: Res<Time>,
+) {
+    let stop_signals = index_signals(
+        &data,
+        signals
+            .iter()
+            .filter(|(_, signal)| signal.kind.is_main() && signal.aspect == Aspect::Stop),
+    );
+    // a route that only holds the start leads nowhere
+    let route_end = route
+        .as_ref()
+        .filter(|route| route.legs.len() > 1)
+        .and_then(|route| route.legs.last().copied());
+
+    for (composition, speed, max_speed, mass, rotating_mass, gradient, speed_limit, mut cab) in
+        trains.iter_mut()
+    {
+        let entities = composition.entities();
+        let (direction, leading) = if speed.0 < 0.0 {
+            (Direction::Backward, entities.last())
+        } else {
+            (Direction::Forward, entities.first())
+        };
+        let leading = leading.and_then(|entity| locations.get(*entity).ok());
+        let speed = speed.0.abs();
+
+        let (true, Some(ceiling), Some(leading)) = (cab.active, speed_limit.current, leading)
+        else {
+            *cab = CabSignalling {
+                active: cab.active,
+                ..default()
+            };
+            for entity in entities {
+                if let Ok(mut brake_lever) = brake_levers.get_mut(entity) {
+                    brake_lever.set_intervention(BrakeIntervention::CabSignalling, false);
+                }
+            }
+            continue;
+        };
+
+        let authority = movement_authority(
+            &data,
+            &switches,
+            &leading.facing(&data, direction),
+            &stop_signals,
+            route_end,
+            CAB_SIGNALLING_LOOKAHEAD,
+        )
+        .map(|(distance, _)| distance);
+
+        let mut targets: Vec<CabTarget> = leading
+            .speed_limits_ahead(&data, &switches, direction, CAB_SIGNALLING_LOOKAHEAD)
+            .into_iter()
+            .skip(1)
+            .map(|(distance, kmh)| CabTarget {
+                speed: (kmh / 3.6).min(max_speed.0),
+                distance,
+            })
+            .collect();
+        if let Some(distance) = authority {
+            targets.push(CabTarget {
+                speed: 0.0,
+                distance,
+            });
+        }
+
+        // the gradient pushes along the travel direction of the vehicles
+        let downhill = match direction {
+            Direction::Forward => gradient.0,
+            Direction::Backward => -gradient.0,
+        };
+        let effective_mass = mass.0 + rotating_mass.0;
+        if effective_mass <= 0.0 {
+            continue;
+        }
+        // an intervention applies the full cylinder pressure on every
+        // vehicle. adhesion drops with speed, so taking it at the ceiling
+        // errs on the safe side
+        let deceleration = ((max_braking_force(mass.0, ceiling, &rail_condition) - downhill)
+            / effective_mass)
+            .max(MIN_DECELERATION);
+        // the drop travels from the controlling engine's valve to the farthest
+        // vehicle, whose pipe vents faster than its cylinder then fills. the
+        // curves count all of it as time without any braking, which errs on
+        // the safe side as well
+        let lengths: Vec<f32> = entities
+            .iter()
+            .map(|entity| {
+                dimensions
+                    .get(*entity)
+                    .map_or(0.0, |dimension| dimension.length)
+            })
+            .collect();
+        let valve = composition
+            .controlling_engine()
+            .and_then(|engine| entities.iter().position(|entity| *entity == engine))
+            .unwrap_or_default();
+        let build_up_time = arrival_time(&lengths, valve) + full_application_time();
+
+        // the end of the authority was left behind during the last tick
+        let passed_authority = speed >= STANDSTILL
+            && cab
+                .authority
+                .is_some_and(|distance| distance <= (speed * time.delta_seconds()) as f64);
+
+        cab.supervise(ceiling, &targets, deceleration, build_up_time);
+        cab.authority = authority;
+
+        let status = if passed_authority
+            || (cab.status == SupervisionStatus::Intervention && speed >= STANDSTILL)
+        {
+            SupervisionStatus::Intervention
+        } else {
+            cab.status_at(speed)
+        };
+        if status == SupervisionStatus::Intervention && cab.status != status {
+            log::warn!("cab signalling intervention at {:.0} km/h", speed * 3.6);
+        }
+        cab.status = status;
+
+        for entity in entities {
+            if let Ok(mut brake_lever) = brake_levers.get_mut(entity) {
+                brake_lever.set_intervention(
+                    BrakeIntervention::CabSignalling,
+                    status == SupervisionStatus::Intervention,
+                );
+            }
+        }
+    }
+}
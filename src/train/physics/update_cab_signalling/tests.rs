@@ -0,0 +1,206 @@
+use super::*;
+use crate::{
+    landscape::{CoordinatePoint, Path, SignalKind},
+    train::TrainComponent,
+};
+use coverage_helper::test;
+use std::time::Duration;
+
+// three straight rails of 1 km in a row
+#[coverage(off)]
+fn gen_data() -> OSMData {
+    let mut rails = std::collections::HashMap::default();
+    for i in 0..3 {
+        let mut path = Path {
+            start_id: i,
+            end_id: i + 1,
+            start_coords: CoordinatePoint(i as f64 * 1000.0, 0.0),
+            end_coords: CoordinatePoint((i + 1) as f64 * 1000.0, 0.0),
+            ..default()
+        };
+        if i > 0 {
+            path.backward_connections = vec![((i - 1, i), Direction::Backward)];
+        }
+        if i < 2 {
+            path.forward_connections = vec![((i + 1, i + 2), Direction::Forward)];
+        }
+        rails.insert(path.id(), path);
+    }
+    OSMData { rails, ..default() }
+}
+
+#[coverage(off)]
+fn location(id: (i64, i64), distance: f64) -> TrackLocation {
+    TrackLocation {
+        id,
+        distance,
+        travel_direction: Direction::Forward,
+    }
+}
+
+#[coverage(off)]
+fn setup(active: bool) -> (App, Entity, Entity) {
+    let mut app = App::new();
+    app.add_systems(Update, system);
+    app.insert_resource(gen_data())
+        .init_resource::<SwitchPositions>()
+        .init_resource::<RailCondition>()
+        .init_resource::<Time>();
+
+    // a main signal at stop in the middle of the second rail
+    app.world_mut().spawn(Signal {
+        index: 0,
+        kind: SignalKind::Main,
+        location: location((1, 2), 500.0),
+        aspect: Aspect::Stop,
+    });
+
+    let engine = app
+        .world_mut()
+        .spawn((BrakeLever::default(), location((0, 1), 100.0)))
+        .id();
+    let train = app
+        .world_mut()
+        .spawn((
+            TrainComposition {
+                components: vec![TrainComponent::Engine(engine)],
+            },
+            Speed(20.0),
+            MaxSpeed(50.0),
+            Mass(100_000.0),
+            RotatingMass(0.0),
+            ForceGradient(0.0),
+            SpeedLimit {
+                current: Some(30.0),
+                next: None,
+            },
+            CabSignalling {
+                active,
+                ..default()
+            },
+        ))
+        .id();
+
+    (app, train, engine)
+}
+
+// one second at the given speed with the engine at `to`
+#[coverage(off)]
+fn drive(app: &mut App, train: Entity, engine: Entity, speed: f32, to: TrackLocation) {
+    app.world_mut().get_mut::<Speed>(train).unwrap().0 = speed;
+    app.world_mut().entity_mut(engine).insert(to);
+
+    let mut time = app.world_mut().resource_mut::<Time>();
+    time.advance_by(Duration::from_secs(1));
+    app.update();
+}
+
+#[coverage(off)]
+fn is_braking(app: &App, engine: Entity) -> bool {
+    app.world()
+        .get::<BrakeLever>(engine)
+        .unwrap()
+        .interventions
+        .contains(&BrakeIntervention::CabSignalling)
+}
+
+#[test]
+fn inactive_supervises_nothing() {
+    let (mut app, train, _) = setup(false);
+    app.update();
+
+    let cab_signalling = app.world().get::<CabSignalling>(train).unwrap();
+    assert_eq!(cab_signalling.authority, None);
+    assert_eq!(cab_signalling.target, None);
+}
+
+#[test]
+fn authority_ends_at_the_stop_signal() {
+    let (mut app, train, engine) = setup(true);
+    app.update();
+
+    let cab_signalling = app.world().get::<CabSignalling>(train).unwrap();
+    assert_eq!(cab_signalling.authority, Some(1400.0));
+    assert_eq!(
+        cab_signalling.target,
+        Some(CabTarget {
+            speed: 0.0,
+            distance: 1400.0,
+        })
+    );
+    assert_eq!(cab_signalling.permitted, 30.0);
+    assert_eq!(cab_signalling.status, SupervisionStatus::Normal);
+    assert!(!is_braking(&app, engine));
+}
+
+#[test]
+fn intervenes_close_to_the_signal() {
+    let (mut app, train, engine) = setup(true);
+
+    drive(&mut app, train, engine, 20.0, location((1, 2), 400.0));
+    let cab_signalling = app.world().get::<CabSignalling>(train).unwrap();
+    assert!(cab_signalling.permitted < 20.0);
+    assert_eq!(cab_signalling.status, SupervisionStatus::Intervention);
+    assert!(is_braking(&app, engine));
+
+    // held while the train is slowing down
+    drive(&mut app, train, engine, 1.0, location((1, 2), 450.0));
+    assert!(is_braking(&app, engine));
+
+    drive(&mut app, train, engine, 0.0, location((1, 2), 450.0));
+    assert!(!is_braking(&app, engine));
+}
+
+#[test]
+fn passing_the_end_of_authority_intervenes() {
+    let (mut app, train, engine) = setup(true);
+
+    drive(&mut app, train, engine, 1.0, location((1, 2), 499.5));
+    assert_ne!(
+        app.world().get::<CabSignalling>(train).unwrap().status,
+        SupervisionStatus::Intervention
+    );
+
+    drive(&mut app, train, engine, 1.0, location((1, 2), 500.5));
+    assert_eq!(
+        app.world().get::<CabSignalling>(train).unwrap().status,
+        SupervisionStatus::Intervention
+    );
+    assert!(is_braking(&app, engine));
+}
+
+#[test]
+fn authority_ends_with_the_route() {
+    let (mut app, train, _) = setup(true);
+    let mut signals = app.world_mut().query::<&mut Signal>();
+    signals.single_mut(app.world_mut()).aspect = Aspect::Clear;
+    app.insert_resource(Route {
+        legs: vec![((0, 1), Direction::Forward), ((1, 2), Direction::Forward)],
+        ..default()
+    });
+
+    app.update();
+
+    let cab_signalling = app.world().get::<CabSignalling>(train).unwrap();
+    assert_eq!(cab_signalling.authority, Some(1900.0));
+}
+
+#[test]
+fn longer_trains_brake_earlier() {
+    let (mut app, train, engine) = setup(true);
+
+    drive(&mut app, train, engine, 20.0, location((1, 2), 200.0));
+    let short = app.world().get::<CabSignalling>(train).unwrap().permitted;
+
+    // the brake pipe takes longer to vent to the end of the train
+    let wagon = app.world_mut().spawn(Dimension { length: 700.0 }).id();
+    app.world_mut()
+        .get_mut::<TrainComposition>(train)
+        .unwrap()
+        .components
+        .push(TrainComponent::Wagon(wagon));
+    drive(&mut app, train, engine, 20.0, location((1, 2), 200.0));
+    let long = app.world().get::<CabSignalling>(train).unwrap().permitted;
+
+    assert!(long < short);
+}
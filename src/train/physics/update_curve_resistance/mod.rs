@@ -0,0 +1,41 @@
+#[cfg(test)]
+mod tests;
+
+use crate::{
+    landscape::OSMData,
+    train::{EngineOrWagons, ForceCurveResistance, Mass, TrackLocation},
+};
+use bevy::prelude::*;
+
+const G: f32 = 9.81;
+// tighter radii are most likely artifacts of the OSM geometry
+const MIN_CURVE_RADIUS: f64 = 100.0;
+
+// specific curve resistance (‰) for standard gauge after Röckl, see
+// Wende: Fahrdynamik des Schienenverkehrs
+fn specific_curve_resistance(radius: f64) -> f32 {
+    let radius = radius.max(MIN_CURVE_RADIUS);
+
+    if radius.is_infinite() {
+        0.0
+    } else if radius >= 300.0 {
+        (650.0 / (radius - 55.0)) as f32
+    } else {
+        (500.0 / (radius - 30.0)) as f32
+    }
+}
+
+pub fn system(
+    data: Res<OSMData>,
+    mut entries: Query<(&mut ForceCurveResistance, &Mass, &TrackLocation), EngineOrWagons>,
+) {
+    for (mut curve_resistance, mass, location) in entries.iter_mut() {
+        let rail = data
+            .rails
+            .get(&location.id)
+            .expect("train location to be valid");
+
+        let n = mass.0 * G;
+        curve_resistance.0 = specific_curve_resistance(rail.curve_radius()) / 1000.0 * n;
+    }
+}
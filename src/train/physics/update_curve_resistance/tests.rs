@@ -0,0 +1,79 @@
+use super::*;
+use crate::{
+    landscape::{CoordinatePoint, Path},
+    train::{Direction, Engine},
+};
+use coverage_helper::test;
+use std::collections::HashMap;
+
+#[coverage(off)]
+fn gen_data() -> OSMData {
+    let mut rails = HashMap::default();
+    rails.insert(
+        (0, 1),
+        Path {
+            start_id: 0,
+            end_id: 1,
+            start_coords: CoordinatePoint(0.0, 0.0),
+            end_coords: CoordinatePoint(100.0, 0.0),
+            ..default()
+        },
+    );
+    rails.insert(
+        (1, 2),
+        Path {
+            start_id: 1,
+            end_id: 2,
+            start_coords: CoordinatePoint(100.0, 0.0),
+            end_coords: CoordinatePoint(200.0, 10.0),
+            curvature: 1.0 / 400.0,
+            ..default()
+        },
+    );
+    OSMData { rails, ..default() }
+}
+
+#[coverage(off)]
+fn spawn_engine(app: &mut App, id: (i64, i64)) -> Entity {
+    app.world_mut()
+        .spawn((
+            Engine,
+            ForceCurveResistance::default(),
+            Mass(80000.0),
+            TrackLocation {
+                id,
+                distance: 10.0,
+                travel_direction: Direction::Forward,
+            },
+        ))
+        .id()
+}
+
+#[test]
+fn curve_has_resistance() {
+    let mut app = App::new();
+    app.add_systems(Update, system);
+    app.insert_resource(gen_data());
+
+    let straight = spawn_engine(&mut app, (0, 1));
+    let curve = spawn_engine(&mut app, (1, 2));
+
+    app.update();
+
+    assert_eq!(
+        app.world().get::<ForceCurveResistance>(straight).unwrap().0,
+        0.0
+    );
+    assert!(app.world().get::<ForceCurveResistance>(curve).unwrap().0 > 0.0);
+}
+
+#[test]
+fn tighter_curves_more_resistance() {
+    assert_eq!(specific_curve_resistance(f64::INFINITY), 0.0);
+    assert!(specific_curve_resistance(1000.0) < specific_curve_resistance(500.0));
+    assert!(specific_curve_resistance(500.0) < specific_curve_resistance(200.0));
+    assert_eq!(
+        specific_curve_resistance(10.0),
+        specific_curve_resistance(MIN_CURVE_RADIUS)
+    );
+}
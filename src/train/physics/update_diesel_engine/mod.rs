@@ -0,0 +1,55 @@
+#[cfg(test)]
+mod tests;
+
+use crate::train::{
+    DieselEngine, DieselState, EnergyMeter, Speed, ThrottleLever, TransmissionKind,
+    DIESEL_ENERGY_DENSITY, SHIFT_TIME,
+};
+use bevy::prelude::*;
+
+pub fn system(
+    mut entries: Query<(
+        &DieselEngine,
+        &mut DieselState,
+        &mut EnergyMeter,
+        &ThrottleLever,
+        &Speed,
+    )>,
+    time: Res<Time>,
+) {
+    let delta_seconds = time.delta_seconds();
+
+    for (diesel_engine, mut state, mut energy_meter, throttle_lever, speed) in entries.iter_mut() {
+        if !diesel_engine.is_available() {
+            continue;
+        }
+
+        // the engine stalls once the tank runs dry
+        let target_rpm = if state.fuel > 0.0 {
+            diesel_engine.target_rpm(throttle_lever.notch())
+        } else {
+            0.0
+        };
+
+        state.rpm += (target_rpm - state.rpm).clamp(
+            -diesel_engine.spool_down * delta_seconds,
+            diesel_engine.spool_up * delta_seconds,
+        );
+
+        let fuel = (diesel_engine.fuel_consumption(state.rpm) * delta_seconds).min(state.fuel);
+        state.fuel -= fuel;
+        energy_meter.diesel += fuel;
+        energy_meter.drawn += fuel * DIESEL_ENERGY_DENSITY;
+
+        state.shift_time = (state.shift_time - delta_seconds).max(0.0);
+
+        let stage = diesel_engine.transmission.stage_for(state.stage, speed.0);
+        if stage != state.stage {
+            state.stage = stage;
+
+            if diesel_engine.transmission.kind == TransmissionKind::Mechanical {
+                state.shift_time = SHIFT_TIME;
+            }
+        }
+    }
+}
@@ -0,0 +1,157 @@
+use super::*;
+use crate::train::{Transmission, TransmissionStage};
+use coverage_helper::test;
+use std::time::Duration;
+
+#[coverage(off)]
+fn diesel_engine(kind: TransmissionKind) -> DieselEngine {
+    DieselEngine {
+        notches: 4,
+        idle_rpm: 600.0,
+        max_rpm: 1800.0,
+        spool_up: 300.0,
+        spool_down: 600.0,
+        idle_fuel: 10.0,
+        full_fuel: 100.0,
+        fuel_tank: 1000.0,
+        transmission: Transmission {
+            kind,
+            stages: vec![
+                TransmissionStage {
+                    max_speed: 30.0,
+                    efficiency: 0.8,
+                },
+                TransmissionStage {
+                    max_speed: 60.0,
+                    efficiency: 0.85,
+                },
+            ],
+        },
+    }
+}
+
+#[coverage(off)]
+fn spawn_engine(app: &mut App, kind: TransmissionKind, notch: u8, speed: f32) -> Entity {
+    app.world_mut()
+        .spawn((
+            diesel_engine(kind),
+            DieselState {
+                rpm: 600.0,
+                fuel: 1000.0,
+                ..default()
+            },
+            EnergyMeter::default(),
+            ThrottleLever {
+                percentage: notch as f32 / 4.0,
+                notches: 4,
+                ..default()
+            },
+            Speed(speed),
+        ))
+        .id()
+}
+
+#[coverage(off)]
+fn update(app: &mut App, millis: u64) {
+    let mut time = app.world_mut().resource_mut::<Time>();
+    time.advance_by(Duration::from_millis(millis));
+    app.update();
+}
+
+#[coverage(off)]
+fn setup() -> App {
+    let mut app = App::new();
+    app.add_systems(Update, system);
+    app.init_resource::<Time>();
+    app
+}
+
+#[test]
+fn spools_up_and_down() {
+    let mut app = setup();
+
+    let engine_id = spawn_engine(&mut app, TransmissionKind::Hydraulic, 4, 0.0);
+
+    update(&mut app, 1000);
+    assert_eq!(
+        app.world().get::<DieselState>(engine_id).unwrap().rpm,
+        900.0
+    );
+
+    for _ in 0..10 {
+        update(&mut app, 1000);
+    }
+    assert_eq!(
+        app.world().get::<DieselState>(engine_id).unwrap().rpm,
+        1800.0
+    );
+
+    app.world_mut()
+        .get_mut::<ThrottleLever>(engine_id)
+        .unwrap()
+        .percentage = 0.0;
+
+    update(&mut app, 1000);
+    assert_eq!(
+        app.world().get::<DieselState>(engine_id).unwrap().rpm,
+        1200.0
+    );
+}
+
+#[test]
+fn burns_fuel() {
+    let mut app = setup();
+
+    let idle = spawn_engine(&mut app, TransmissionKind::Hydraulic, 0, 0.0);
+    let full = spawn_engine(&mut app, TransmissionKind::Hydraulic, 4, 0.0);
+
+    update(&mut app, 1000);
+
+    let fuel = |entity| app.world().get::<DieselState>(entity).unwrap().fuel;
+    assert!((fuel(idle) - (1000.0 - 10.0 / 3600.0)).abs() < 0.0001);
+    assert!(fuel(full) < fuel(idle));
+
+    let meter = app.world().get::<EnergyMeter>(idle).unwrap();
+    assert!((meter.diesel - 10.0 / 3600.0).abs() < 0.0001);
+    assert!((meter.drawn - 10.0 / 3600.0 * DIESEL_ENERGY_DENSITY).abs() < 0.0001);
+}
+
+#[test]
+fn stalls_without_fuel() {
+    let mut app = setup();
+
+    let engine_id = spawn_engine(&mut app, TransmissionKind::Hydraulic, 4, 0.0);
+    app.world_mut()
+        .get_mut::<DieselState>(engine_id)
+        .unwrap()
+        .fuel = 0.0;
+
+    update(&mut app, 1000);
+
+    assert_eq!(app.world().get::<DieselState>(engine_id).unwrap().rpm, 0.0);
+}
+
+#[test]
+fn shifts_stages() {
+    let mut app = setup();
+
+    let hydraulic = spawn_engine(&mut app, TransmissionKind::Hydraulic, 4, 40.0 / 3.6);
+    let mechanical = spawn_engine(&mut app, TransmissionKind::Mechanical, 4, 40.0 / 3.6);
+
+    update(&mut app, 100);
+
+    let state = |entity| app.world().get::<DieselState>(entity).unwrap();
+    assert_eq!(state(hydraulic).stage, 1);
+    assert_eq!(state(hydraulic).shift_time, 0.0);
+    assert_eq!(state(mechanical).stage, 1);
+    assert_eq!(state(mechanical).shift_time, SHIFT_TIME);
+
+    // no downshift right below the shift point
+    app.world_mut().get_mut::<Speed>(mechanical).unwrap().0 = 28.0 / 3.6;
+    update(&mut app, 100);
+    assert_eq!(app.world().get::<DieselState>(mechanical).unwrap().stage, 1);
+
+    app.world_mut().get_mut::<Speed>(mechanical).unwrap().0 = 20.0 / 3.6;
+    update(&mut app, 100);
+    assert_eq!(app.world().get::<DieselState>(mechanical).unwrap().stage, 0);
+}
@@ -1,33 +1,109 @@
 #[cfg(test)]
 mod tests;
 
-use crate::train::{Direction, Engine, ForceDriving, MaxPower, Speed, ThrottleLever};
+use crate::train::{
+    AdhesiveMass, Boiler, DieselEngine, DieselState, Direction, Engine, ForceDriving, MaxPower,
+    RailCondition, Sanding, Speed, SteamControls, SteamEngine, ThrottleLever, TractiveEffort,
+    WheelSlip,
+};
 use bevy::prelude::*;
 
 use super::BrakeLever;
 
+const G: f32 = 9.81;
+// share of the adhesion that remains once the wheels spin
+const SLIPPING_ADHESION: f32 = 0.6;
+
+type DriveForceQuery<'a> = (
+    &'a mut ForceDriving,
+    &'a mut WheelSlip,
+    &'a MaxPower,
+    &'a TractiveEffort,
+    &'a SteamEngine,
+    &'a SteamControls,
+    &'a Boiler,
+    (&'a DieselEngine, &'a DieselState),
+    &'a AdhesiveMass,
+    &'a Speed,
+    &'a Sanding,
+    &'a ThrottleLever,
+    &'a BrakeLever,
+);
+
 pub fn system(
-    mut entries: Query<
-        (
-            &mut ForceDriving,
-            &MaxPower,
-            &Speed,
-            &ThrottleLever,
-            &BrakeLever,
-        ),
-        With<Engine>,
-    >,
+    mut entries: Query<DriveForceQuery, With<Engine>>,
+    rail_condition: Res<RailCondition>,
 ) {
-    for (mut force_driving, max_power, speed, throttle_lever, brake_lever) in entries.iter_mut() {
-        force_driving.0 = if brake_lever.release_valve > 0.0 || brake_lever.engine_brake > 0.0 {
-            0.0
-        } else {
-            let direction = match throttle_lever.direction {
-                Direction::Forward => 1.0,
-                Direction::Backward => -1.0,
-            };
+    for (
+        mut force_driving,
+        mut wheel_slip,
+        max_power,
+        tractive_effort,
+        steam_engine,
+        steam_controls,
+        boiler,
+        (diesel_engine, diesel_state),
+        adhesive_mass,
+        speed,
+        sanding,
+        throttle_lever,
+        brake_lever,
+    ) in entries.iter_mut()
+    {
+        // the traction motors can either drive or brake
+        if brake_lever.effective_valve().is_braking()
+            || brake_lever.engine_brake > 0.0
+            || brake_lever.dynamic_brake > 0.0
+        {
+            force_driving.0 = 0.0;
 
-            direction * (max_power.0 * 1000.0 * throttle_lever.percentage) / speed.0.abs().max(1.0)
+            if *wheel_slip == WheelSlip::Slipping {
+                *wheel_slip = WheelSlip::Gripping;
+            }
+
+            continue;
         }
+
+        let direction = match throttle_lever.direction {
+            Direction::Forward => 1.0,
+            Direction::Backward => -1.0,
+        };
+
+        // without a tractive effort table the engine delivers constant power
+        let demanded_force = if steam_engine.is_available() {
+            steam_engine.tractive_effort(
+                boiler.pressure * steam_controls.regulator,
+                steam_controls.cut_off,
+            )
+        } else if diesel_engine.is_available() {
+            diesel_engine.force(max_power.0, diesel_state, speed.0)
+        } else {
+            tractive_effort
+                .force(speed.0, throttle_lever.percentage)
+                .unwrap_or_else(|| {
+                    (max_power.0 * 1000.0 * throttle_lever.percentage) / speed.0.abs().max(1.0)
+                })
+        };
+
+        let adhesion_force =
+            adhesive_mass.0 * G * rail_condition.adhesion_coefficient(speed.0, sanding.0);
+
+        // once slipping, the wheels only grip again if the driver reduces
+        // the throttle (or sands) below what the spinning wheels transmit
+        let limit = if *wheel_slip == WheelSlip::Slipping {
+            adhesion_force * SLIPPING_ADHESION
+        } else {
+            adhesion_force
+        };
+
+        force_driving.0 = if demanded_force > limit {
+            *wheel_slip = WheelSlip::Slipping;
+            direction * adhesion_force * SLIPPING_ADHESION
+        } else {
+            if *wheel_slip == WheelSlip::Slipping {
+                *wheel_slip = WheelSlip::Gripping;
+            }
+            direction * demanded_force
+        };
     }
 }
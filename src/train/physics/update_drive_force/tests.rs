@@ -1,5 +1,5 @@
 use super::*;
-use crate::train::Direction;
+use crate::train::{BrakeValvePosition, Direction};
 use coverage_helper::test;
 
 #[coverage(off)]
@@ -14,7 +14,15 @@ fn spawn_engine(
         .spawn((
             Engine,
             MaxPower(1000.0),
+            TractiveEffort::default(),
+            SteamEngine::default(),
+            SteamControls::default(),
+            Boiler::default(),
+            (DieselEngine::default(), DieselState::default()),
+            AdhesiveMass(1_000_000.0),
             Speed(speed),
+            Sanding::default(),
+            WheelSlip::default(),
             throttle,
             brake,
             ForceDriving(force_driving),
@@ -26,6 +34,7 @@ fn spawn_engine(
 fn no_throttle_no_force() {
     let mut app = App::new();
     app.add_systems(Update, system);
+    app.init_resource::<RailCondition>();
 
     let engine_id = spawn_engine(
         &mut app,
@@ -45,12 +54,14 @@ fn no_throttle_no_force() {
 fn forward_throttle_forward_force() {
     let mut app = App::new();
     app.add_systems(Update, system);
+    app.init_resource::<RailCondition>();
 
     let engine_id = spawn_engine(
         &mut app,
         ThrottleLever {
             percentage: 0.2,
             direction: Direction::Forward,
+            ..default()
         },
         BrakeLever::default(),
         0.0,
@@ -67,12 +78,14 @@ fn forward_throttle_forward_force() {
 fn backward_throttle_backward_force() {
     let mut app = App::new();
     app.add_systems(Update, system);
+    app.init_resource::<RailCondition>();
 
     let engine_id = spawn_engine(
         &mut app,
         ThrottleLever {
             percentage: 0.2,
             direction: Direction::Backward,
+            ..default()
         },
         BrakeLever::default(),
         0.0,
@@ -89,12 +102,14 @@ fn backward_throttle_backward_force() {
 fn more_throttle_more_force() {
     let mut app = App::new();
     app.add_systems(Update, system);
+    app.init_resource::<RailCondition>();
 
     let low_throttle = spawn_engine(
         &mut app,
         ThrottleLever {
             percentage: 0.2,
             direction: Direction::Forward,
+            ..default()
         },
         BrakeLever::default(),
         0.0,
@@ -106,6 +121,7 @@ fn more_throttle_more_force() {
         ThrottleLever {
             percentage: 1.0,
             direction: Direction::Forward,
+            ..default()
         },
         BrakeLever::default(),
         0.0,
@@ -126,12 +142,14 @@ fn more_throttle_more_force() {
 fn more_speed_less_force() {
     let mut app = App::new();
     app.add_systems(Update, system);
+    app.init_resource::<RailCondition>();
 
     let low_speed = spawn_engine(
         &mut app,
         ThrottleLever {
             percentage: 0.2,
             direction: Direction::Forward,
+            ..default()
         },
         BrakeLever::default(),
         0.0,
@@ -143,6 +161,7 @@ fn more_speed_less_force() {
         ThrottleLever {
             percentage: 0.2,
             direction: Direction::Forward,
+            ..default()
         },
         BrakeLever::default(),
         30.0,
@@ -163,16 +182,18 @@ fn more_speed_less_force() {
 fn brake_applied_no_force() {
     let mut app = App::new();
     app.add_systems(Update, system);
+    app.init_resource::<RailCondition>();
 
     let engine_id = spawn_engine(
         &mut app,
         ThrottleLever {
             percentage: 0.5,
             direction: Direction::Forward,
+            ..default()
         },
         BrakeLever {
-            release_valve: 1.0,
-            engine_brake: 0.0,
+            valve: BrakeValvePosition::Service(1),
+            ..default()
         },
         10.0,
         123.0,
@@ -188,16 +209,18 @@ fn brake_applied_no_force() {
 fn engine_brake_applied_no_force() {
     let mut app = App::new();
     app.add_systems(Update, system);
+    app.init_resource::<RailCondition>();
 
     let engine_id = spawn_engine(
         &mut app,
         ThrottleLever {
             percentage: 0.5,
             direction: Direction::Forward,
+            ..default()
         },
         BrakeLever {
-            release_valve: 0.0,
             engine_brake: 1.0,
+            ..default()
         },
         10.0,
         123.0,
@@ -208,3 +231,257 @@ fn engine_brake_applied_no_force() {
     assert!(app.world().get::<ForceDriving>(engine_id).is_some());
     assert_eq!(app.world().get::<ForceDriving>(engine_id).unwrap().0, 0.0);
 }
+
+#[test]
+fn dynamic_brake_applied_no_force() {
+    let mut app = App::new();
+    app.add_systems(Update, system);
+    app.init_resource::<RailCondition>();
+
+    let engine_id = spawn_engine(
+        &mut app,
+        ThrottleLever {
+            percentage: 0.5,
+            direction: Direction::Forward,
+            ..default()
+        },
+        BrakeLever {
+            dynamic_brake: 0.5,
+            ..default()
+        },
+        10.0,
+        123.0,
+    );
+
+    app.update();
+
+    assert_eq!(app.world().get::<ForceDriving>(engine_id).unwrap().0, 0.0);
+}
+
+#[test]
+fn too_much_throttle_slips() {
+    let mut app = App::new();
+    app.add_systems(Update, system);
+    app.init_resource::<RailCondition>();
+
+    let engine_id = spawn_engine(
+        &mut app,
+        ThrottleLever {
+            percentage: 1.0,
+            direction: Direction::Forward,
+            ..default()
+        },
+        BrakeLever::default(),
+        0.0,
+        0.0,
+    );
+    app.world_mut()
+        .get_mut::<AdhesiveMass>(engine_id)
+        .unwrap()
+        .0 = 80000.0;
+
+    app.update();
+
+    let adhesion_force = 80000.0 * G * RailCondition::Dry.adhesion_coefficient(0.0, false);
+    assert_eq!(
+        *app.world().get::<WheelSlip>(engine_id).unwrap(),
+        WheelSlip::Slipping
+    );
+    assert!(app.world().get::<ForceDriving>(engine_id).unwrap().0 < adhesion_force);
+
+    // reducing throttle just below the adhesion limit is not enough
+    let max_power = app.world().get::<MaxPower>(engine_id).unwrap().0;
+    app.world_mut()
+        .get_mut::<ThrottleLever>(engine_id)
+        .unwrap()
+        .percentage = adhesion_force * 0.7 / (max_power * 1000.0);
+
+    app.update();
+
+    assert_eq!(
+        *app.world().get::<WheelSlip>(engine_id).unwrap(),
+        WheelSlip::Slipping
+    );
+
+    // sanding restores grip
+    app.world_mut().get_mut::<Sanding>(engine_id).unwrap().0 = true;
+
+    app.update();
+
+    assert_eq!(
+        *app.world().get::<WheelSlip>(engine_id).unwrap(),
+        WheelSlip::Gripping
+    );
+    assert!(app.world().get::<ForceDriving>(engine_id).unwrap().0 > adhesion_force * 0.6);
+}
+
+#[test]
+fn slippery_rails_slip_earlier() {
+    let mut app = App::new();
+    app.add_systems(Update, system);
+    app.insert_resource(RailCondition::Slippery);
+
+    let engine_id = spawn_engine(
+        &mut app,
+        ThrottleLever {
+            percentage: 0.2,
+            direction: Direction::Forward,
+            ..default()
+        },
+        BrakeLever::default(),
+        5.0,
+        0.0,
+    );
+    app.world_mut()
+        .get_mut::<AdhesiveMass>(engine_id)
+        .unwrap()
+        .0 = 80000.0;
+
+    app.update();
+
+    assert_eq!(
+        *app.world().get::<WheelSlip>(engine_id).unwrap(),
+        WheelSlip::Gripping
+    );
+
+    app.world_mut()
+        .get_mut::<ThrottleLever>(engine_id)
+        .unwrap()
+        .percentage = 1.0;
+
+    app.update();
+
+    assert_eq!(
+        *app.world().get::<WheelSlip>(engine_id).unwrap(),
+        WheelSlip::Slipping
+    );
+}
+
+#[test]
+fn tractive_effort_table() {
+    let mut app = App::new();
+    app.add_systems(Update, system);
+    app.init_resource::<RailCondition>();
+
+    let throttle = ThrottleLever {
+        percentage: 1.0,
+        direction: Direction::Forward,
+        ..default()
+    };
+
+    let engine_id = spawn_engine(&mut app, throttle, BrakeLever::default(), 0.0, 0.0);
+    *app.world_mut()
+        .get_mut::<TractiveEffort>(engine_id)
+        .unwrap() = TractiveEffort {
+        curve: vec![(0.0, 200.0), (50.0, 200.0), (100.0, 100.0)],
+        ..default()
+    };
+
+    app.update();
+
+    // constant force at low speed instead of max power / 1 m/s
+    assert_eq!(
+        app.world().get::<ForceDriving>(engine_id).unwrap().0,
+        200_000.0
+    );
+
+    app.world_mut().get_mut::<Speed>(engine_id).unwrap().0 = 75.0 / 3.6;
+
+    app.update();
+
+    assert!((app.world().get::<ForceDriving>(engine_id).unwrap().0 - 150_000.0).abs() < 1.0);
+}
+
+#[test]
+fn steam_engine_force() {
+    let mut app = App::new();
+    app.add_systems(Update, system);
+    app.init_resource::<RailCondition>();
+
+    let throttle = ThrottleLever {
+        percentage: 0.0,
+        direction: Direction::Backward,
+        ..default()
+    };
+
+    let engine_id = spawn_engine(&mut app, throttle, BrakeLever::default(), 0.0, 0.0);
+    let steam_engine = SteamEngine {
+        cylinders: 2,
+        cylinder_diameter: 0.6,
+        piston_stroke: 0.66,
+        wheel_diameter: 1.4,
+        max_boiler_pressure: 16.0,
+        ..default()
+    };
+    app.world_mut().entity_mut(engine_id).insert((
+        steam_engine.clone(),
+        SteamControls {
+            regulator: 0.5,
+            cut_off: 0.4,
+            ..default()
+        },
+        Boiler { pressure: 16.0 },
+    ));
+
+    app.update();
+
+    // the throttle lever only sets the direction
+    assert_eq!(
+        app.world().get::<ForceDriving>(engine_id).unwrap().0,
+        -steam_engine.tractive_effort(8.0, 0.4)
+    );
+
+    app.world_mut()
+        .get_mut::<SteamControls>(engine_id)
+        .unwrap()
+        .regulator = 0.0;
+
+    app.update();
+
+    assert_eq!(app.world().get::<ForceDriving>(engine_id).unwrap().0, 0.0);
+}
+
+#[test]
+fn diesel_engine_force() {
+    let mut app = App::new();
+    app.add_systems(Update, system);
+    app.init_resource::<RailCondition>();
+
+    let throttle = ThrottleLever {
+        percentage: 1.0,
+        direction: Direction::Forward,
+        notches: 4,
+    };
+
+    let engine_id = spawn_engine(&mut app, throttle, BrakeLever::default(), 10.0, 0.0);
+    let diesel_engine = DieselEngine {
+        notches: 4,
+        idle_rpm: 600.0,
+        max_rpm: 1800.0,
+        ..default()
+    };
+    app.world_mut().entity_mut(engine_id).insert((
+        diesel_engine,
+        DieselState {
+            rpm: 1200.0,
+            ..default()
+        },
+    ));
+
+    app.update();
+
+    // half the power at half the rpm range, max power / speed without stages
+    assert_eq!(
+        app.world().get::<ForceDriving>(engine_id).unwrap().0,
+        1000.0 * 1000.0 * 0.5 / 10.0
+    );
+
+    app.world_mut()
+        .get_mut::<DieselState>(engine_id)
+        .unwrap()
+        .shift_time = 0.5;
+
+    app.update();
+
+    assert_eq!(app.world().get::<ForceDriving>(engine_id).unwrap().0, 0.0);
+}
@@ -0,0 +1,68 @@
+#[cfg(test)]
+mod tests;
+
+use crate::{
+    landscape::OSMData,
+    train::{
+        AdhesiveMass, BrakeLever, DynamicBrake, EnergyMeter, Engine, ForceBraking, RailCondition,
+        Sanding, Speed, TrackLocation, JOULES_PER_KWH,
+    },
+};
+use bevy::prelude::*;
+
+const G: f32 = 9.81;
+
+type DynamicBrakeQuery<'a> = (
+    &'a mut ForceBraking,
+    &'a mut EnergyMeter,
+    &'a DynamicBrake,
+    &'a BrakeLever,
+    &'a AdhesiveMass,
+    &'a Speed,
+    &'a Sanding,
+    Option<&'a TrackLocation>,
+);
+
+pub fn system(
+    mut entries: Query<DynamicBrakeQuery, With<Engine>>,
+    rail_condition: Res<RailCondition>,
+    data: Option<Res<OSMData>>,
+    time: Res<Time>,
+) {
+    for (
+        mut force_braking,
+        mut energy_meter,
+        dynamic_brake,
+        brake_lever,
+        adhesive_mass,
+        speed,
+        sanding,
+        location,
+    ) in entries.iter_mut()
+    {
+        if !dynamic_brake.is_available() || brake_lever.dynamic_brake <= 0.0 {
+            continue;
+        }
+
+        let demanded_force = dynamic_brake.force(speed.0, brake_lever.dynamic_brake);
+
+        // the dynamic brake only acts on the driven axles and is reduced
+        // (blended) so that together with the air brake they do not slide
+        let adhesion_force =
+            adhesive_mass.0 * G * rail_condition.adhesion_coefficient(speed.0, sanding.0);
+        let force = demanded_force.min((adhesion_force - force_braking.0).max(0.0));
+
+        force_braking.0 += force;
+
+        // without a contact line to feed into, the braking resistors take
+        // the energy of a regenerative brake as well
+        let is_electrified = data
+            .as_ref()
+            .zip(location)
+            .is_some_and(|(data, location)| location.is_electrified(data));
+        if dynamic_brake.regenerative && is_electrified {
+            energy_meter.regenerated +=
+                force * speed.0.abs() * time.delta_seconds() / JOULES_PER_KWH;
+        }
+    }
+}
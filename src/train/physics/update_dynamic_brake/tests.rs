@@ -0,0 +1,194 @@
+use super::*;
+use crate::{
+    landscape::{CoordinatePoint, Path, RailTags},
+    train::Direction,
+};
+use coverage_helper::test;
+use std::{collections::HashMap, time::Duration};
+
+// an electrified rail and one without a contact line
+#[coverage(off)]
+fn gen_data() -> OSMData {
+    let mut rails = HashMap::default();
+    for (id, electrified) in [((0, 1), "contact_line"), ((1, 2), "no")] {
+        rails.insert(
+            id,
+            Path {
+                start_id: id.0,
+                end_id: id.1,
+                start_coords: CoordinatePoint(id.0 as f64 * 1000.0, 0.0),
+                end_coords: CoordinatePoint(id.1 as f64 * 1000.0, 0.0),
+                tags: RailTags {
+                    electrified: Some(electrified.to_string()),
+                    ..default()
+                },
+                ..default()
+            },
+        );
+    }
+    OSMData { rails, ..default() }
+}
+
+#[coverage(off)]
+fn location(id: (i64, i64)) -> TrackLocation {
+    TrackLocation {
+        id,
+        distance: 500.0,
+        travel_direction: Direction::Forward,
+    }
+}
+
+#[coverage(off)]
+fn dynamic_brake(regenerative: bool) -> DynamicBrake {
+    DynamicBrake {
+        regenerative,
+        curve: vec![(0.0, 0.0), (10.0, 150.0), (100.0, 150.0)],
+    }
+}
+
+#[coverage(off)]
+fn spawn_engine(
+    app: &mut App,
+    dynamic_brake: DynamicBrake,
+    lever: f32,
+    speed: f32,
+    force_braking: f32,
+) -> Entity {
+    app.world_mut()
+        .spawn((
+            Engine,
+            ForceBraking(force_braking),
+            EnergyMeter::default(),
+            dynamic_brake,
+            BrakeLever {
+                dynamic_brake: lever,
+                ..default()
+            },
+            AdhesiveMass(84_000.0),
+            Speed(speed),
+            Sanding::default(),
+        ))
+        .id()
+}
+
+#[coverage(off)]
+fn setup() -> App {
+    let mut app = App::new();
+    app.add_systems(Update, system);
+    app.init_resource::<RailCondition>();
+    app.insert_resource(gen_data());
+    app.init_resource::<Time>();
+    app
+}
+
+#[coverage(off)]
+fn update(app: &mut App) {
+    let mut time = app.world_mut().resource_mut::<Time>();
+    time.advance_by(Duration::from_millis(100));
+    app.update();
+}
+
+#[test]
+fn released_no_force() {
+    let mut app = setup();
+
+    let engine_id = spawn_engine(&mut app, dynamic_brake(true), 0.0, 20.0, 0.0);
+
+    update(&mut app);
+
+    assert_eq!(app.world().get::<ForceBraking>(engine_id).unwrap().0, 0.0);
+    assert_eq!(
+        app.world()
+            .get::<EnergyMeter>(engine_id)
+            .unwrap()
+            .regenerated,
+        0.0
+    );
+}
+
+#[test]
+fn no_dynamic_brake_no_force() {
+    let mut app = setup();
+
+    let engine_id = spawn_engine(&mut app, DynamicBrake::default(), 1.0, 20.0, 0.0);
+
+    update(&mut app);
+
+    assert_eq!(app.world().get::<ForceBraking>(engine_id).unwrap().0, 0.0);
+}
+
+#[test]
+fn force_follows_curve() {
+    let mut app = setup();
+
+    let half = spawn_engine(&mut app, dynamic_brake(false), 0.5, 20.0, 0.0);
+    let full = spawn_engine(&mut app, dynamic_brake(false), 1.0, 20.0, 0.0);
+    let standing = spawn_engine(&mut app, dynamic_brake(false), 1.0, 0.0, 0.0);
+
+    update(&mut app);
+
+    assert!((app.world().get::<ForceBraking>(half).unwrap().0 - 75_000.0).abs() < 1.0);
+    assert!((app.world().get::<ForceBraking>(full).unwrap().0 - 150_000.0).abs() < 1.0);
+    assert_eq!(app.world().get::<ForceBraking>(standing).unwrap().0, 0.0);
+}
+
+#[test]
+fn adds_to_air_brake_up_to_adhesion() {
+    let mut app = setup();
+
+    let adhesion_force = 84_000.0 * G * RailCondition::Dry.adhesion_coefficient(20.0, false);
+
+    let light_air = spawn_engine(&mut app, dynamic_brake(false), 0.5, 20.0, 10_000.0);
+    let heavy_air = spawn_engine(&mut app, dynamic_brake(false), 1.0, 20.0, adhesion_force);
+
+    update(&mut app);
+
+    assert!((app.world().get::<ForceBraking>(light_air).unwrap().0 - 85_000.0).abs() < 1.0);
+    assert_eq!(
+        app.world().get::<ForceBraking>(heavy_air).unwrap().0,
+        adhesion_force
+    );
+}
+
+#[test]
+fn regenerates_energy() {
+    let mut app = setup();
+
+    let regenerative = spawn_engine(&mut app, dynamic_brake(true), 1.0, 20.0, 0.0);
+    let rheostatic = spawn_engine(&mut app, dynamic_brake(false), 1.0, 20.0, 0.0);
+    let unelectrified = spawn_engine(&mut app, dynamic_brake(true), 1.0, 20.0, 0.0);
+    for (engine, id) in [
+        (regenerative, (0, 1)),
+        (rheostatic, (0, 1)),
+        (unelectrified, (1, 2)),
+    ] {
+        app.world_mut().entity_mut(engine).insert(location(id));
+    }
+
+    update(&mut app);
+
+    // 150 kN at 20 m/s for 0.1 s
+    let expected = 150_000.0 * 20.0 * 0.1 / JOULES_PER_KWH;
+    let regenerated = app
+        .world()
+        .get::<EnergyMeter>(regenerative)
+        .unwrap()
+        .regenerated;
+    assert!((regenerated - expected).abs() < 0.0001);
+    assert_eq!(
+        app.world()
+            .get::<EnergyMeter>(rheostatic)
+            .unwrap()
+            .regenerated,
+        0.0
+    );
+
+    // without a contact line the energy goes to the resistors
+    assert_eq!(
+        app.world()
+            .get::<EnergyMeter>(unelectrified)
+            .unwrap()
+            .regenerated,
+        0.0
+    );
+}
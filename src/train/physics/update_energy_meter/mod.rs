@@ -0,0 +1,65 @@
+#[cfg(test)]
+mod tests;
+
+use crate::{
+    landscape::OSMData,
+    train::{
+        DieselEngine, EnergyMeter, ForceAirResistance, ForceBraking, ForceCurveResistance,
+        ForceDriving, ForceFriction, Speed, SteamEngine, TrackLocation, ELECTRIC_EFFICIENCY,
+        JOULES_PER_KWH,
+    },
+};
+use bevy::prelude::*;
+
+type EnergyMeterQuery<'a> = (
+    &'a mut EnergyMeter,
+    &'a ForceDriving,
+    &'a ForceBraking,
+    &'a ForceFriction,
+    &'a ForceAirResistance,
+    &'a ForceCurveResistance,
+    &'a Speed,
+    // only engines have a traction model, trains do not
+    Option<(&'a SteamEngine, &'a DieselEngine)>,
+    Option<&'a TrackLocation>,
+);
+
+pub fn system(mut entries: Query<EnergyMeterQuery>, data: Option<Res<OSMData>>, time: Res<Time>) {
+    let delta_seconds = time.delta_seconds();
+
+    for (
+        mut energy_meter,
+        force_driving,
+        force_braking,
+        force_friction,
+        force_air_resistance,
+        force_curve_resistance,
+        speed,
+        traction_model,
+        location,
+    ) in entries.iter_mut()
+    {
+        let kwh = |power: f32| power * delta_seconds / JOULES_PER_KWH;
+
+        // pushing against the direction of travel does not count as traction
+        let traction = kwh((force_driving.0 * speed.0).max(0.0));
+        energy_meter.traction += traction;
+        energy_meter.braking += kwh(force_braking.0 * speed.0.abs());
+        energy_meter.resistance += kwh((force_friction.0
+            + force_air_resistance.0
+            + force_curve_resistance.0)
+            * speed.0.abs());
+
+        // steam and diesel engines meter the coal and fuel they burn,
+        // electric ones what they take from the contact line
+        let is_electrified = data
+            .as_ref()
+            .zip(location)
+            .is_some_and(|(data, location)| location.is_electrified(data));
+        if let Some((steam_engine, diesel_engine)) = traction_model {
+            if !steam_engine.is_available() && !diesel_engine.is_available() && is_electrified {
+                energy_meter.drawn += traction / ELECTRIC_EFFICIENCY;
+            }
+        }
+    }
+}
@@ -0,0 +1,147 @@
+use super::*;
+use crate::{
+    landscape::{CoordinatePoint, Path, RailTags},
+    train::Direction,
+};
+use coverage_helper::test;
+use std::{collections::HashMap, time::Duration};
+
+// an electrified rail and one without a contact line
+#[coverage(off)]
+fn gen_data() -> OSMData {
+    let mut rails = HashMap::default();
+    for (id, electrified) in [((0, 1), "contact_line"), ((1, 2), "no")] {
+        rails.insert(
+            id,
+            Path {
+                start_id: id.0,
+                end_id: id.1,
+                start_coords: CoordinatePoint(id.0 as f64 * 1000.0, 0.0),
+                end_coords: CoordinatePoint(id.1 as f64 * 1000.0, 0.0),
+                tags: RailTags {
+                    electrified: Some(electrified.to_string()),
+                    ..default()
+                },
+                ..default()
+            },
+        );
+    }
+    OSMData { rails, ..default() }
+}
+
+#[coverage(off)]
+fn location(id: (i64, i64)) -> TrackLocation {
+    TrackLocation {
+        id,
+        distance: 500.0,
+        travel_direction: Direction::Forward,
+    }
+}
+
+#[coverage(off)]
+fn spawn_engine(app: &mut App, force_driving: f32, speed: f32) -> Entity {
+    app.world_mut()
+        .spawn((
+            EnergyMeter::default(),
+            ForceDriving(force_driving),
+            ForceBraking::default(),
+            ForceFriction::default(),
+            ForceAirResistance::default(),
+            ForceCurveResistance::default(),
+            Speed(speed),
+            (SteamEngine::default(), DieselEngine::default()),
+            location((0, 1)),
+        ))
+        .id()
+}
+
+#[coverage(off)]
+fn update(app: &mut App) {
+    let mut time = app.world_mut().resource_mut::<Time>();
+    time.advance_by(Duration::from_millis(100));
+    app.update();
+}
+
+#[coverage(off)]
+fn setup() -> App {
+    let mut app = App::new();
+    app.add_systems(Update, system);
+    app.insert_resource(gen_data());
+    app.init_resource::<Time>();
+    app
+}
+
+#[test]
+fn counts_traction() {
+    let mut app = setup();
+
+    let forward = spawn_engine(&mut app, 100_000.0, 20.0);
+    let backward = spawn_engine(&mut app, -100_000.0, -20.0);
+    let standing = spawn_engine(&mut app, 100_000.0, 0.0);
+    let against = spawn_engine(&mut app, -100_000.0, 20.0);
+
+    update(&mut app);
+
+    // 100 kN at 20 m/s for 0.1 s
+    let expected = 100_000.0 * 20.0 * 0.1 / JOULES_PER_KWH;
+    let traction = |entity| app.world().get::<EnergyMeter>(entity).unwrap().traction;
+
+    assert!((traction(forward) - expected).abs() < 0.0001);
+    assert!((traction(backward) - expected).abs() < 0.0001);
+    assert_eq!(traction(standing), 0.0);
+    assert_eq!(traction(against), 0.0);
+}
+
+#[test]
+fn electric_engines_draw_more_than_traction() {
+    let mut app = setup();
+
+    let electric = spawn_engine(&mut app, 100_000.0, 20.0);
+    let diesel = spawn_engine(&mut app, 100_000.0, 20.0);
+    app.world_mut().entity_mut(diesel).insert(DieselEngine {
+        notches: 4,
+        ..default()
+    });
+    let train = spawn_engine(&mut app, 100_000.0, 20.0);
+    app.world_mut()
+        .entity_mut(train)
+        .remove::<(SteamEngine, DieselEngine)>();
+    let off_the_wire = spawn_engine(&mut app, 100_000.0, 20.0);
+    app.world_mut()
+        .entity_mut(off_the_wire)
+        .insert(location((1, 2)));
+
+    update(&mut app);
+
+    let meter = |entity| app.world().get::<EnergyMeter>(entity).unwrap().clone();
+
+    assert!(
+        (meter(electric).drawn - meter(electric).traction / ELECTRIC_EFFICIENCY).abs() < 0.0001
+    );
+    assert_eq!(meter(diesel).drawn, 0.0);
+    assert_eq!(meter(train).drawn, 0.0);
+    assert!(meter(train).traction > 0.0);
+    // there is no contact line to draw from
+    assert_eq!(meter(off_the_wire).drawn, 0.0);
+}
+
+#[test]
+fn counts_losses() {
+    let mut app = setup();
+
+    let engine = spawn_engine(&mut app, 0.0, -20.0);
+    app.world_mut().entity_mut(engine).insert((
+        ForceBraking(50_000.0),
+        ForceFriction(1_000.0),
+        ForceAirResistance(2_000.0),
+        ForceCurveResistance(500.0),
+    ));
+
+    update(&mut app);
+
+    let meter = app.world().get::<EnergyMeter>(engine).unwrap();
+    assert!((meter.braking - 50_000.0 * 20.0 * 0.1 / JOULES_PER_KWH).abs() < 0.0001);
+    assert!((meter.resistance - 3_500.0 * 20.0 * 0.1 / JOULES_PER_KWH).abs() < 0.0001);
+    assert_eq!(meter.braking_losses(), meter.braking);
+    assert_eq!(meter.traction, 0.0);
+}
@@ -9,12 +9,22 @@ fn higher_mass_higher_friction() {
 
     let light_train = app
         .world_mut()
-        .spawn((Engine, ForceFriction::default(), Mass(7000.0)))
+        .spawn((
+            Engine,
+            ForceFriction::default(),
+            Mass(7000.0),
+            ResistanceCoefficients::default(),
+        ))
         .id();
 
     let heavy_train = app
         .world_mut()
-        .spawn((Engine, ForceFriction::default(), Mass(70000.0)))
+        .spawn((
+            Engine,
+            ForceFriction::default(),
+            Mass(70000.0),
+            ResistanceCoefficients::default(),
+        ))
         .id();
 
     app.update();
@@ -26,3 +36,40 @@ fn higher_mass_higher_friction() {
             < app.world().get::<ForceFriction>(heavy_train).unwrap().0
     );
 }
+
+#[test]
+fn coefficients_per_vehicle() {
+    let mut app = App::new();
+    app.add_systems(Update, system);
+
+    let default_train = app
+        .world_mut()
+        .spawn((
+            Engine,
+            ForceFriction::default(),
+            Mass(70000.0),
+            ResistanceCoefficients::default(),
+        ))
+        .id();
+
+    let rough_train = app
+        .world_mut()
+        .spawn((
+            Engine,
+            ForceFriction::default(),
+            Mass(70000.0),
+            ResistanceCoefficients {
+                rolling: 0.003,
+                bearing: 500.0,
+                ..default()
+            },
+        ))
+        .id();
+
+    app.update();
+
+    assert!(
+        app.world().get::<ForceFriction>(default_train).unwrap().0
+            < app.world().get::<ForceFriction>(rough_train).unwrap().0
+    );
+}
@@ -0,0 +1,44 @@
+#[cfg(test)]
+mod tests;
+
+use crate::{
+    landscape::{HeightMap, OSMData},
+    train::{Direction, EngineOrWagons, ForceGradient, Mass, TrackLocation},
+};
+use bevy::prelude::*;
+
+const G: f32 = 9.81;
+
+// downhill force of a mass on a slope that rises by height_difference
+// over length meters (in direction of travel)
+fn gradient_force(mass: f32, height_difference: f32, length: f32) -> f32 {
+    if length <= 0.0 {
+        return 0.0;
+    }
+
+    let angle = f32::atan2(height_difference, length);
+    -mass * G * angle.sin()
+}
+
+pub fn system(
+    data: Res<OSMData>,
+    height_map: Res<HeightMap>,
+    mut entries: Query<(&mut ForceGradient, &Mass, &TrackLocation), EngineOrWagons>,
+) {
+    for (mut force_gradient, mass, location) in entries.iter_mut() {
+        let rail = data
+            .rails
+            .get(&location.id)
+            .expect("train location to be valid");
+
+        let (s, e) = match location.travel_direction {
+            Direction::Forward => (rail.start_coords, rail.end_coords),
+            Direction::Backward => (rail.end_coords, rail.start_coords),
+        };
+
+        let height_difference =
+            height_map.height_at_position(e.0, e.1) - height_map.height_at_position(s.0, s.1);
+
+        force_gradient.0 = gradient_force(mass.0, height_difference, rail.length() as f32);
+    }
+}
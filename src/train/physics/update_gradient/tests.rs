@@ -0,0 +1,70 @@
+use super::*;
+use crate::{
+    landscape::{CoordinatePoint, Path},
+    train::Engine,
+};
+use coverage_helper::test;
+use std::collections::HashMap;
+
+#[coverage(off)]
+fn gen_data() -> OSMData {
+    let mut rails = HashMap::default();
+    rails.insert(
+        (0, 1),
+        Path {
+            start_id: 0,
+            end_id: 1,
+            start_coords: CoordinatePoint(0.0, 0.0),
+            end_coords: CoordinatePoint(100.0, 100.0),
+            ..default()
+        },
+    );
+    OSMData { rails, ..default() }
+}
+
+#[test]
+fn flat_no_force() {
+    let mut app = App::new();
+    app.add_systems(Update, system);
+    app.insert_resource(gen_data());
+    app.insert_resource(HeightMap::test_dummy());
+
+    let engine_id = app
+        .world_mut()
+        .spawn((
+            Engine,
+            ForceGradient(123.0),
+            Mass(80000.0),
+            TrackLocation {
+                id: (0, 1),
+                distance: 10.0,
+                travel_direction: Direction::Forward,
+            },
+        ))
+        .id();
+
+    app.update();
+
+    assert!(app.world().get::<ForceGradient>(engine_id).is_some());
+    assert_eq!(app.world().get::<ForceGradient>(engine_id).unwrap().0, 0.0);
+}
+
+#[test]
+fn uphill_slows_downhill_accelerates() {
+    let uphill = gradient_force(80000.0, 2.0, 100.0);
+    let downhill = gradient_force(80000.0, -2.0, 100.0);
+
+    assert!(uphill < 0.0);
+    assert!(downhill > 0.0);
+    assert_eq!(uphill, -downhill);
+}
+
+#[test]
+fn steeper_and_heavier_more_force() {
+    let base = gradient_force(80000.0, 1.0, 100.0);
+
+    assert!(gradient_force(80000.0, 2.0, 100.0) < base);
+    assert!(gradient_force(160000.0, 1.0, 100.0) < base);
+    assert_eq!(gradient_force(80000.0, 0.0, 100.0), 0.0);
+    assert_eq!(gradient_force(80000.0, 1.0, 0.0), 0.0);
+}
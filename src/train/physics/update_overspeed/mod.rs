@@ -0,0 +1,72 @@
+#[cfg(test)]
+mod tests;
+
+use crate::train::{
+    BrakeIntervention, BrakeLever, Overspeed, OverspeedViolation, Speed, SpeedLimit,
+    TrainComposition, OVERSPEED_TOLERANCE, STANDSTILL,
+};
+use bevy::prelude::*;
+
+pub fn system(
+    mut trains: Query<(&TrainComposition, &Speed, &SpeedLimit, &mut Overspeed)>,
+    mut brake_levers: Query<&mut BrakeLever>,
+    time: Res<Time>,
+) {
+    for (composition, speed, speed_limit, mut overspeed) in trains.iter_mut() {
+        let speed = speed.0.abs();
+        let Some(limit) = speed_limit.current else {
+            continue;
+        };
+
+        if speed > limit {
+            if !overspeed.is_active {
+                overspeed.violations.push(OverspeedViolation {
+                    limit,
+                    max_speed: speed,
+                    distance: 0.0,
+                    penalty: false,
+                });
+                overspeed.is_active = true;
+
+                log::info!(
+                    "overspeed: {:.0} km/h where {:.0} km/h are permitted",
+                    speed * 3.6,
+                    limit * 3.6
+                );
+            }
+
+            let violation = overspeed
+                .violations
+                .last_mut()
+                .expect("an active violation to be recorded");
+            violation.max_speed = violation.max_speed.max(speed);
+            violation.distance += speed * time.delta_seconds();
+
+            if speed > limit + OVERSPEED_TOLERANCE / 3.6 && !violation.penalty {
+                violation.penalty = true;
+                log::warn!("penalty brake applied at {:.0} km/h", speed * 3.6);
+
+                for entity in composition.entities() {
+                    if let Ok(mut brake_lever) = brake_levers.get_mut(entity) {
+                        brake_lever.set_intervention(BrakeIntervention::Overspeed, true);
+                    }
+                }
+            }
+        } else {
+            overspeed.is_active = false;
+        }
+
+        if speed < STANDSTILL {
+            for entity in composition.entities() {
+                if let Ok(mut brake_lever) = brake_levers.get_mut(entity) {
+                    if brake_lever
+                        .interventions
+                        .contains(&BrakeIntervention::Overspeed)
+                    {
+                        brake_lever.set_intervention(BrakeIntervention::Overspeed, false);
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,116 @@
+use super::*;
+use crate::train::TrainComponent;
+use coverage_helper::test;
+use std::time::Duration;
+
+#[coverage(off)]
+fn setup() -> (App, Entity, Entity) {
+    let mut app = App::new();
+    app.add_systems(Update, system);
+    app.init_resource::<Time>();
+
+    let engine = app.world_mut().spawn(BrakeLever::default()).id();
+    let train = app
+        .world_mut()
+        .spawn((
+            TrainComposition {
+                components: vec![TrainComponent::Engine(engine)],
+            },
+            Speed(0.0),
+            SpeedLimit {
+                current: Some(20.0),
+                next: None,
+            },
+            Overspeed::default(),
+        ))
+        .id();
+
+    (app, train, engine)
+}
+
+#[coverage(off)]
+fn drive(app: &mut App, train: Entity, speed: f32) {
+    app.world_mut().get_mut::<Speed>(train).unwrap().0 = speed;
+    let mut time = app.world_mut().resource_mut::<Time>();
+    time.advance_by(Duration::from_secs(1));
+    app.update();
+}
+
+#[coverage(off)]
+fn is_braking(app: &App, engine: Entity) -> bool {
+    app.world()
+        .get::<BrakeLever>(engine)
+        .unwrap()
+        .interventions
+        .contains(&BrakeIntervention::Overspeed)
+}
+
+#[test]
+fn records_violations() {
+    let (mut app, train, engine) = setup();
+
+    drive(&mut app, train, 19.0);
+    assert!(app
+        .world()
+        .get::<Overspeed>(train)
+        .unwrap()
+        .violations
+        .is_empty());
+
+    drive(&mut app, train, 21.0);
+    drive(&mut app, train, 22.0);
+    drive(&mut app, train, 19.0);
+    drive(&mut app, train, -21.0);
+
+    let overspeed = app.world().get::<Overspeed>(train).unwrap();
+    assert_eq!(overspeed.violations.len(), 2);
+    assert!(overspeed.is_active);
+
+    let first = &overspeed.violations[0];
+    assert_eq!(first.limit, 20.0);
+    assert_eq!(first.max_speed, 22.0);
+    assert!(!first.penalty);
+    assert!(!is_braking(&app, engine));
+}
+
+#[test]
+fn penalty_brake_above_tolerance() {
+    let (mut app, train, engine) = setup();
+
+    drive(&mut app, train, 20.0 + OVERSPEED_TOLERANCE / 3.6 + 0.5);
+    assert!(is_braking(&app, engine));
+    assert!(app.world().get::<Overspeed>(train).unwrap().violations[0].penalty);
+    assert_eq!(
+        app.world()
+            .get::<BrakeLever>(engine)
+            .unwrap()
+            .effective_valve(),
+        crate::train::BrakeValvePosition::Emergency
+    );
+
+    // slowing down below the limit keeps the brakes applied
+    drive(&mut app, train, 10.0);
+    assert!(is_braking(&app, engine));
+
+    // until the train stands
+    drive(&mut app, train, 0.0);
+    assert!(!is_braking(&app, engine));
+}
+
+#[test]
+fn unknown_limits_are_not_supervised() {
+    let (mut app, train, engine) = setup();
+    app.world_mut()
+        .get_mut::<SpeedLimit>(train)
+        .unwrap()
+        .current = None;
+
+    drive(&mut app, train, 100.0);
+    assert!(app
+        .world()
+        .get::<Overspeed>(train)
+        .unwrap()
+        .violations
+        .is_empty());
+    assert!(!is_braking(&app, engine));
+}
@@ -0,0 +1,13 @@
+#[cfg(test)]
+mod tests;
+
+use crate::train::{LoadChange, Mass, Payload};
+use bevy::prelude::*;
+
+pub fn system(mut events: EventReader<LoadChange>, mut wagons: Query<(&mut Payload, &mut Mass)>) {
+    for event in events.read() {
+        if let Ok((mut payload, mut mass)) = wagons.get_mut(event.wagon) {
+            payload.set_share(event.share, &mut mass);
+        }
+    }
+}
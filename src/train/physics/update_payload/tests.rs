@@ -0,0 +1,52 @@
+use super::*;
+use crate::train::{
+    physics::apply_sum_component_values_to_train, TrainComponent, TrainComposition,
+};
+use coverage_helper::test;
+
+#[test]
+fn train_mass_follows_the_load() {
+    let mut app = App::new();
+    app.add_event::<LoadChange>().add_systems(
+        Update,
+        (system, apply_sum_component_values_to_train::system::<Mass>).chain(),
+    );
+
+    let engine = app.world_mut().spawn(Mass(80_000.0)).id();
+    let wagon = app
+        .world_mut()
+        .spawn((
+            Mass(20_000.0),
+            Payload {
+                capacity: 60_000.0,
+                load: 0.0,
+            },
+        ))
+        .id();
+    let train = app
+        .world_mut()
+        .spawn((
+            TrainComposition {
+                components: vec![TrainComponent::Engine(engine), TrainComponent::Wagon(wagon)],
+            },
+            Mass(0.0),
+        ))
+        .id();
+
+    app.update();
+    assert_eq!(app.world().get::<Mass>(train).unwrap().0, 100_000.0);
+
+    app.world_mut().send_event(LoadChange { wagon, share: 0.5 });
+    app.update();
+    assert_eq!(app.world().get::<Mass>(wagon).unwrap().0, 50_000.0);
+    assert_eq!(app.world().get::<Mass>(train).unwrap().0, 130_000.0);
+
+    // engines carry no payload
+    app.world_mut().send_event(LoadChange {
+        wagon: engine,
+        share: 1.0,
+    });
+    app.world_mut().send_event(LoadChange { wagon, share: 0.0 });
+    app.update();
+    assert_eq!(app.world().get::<Mass>(train).unwrap().0, 100_000.0);
+}
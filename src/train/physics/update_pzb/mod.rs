@@ -0,0 +1,151 @@
+#[cfg(test)]
+mod tests;
+
+use crate::{
+    landscape::{passed_magnets, MagnetFrequency, OSMData, Signal, SwitchPositions, TrackMagnet},
+    train::{
+        BrakeIntervention, BrakeLever, CabSignalling, PreviousTrackLocation, Pzb, PzbMonitoring,
+        Speed, TrackLocation, TrainComposition, PZB_1000_HZ_DISTANCE, PZB_500_HZ_DISTANCE,
+        PZB_ACKNOWLEDGE_TIME, PZB_CRAWLING_SPEED, PZB_CRAWLING_TIME, STANDSTILL,
+    },
+};
+use bevy::prelude::*;
+
+// moves a monitoring on and ends it after the given distance
+fn advance(monitoring: &mut Option<PzbMonitoring>, speed: f32, delta_seconds: f32, length: f32) {
+    if let Some(current) = monitoring {
+        current.time += delta_seconds;
+        current.distance += speed * delta_seconds;
+
+        if current.distance >= length {
+            *monitoring = None;
+        }
+    }
+}
+
+fn trip(pzb: &mut Pzb) {
+    if !pzb.emergency {
+        log::warn!("PZB emergency brake application");
+    }
+
+    pzb.emergency = true;
+    if pzb.hz1000.is_some() || pzb.hz500.is_some() {
+        pzb.restrictive = true;
+    }
+}
+
+pub fn system(
+    (data, switches): (Res<OSMData>, Res<SwitchPositions>),
+    trains: Query<(&TrainComposition, &Speed, Option<&CabSignalling>)>,
+    (mut engines, locations): (
+        Query<&mut Pzb>,
+        Query<(&PreviousTrackLocation, &TrackLocation)>,
+    ),
+    mut brake_levers: Query<&mut BrakeLever>,
+    magnets: Query<(Entity, &TrackMagnet)>,
+    signals: Query<&Signal>,
+    time: Res<Time>,
+) {
+    let delta_seconds = time.delta_seconds();
+    let magnet_list: Vec<_> = magnets.iter().collect();
+
+    for (composition, speed, cab_signalling) in trains.iter() {
+        let entities = composition.entities();
+        // the train magnet sits at the front of the leading vehicle
+        let leading = if speed.0 < 0.0 {
+            entities.last()
+        } else {
+            entities.first()
+        };
+        let Some((previous, current)) = leading.and_then(|entity| locations.get(*entity).ok())
+        else {
+            continue;
+        };
+        // m the vehicles moved along their travel direction during the tick
+        let moved = speed.0 as f64 * time.delta_seconds_f64();
+        let speed = speed.0.abs();
+
+        // the first engine with the equipment is the one driven from
+        let Some(engine) = entities.iter().find(|entity| engines.contains(**entity)) else {
+            continue;
+        };
+        let mut pzb = engines.get_mut(*engine).unwrap();
+        let pzb = pzb.as_mut();
+
+        if pzb.acknowledge {
+            pzb.acknowledge = false;
+            pzb.acknowledge_time = None;
+        }
+        if pzb.release {
+            pzb.release = false;
+
+            if pzb.emergency {
+                pzb.emergency = speed >= STANDSTILL;
+            } else if pzb.can_release() {
+                pzb.hz1000 = None;
+                pzb.restrictive = false;
+            }
+        }
+
+        // the magnets are ignored while cab signalling supervises the train
+        let passed = if cab_signalling.is_some_and(|cab_signalling| cab_signalling.active) {
+            vec![]
+        } else {
+            passed_magnets(&data, &switches, &magnet_list, &previous.0, current, moved)
+        };
+
+        for entity in passed {
+            let (_, magnet) = magnets.get(entity).unwrap();
+            if !magnet.is_active(|signal| signals.get(signal).ok().map(|signal| signal.aspect)) {
+                continue;
+            }
+
+            match magnet.frequency {
+                MagnetFrequency::Hz1000 => {
+                    pzb.hz1000 = Some(PzbMonitoring::default());
+                    pzb.acknowledge_time = Some(PZB_ACKNOWLEDGE_TIME);
+                    pzb.crawling_time = 0.0;
+                }
+                MagnetFrequency::Hz500 => pzb.hz500 = Some(PzbMonitoring::default()),
+                MagnetFrequency::Hz2000 => trip(pzb),
+            }
+        }
+
+        advance(&mut pzb.hz1000, speed, delta_seconds, PZB_1000_HZ_DISTANCE);
+        advance(&mut pzb.hz500, speed, delta_seconds, PZB_500_HZ_DISTANCE);
+
+        if pzb.hz1000.is_some() && speed < PZB_CRAWLING_SPEED / 3.6 {
+            pzb.crawling_time += delta_seconds;
+            if pzb.crawling_time >= PZB_CRAWLING_TIME {
+                pzb.restrictive = true;
+            }
+        } else {
+            pzb.crawling_time = 0.0;
+        }
+
+        if pzb.hz1000.is_none() && pzb.hz500.is_none() && !pzb.emergency {
+            pzb.restrictive = false;
+        }
+
+        if let Some(acknowledge_time) = pzb.acknowledge_time.as_mut() {
+            *acknowledge_time -= delta_seconds;
+            if *acknowledge_time <= 0.0 {
+                pzb.acknowledge_time = None;
+                trip(pzb);
+            }
+        }
+
+        if pzb
+            .permitted_speed()
+            .is_some_and(|permitted| speed > permitted)
+        {
+            trip(pzb);
+        }
+
+        for entity in entities {
+            if let Ok(mut brake_lever) = brake_levers.get_mut(entity) {
+                brake_lever.set_intervention(BrakeIntervention::Pzb, pzb.emergency);
+            }
+        }
+    }
+}
@@ -0,0 +1,263 @@
+use super::*;
+use crate::{
+    landscape::{Aspect, CoordinatePoint, MagnetSource, Path},
+    train::{Direction, TrainComponent},
+};
+use coverage_helper::test;
+use std::{collections::HashMap, time::Duration};
+
+#[coverage(off)]
+fn gen_data() -> OSMData {
+    let mut rails = HashMap::default();
+    rails.insert(
+        (0, 1),
+        Path {
+            start_id: 0,
+            end_id: 1,
+            start_coords: CoordinatePoint(0.0, 0.0),
+            end_coords: CoordinatePoint(5000.0, 0.0),
+            ..default()
+        },
+    );
+    OSMData { rails, ..default() }
+}
+
+#[coverage(off)]
+fn location(distance: f64) -> TrackLocation {
+    TrackLocation {
+        id: (0, 1),
+        distance,
+        travel_direction: Direction::Forward,
+    }
+}
+
+#[coverage(off)]
+fn magnet(frequency: MagnetFrequency, source: MagnetSource) -> TrackMagnet {
+    TrackMagnet {
+        rail: (0, 1),
+        offset: 50.0,
+        direction: Direction::Forward,
+        frequency,
+        source,
+    }
+}
+
+#[coverage(off)]
+fn setup(magnet: TrackMagnet) -> (App, Entity, Entity) {
+    let mut app = App::new();
+    app.add_systems(Update, system);
+    app.insert_resource(gen_data());
+    app.init_resource::<SwitchPositions>();
+    app.init_resource::<Time>();
+
+    app.world_mut().spawn(magnet);
+    let engine = app
+        .world_mut()
+        .spawn((
+            Pzb::default(),
+            BrakeLever::default(),
+            PreviousTrackLocation(location(40.0)),
+            location(40.0),
+        ))
+        .id();
+    let train = app
+        .world_mut()
+        .spawn((
+            TrainComposition {
+                components: vec![TrainComponent::Engine(engine)],
+            },
+            Speed(20.0),
+        ))
+        .id();
+
+    (app, train, engine)
+}
+
+// one second at the given speed, moving the engine from `from` to `to`
+#[coverage(off)]
+fn drive(app: &mut App, train: Entity, engine: Entity, speed: f32, from: f64, to: f64) {
+    app.world_mut().get_mut::<Speed>(train).unwrap().0 = speed;
+    app.world_mut()
+        .entity_mut(engine)
+        .insert((PreviousTrackLocation(location(from)), location(to)));
+
+    let mut time = app.world_mut().resource_mut::<Time>();
+    time.advance_by(Duration::from_secs(1));
+    app.update();
+}
+
+#[coverage(off)]
+fn pzb(app: &App, engine: Entity) -> &Pzb {
+    app.world().get::<Pzb>(engine).unwrap()
+}
+
+#[coverage(off)]
+fn is_braking(app: &App, engine: Entity) -> bool {
+    app.world()
+        .get::<BrakeLever>(engine)
+        .unwrap()
+        .interventions
+        .contains(&BrakeIntervention::Pzb)
+}
+
+#[test]
+fn unacknowledged_1000_hz_trips() {
+    let (mut app, train, engine) = setup(magnet(
+        MagnetFrequency::Hz1000,
+        MagnetSource::SpeedRestriction,
+    ));
+
+    drive(&mut app, train, engine, 20.0, 40.0, 60.0);
+    assert!(pzb(&app, engine).hz1000.is_some());
+    assert!(!is_braking(&app, engine));
+
+    for _ in 0..3 {
+        drive(&mut app, train, engine, 20.0, 60.0, 60.0);
+    }
+    assert!(is_braking(&app, engine));
+    assert!(pzb(&app, engine).restrictive);
+
+    // released only at a standstill
+    app.world_mut().get_mut::<Pzb>(engine).unwrap().release = true;
+    drive(&mut app, train, engine, 5.0, 60.0, 60.0);
+    assert!(is_braking(&app, engine));
+
+    app.world_mut().get_mut::<Pzb>(engine).unwrap().release = true;
+    drive(&mut app, train, engine, 0.0, 60.0, 60.0);
+    assert!(!is_braking(&app, engine));
+}
+
+#[test]
+fn braking_curve_after_1000_hz() {
+    let (mut app, train, engine) = setup(magnet(
+        MagnetFrequency::Hz1000,
+        MagnetSource::SpeedRestriction,
+    ));
+
+    drive(&mut app, train, engine, 20.0, 40.0, 60.0);
+    app.world_mut().get_mut::<Pzb>(engine).unwrap().acknowledge = true;
+    drive(&mut app, train, engine, 20.0, 60.0, 60.0);
+    assert_eq!(pzb(&app, engine).acknowledge_time, None);
+
+    // 72 km/h stays below the curve
+    for _ in 0..20 {
+        drive(&mut app, train, engine, 20.0, 60.0, 60.0);
+    }
+    assert!(!is_braking(&app, engine));
+
+    // 108 km/h is above it after 23 s
+    drive(&mut app, train, engine, 30.0, 60.0, 60.0);
+    drive(&mut app, train, engine, 30.0, 60.0, 60.0);
+    assert!(is_braking(&app, engine));
+}
+
+#[test]
+fn releasing_after_700_m() {
+    let (mut app, train, engine) = setup(magnet(
+        MagnetFrequency::Hz1000,
+        MagnetSource::SpeedRestriction,
+    ));
+
+    drive(&mut app, train, engine, 20.0, 40.0, 60.0);
+    app.world_mut().get_mut::<Pzb>(engine).unwrap().acknowledge = true;
+    app.world_mut().get_mut::<Pzb>(engine).unwrap().release = true;
+    drive(&mut app, train, engine, 20.0, 60.0, 60.0);
+    assert!(pzb(&app, engine).hz1000.is_some());
+
+    for _ in 0..35 {
+        drive(&mut app, train, engine, 20.0, 60.0, 60.0);
+    }
+    app.world_mut().get_mut::<Pzb>(engine).unwrap().release = true;
+    drive(&mut app, train, engine, 20.0, 60.0, 60.0);
+    assert!(pzb(&app, engine).hz1000.is_none());
+    assert!(!is_braking(&app, engine));
+}
+
+#[test]
+fn crawling_gets_restrictive() {
+    let (mut app, train, engine) = setup(magnet(
+        MagnetFrequency::Hz1000,
+        MagnetSource::SpeedRestriction,
+    ));
+
+    drive(&mut app, train, engine, 2.0, 40.0, 60.0);
+    app.world_mut().get_mut::<Pzb>(engine).unwrap().acknowledge = true;
+    for _ in 0..16 {
+        drive(&mut app, train, engine, 2.0, 60.0, 60.0);
+    }
+    assert!(pzb(&app, engine).restrictive);
+    assert_eq!(pzb(&app, engine).permitted_speed(), Some(45.0 / 3.6));
+    assert!(!is_braking(&app, engine));
+
+    // 54 km/h exceeds the restrictive curve
+    drive(&mut app, train, engine, 15.0, 60.0, 60.0);
+    assert!(is_braking(&app, engine));
+}
+
+#[test]
+fn stop_signals_trip_at_2000_hz() {
+    let (mut app, train, engine) = setup(magnet(
+        MagnetFrequency::Hz2000,
+        MagnetSource::SpeedRestriction,
+    ));
+    let signal = app
+        .world_mut()
+        .spawn(Signal {
+            index: 0,
+            kind: crate::landscape::SignalKind::Main,
+            location: location(50.0),
+            aspect: Aspect::Clear,
+        })
+        .id();
+    let mut magnets = app.world_mut().query::<&mut TrackMagnet>();
+    magnets.single_mut(app.world_mut()).source = MagnetSource::Signal {
+        entity: signal,
+        aspect: Aspect::Stop,
+    };
+
+    // passing a clear signal
+    drive(&mut app, train, engine, 20.0, 40.0, 60.0);
+    assert!(!is_braking(&app, engine));
+
+    app.world_mut().get_mut::<Signal>(signal).unwrap().aspect = Aspect::Stop;
+    drive(&mut app, train, engine, 20.0, 40.0, 60.0);
+    assert!(is_braking(&app, engine));
+}
+
+#[test]
+fn cab_signalling_suppresses_magnets() {
+    let (mut app, train, engine) = setup(magnet(
+        MagnetFrequency::Hz1000,
+        MagnetSource::SpeedRestriction,
+    ));
+    app.world_mut().entity_mut(train).insert(CabSignalling {
+        active: true,
+        ..default()
+    });
+
+    drive(&mut app, train, engine, 20.0, 40.0, 60.0);
+    assert!(pzb(&app, engine).hz1000.is_none());
+    assert!(!is_braking(&app, engine));
+}
+
+#[test]
+fn magnets_act_at_the_leading_vehicle() {
+    let (mut app, train, engine) = setup(magnet(
+        MagnetFrequency::Hz1000,
+        MagnetSource::SpeedRestriction,
+    ));
+
+    // pushed from the engine at the rear, only the wagon passes the magnet
+    let wagon = app
+        .world_mut()
+        .spawn((PreviousTrackLocation(location(40.0)), location(60.0)))
+        .id();
+    app.world_mut()
+        .get_mut::<TrainComposition>(train)
+        .unwrap()
+        .components
+        .insert(0, TrainComponent::Wagon(wagon));
+
+    drive(&mut app, train, engine, 20.0, 20.0, 40.0);
+    assert!(pzb(&app, engine).hz1000.is_some());
+}
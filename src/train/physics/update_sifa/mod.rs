@@ -0,0 +1,59 @@
+#[cfg(test)]
+mod tests;
+
+use crate::train::{
+    BrakeIntervention, BrakeLever, Sifa, Speed, TrainComposition, Vigilance, STANDSTILL,
+};
+use bevy::prelude::*;
+
+pub fn system(
+    trains: Query<(&TrainComposition, &Speed)>,
+    mut engines: Query<&mut Sifa>,
+    mut brake_levers: Query<&mut BrakeLever>,
+    vigilance: Res<Vigilance>,
+    time: Res<Time>,
+) {
+    let delta_seconds = time.delta_seconds();
+
+    for (composition, speed) in trains.iter() {
+        let entities = composition.entities();
+        let speed = speed.0.abs();
+
+        // the first engine with the device is the one driven from
+        let Some(engine) = entities.iter().find(|entity| engines.contains(**entity)) else {
+            continue;
+        };
+        let mut sifa = engines.get_mut(*engine).unwrap();
+
+        if *vigilance == Vigilance::Disabled {
+            sifa.elapsed = 0.0;
+            sifa.emergency = false;
+            sifa.acknowledge = false;
+        } else {
+            if sifa.acknowledge {
+                sifa.acknowledge = false;
+                sifa.elapsed = 0.0;
+
+                // the brakes are only released once the train stands
+                if speed < STANDSTILL {
+                    sifa.emergency = false;
+                }
+            }
+
+            if speed >= STANDSTILL && !sifa.emergency {
+                sifa.elapsed += delta_seconds;
+
+                if sifa.time_left() <= 0.0 {
+                    sifa.emergency = true;
+                    log::warn!("Sifa emergency brake application");
+                }
+            }
+        }
+
+        for entity in entities {
+            if let Ok(mut brake_lever) = brake_levers.get_mut(entity) {
+                brake_lever.set_intervention(BrakeIntervention::Sifa, sifa.emergency);
+            }
+        }
+    }
+}
@@ -0,0 +1,112 @@
+use super::*;
+use crate::train::{SifaStage, TrainComponent};
+use coverage_helper::test;
+use std::time::Duration;
+
+#[coverage(off)]
+fn setup(vigilance: Vigilance) -> (App, Entity, Entity) {
+    let mut app = App::new();
+    app.add_systems(Update, system);
+    app.insert_resource(vigilance);
+    app.init_resource::<Time>();
+
+    let engine = app
+        .world_mut()
+        .spawn((Sifa::default(), BrakeLever::default()))
+        .id();
+    let train = app
+        .world_mut()
+        .spawn((
+            TrainComposition {
+                components: vec![TrainComponent::Engine(engine)],
+            },
+            Speed(20.0),
+        ))
+        .id();
+
+    (app, train, engine)
+}
+
+#[coverage(off)]
+fn run_for(app: &mut App, seconds: u64) {
+    for _ in 0..seconds {
+        let mut time = app.world_mut().resource_mut::<Time>();
+        time.advance_by(Duration::from_secs(1));
+        app.update();
+    }
+}
+
+#[coverage(off)]
+fn sifa(app: &App, engine: Entity) -> &Sifa {
+    app.world().get::<Sifa>(engine).unwrap()
+}
+
+#[coverage(off)]
+fn is_braking(app: &App, engine: Entity) -> bool {
+    app.world()
+        .get::<BrakeLever>(engine)
+        .unwrap()
+        .interventions
+        .contains(&BrakeIntervention::Sifa)
+}
+
+#[test]
+fn escalates_without_acknowledgement() {
+    let (mut app, train, engine) = setup(Vigilance::Enabled);
+
+    run_for(&mut app, 31);
+    assert_eq!(sifa(&app, engine).stage(), SifaStage::VisualWarning);
+    assert!(!is_braking(&app, engine));
+
+    run_for(&mut app, 4);
+    assert_eq!(sifa(&app, engine).stage(), SifaStage::AudibleWarning);
+
+    run_for(&mut app, 2);
+    assert_eq!(sifa(&app, engine).stage(), SifaStage::Emergency);
+    assert!(is_braking(&app, engine));
+
+    // acknowledging does not release the brakes while moving
+    app.world_mut().get_mut::<Sifa>(engine).unwrap().acknowledge = true;
+    run_for(&mut app, 1);
+    assert!(is_braking(&app, engine));
+
+    app.world_mut().get_mut::<Speed>(train).unwrap().0 = 0.0;
+    app.world_mut().get_mut::<Sifa>(engine).unwrap().acknowledge = true;
+    run_for(&mut app, 1);
+    assert!(!is_braking(&app, engine));
+    assert_eq!(sifa(&app, engine).stage(), SifaStage::Monitoring);
+}
+
+#[test]
+fn acknowledging_restarts_the_interval() {
+    let (mut app, _, engine) = setup(Vigilance::Enabled);
+
+    run_for(&mut app, 32);
+    assert_eq!(sifa(&app, engine).stage(), SifaStage::VisualWarning);
+
+    app.world_mut().get_mut::<Sifa>(engine).unwrap().acknowledge = true;
+    run_for(&mut app, 1);
+    let sifa = sifa(&app, engine);
+    assert_eq!(sifa.stage(), SifaStage::Monitoring);
+    assert!(!sifa.acknowledge);
+    assert_eq!(sifa.elapsed, 1.0);
+}
+
+#[test]
+fn waits_while_standing() {
+    let (mut app, train, engine) = setup(Vigilance::Enabled);
+    app.world_mut().get_mut::<Speed>(train).unwrap().0 = 0.0;
+
+    run_for(&mut app, 60);
+    assert_eq!(sifa(&app, engine).elapsed, 0.0);
+    assert!(!is_braking(&app, engine));
+}
+
+#[test]
+fn disabled_by_the_scenario() {
+    let (mut app, _, engine) = setup(Vigilance::Disabled);
+
+    run_for(&mut app, 60);
+    assert_eq!(sifa(&app, engine).stage(), SifaStage::Monitoring);
+    assert!(!is_braking(&app, engine));
+}
@@ -0,0 +1,53 @@
+#[cfg(test)]
+mod tests;
+
+use crate::{
+    landscape::{OSMData, SwitchPositions},
+    train::{Direction, MaxSpeed, Speed, SpeedLimit, TrackLocation, TrainComposition},
+};
+use bevy::prelude::*;
+
+// m, how far ahead the next limit is looked up
+const SPEED_LIMIT_LOOKAHEAD: f64 = 2000.0;
+
+pub fn system(
+    data: Res<OSMData>,
+    switches: Res<SwitchPositions>,
+    mut trains: Query<(&TrainComposition, &Speed, &MaxSpeed, &mut SpeedLimit)>,
+    locations: Query<&TrackLocation>,
+) {
+    for (composition, speed, max_speed, mut speed_limit) in trains.iter_mut() {
+        let entities = composition.entities();
+        let permitted = |kmh: f32| (kmh / 3.6).min(max_speed.0);
+
+        // a limit holds until the last vehicle has left it behind
+        let current = entities
+            .iter()
+            .filter_map(|entity| locations.get(*entity).ok())
+            .filter_map(|location| data.rails.get(&location.id))
+            .map(|rail| permitted(rail.speed_limit()))
+            .reduce(f32::min);
+
+        let (direction, leading) = if speed.0 < 0.0 {
+            (Direction::Backward, entities.last())
+        } else {
+            (Direction::Forward, entities.first())
+        };
+
+        let next = leading
+            .and_then(|entity| locations.get(*entity).ok())
+            .and_then(|location| {
+                location
+                    .speed_limits_ahead(&data, &switches, direction, SPEED_LIMIT_LOOKAHEAD)
+                    .into_iter()
+                    .skip(1)
+                    .map(|(distance, kmh)| (permitted(kmh), distance))
+                    .find(|(limit, _)| Some(*limit) != current)
+            });
+
+        let new_limit = SpeedLimit { current, next };
+        if *speed_limit != new_limit {
+            *speed_limit = new_limit;
+        }
+    }
+}
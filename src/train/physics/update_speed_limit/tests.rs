@@ -0,0 +1,106 @@
+use super::*;
+use crate::{
+    landscape::{CoordinatePoint, Path},
+    train::TrainComponent,
+};
+use coverage_helper::test;
+use std::collections::HashMap;
+
+#[coverage(off)]
+fn gen_data() -> OSMData {
+    let mut rails = HashMap::default();
+    rails.insert(
+        (0, 1),
+        Path {
+            start_id: 0,
+            end_id: 1,
+            start_coords: CoordinatePoint(0.0, 0.0),
+            end_coords: CoordinatePoint(100.0, 0.0),
+            forward_connections: vec![((1, 2), Direction::Forward)],
+            ..default()
+        },
+    );
+    let mut slow = Path {
+        start_id: 1,
+        end_id: 2,
+        start_coords: CoordinatePoint(100.0, 0.0),
+        end_coords: CoordinatePoint(200.0, 0.0),
+        backward_connections: vec![((0, 1), Direction::Backward)],
+        ..default()
+    };
+    slow.tags.max_speed = Some(60.0);
+    rails.insert((1, 2), slow);
+    OSMData { rails, ..default() }
+}
+
+#[coverage(off)]
+fn location(id: (i64, i64), distance: f64) -> TrackLocation {
+    TrackLocation {
+        id,
+        distance,
+        travel_direction: Direction::Forward,
+    }
+}
+
+#[coverage(off)]
+fn setup(max_speed: f32) -> (App, Entity, Entity) {
+    let mut app = App::new();
+    app.add_systems(Update, system);
+    app.insert_resource(gen_data());
+    app.init_resource::<SwitchPositions>();
+
+    let engine = app.world_mut().spawn(location((0, 1), 90.0)).id();
+    let wagon = app.world_mut().spawn(location((0, 1), 70.0)).id();
+    let train = app
+        .world_mut()
+        .spawn((
+            TrainComposition {
+                components: vec![TrainComponent::Engine(engine), TrainComponent::Wagon(wagon)],
+            },
+            Speed(10.0),
+            MaxSpeed::from_kmh(max_speed),
+            SpeedLimit::default(),
+        ))
+        .id();
+
+    (app, train, engine)
+}
+
+#[test]
+fn looks_up_the_line_speed() {
+    let (mut app, train, engine) = setup(120.0);
+    app.update();
+
+    let speed_limit = app.world().get::<SpeedLimit>(train).unwrap();
+    assert_eq!(speed_limit.current, Some(100.0 / 3.6));
+    let (limit, distance) = speed_limit.next.unwrap();
+    assert_eq!(limit, 60.0 / 3.6);
+    assert!((distance - 10.0).abs() < 1e-9);
+
+    // the slower limit applies as soon as the engine enters it
+    *app.world_mut().get_mut::<TrackLocation>(engine).unwrap() = location((1, 2), 5.0);
+    app.update();
+
+    let speed_limit = app.world().get::<SpeedLimit>(train).unwrap();
+    assert_eq!(speed_limit.current, Some(60.0 / 3.6));
+    assert_eq!(speed_limit.next, None);
+
+    // reversing looks out from the other end of the train
+    app.world_mut().get_mut::<Speed>(train).unwrap().0 = -10.0;
+    *app.world_mut().get_mut::<TrackLocation>(engine).unwrap() = location((0, 1), 90.0);
+    app.update();
+
+    let speed_limit = app.world().get::<SpeedLimit>(train).unwrap();
+    assert_eq!(speed_limit.current, Some(100.0 / 3.6));
+    assert_eq!(speed_limit.next, None);
+}
+
+#[test]
+fn slowest_vehicle_caps_the_limit() {
+    let (mut app, train, _) = setup(50.0);
+    app.update();
+
+    let speed_limit = app.world().get::<SpeedLimit>(train).unwrap();
+    assert_eq!(speed_limit.current, Some(50.0 / 3.6));
+    assert_eq!(speed_limit.next, None);
+}
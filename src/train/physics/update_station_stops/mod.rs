@@ -0,0 +1,92 @@
+#[cfg(test)]
+mod tests;
+
+use crate::{
+    landscape::{distance_to_window, OSMData, StopWindows, SwitchPositions},
+    train::{
+        Direction, Speed, StationStops, StopCall, TrackLocation, TrainComposition, STANDSTILL,
+    },
+};
+use bevy::prelude::*;
+
+// m, how far ahead the next stop is looked for
+const STOP_LOOKAHEAD: f64 = 10000.0;
+
+pub fn system(
+    (data, switches, windows): (Res<OSMData>, Res<SwitchPositions>, Res<StopWindows>),
+    mut trains: Query<(Entity, &TrainComposition, &Speed, &mut StationStops)>,
+    locations: Query<&TrackLocation>,
+    time: Res<Time>,
+) {
+    for (train, composition, speed, mut stops) in trains.iter_mut() {
+        // stops that are not on the track cannot be called at
+        while windows.0.get(stops.next).is_some_and(Option::is_none) {
+            stops.next += 1;
+        }
+        let Some(Some(window)) = windows.0.get(stops.next) else {
+            stops.distance = None;
+            continue;
+        };
+
+        let entities = composition.entities();
+        let vehicles: Vec<&TrackLocation> = entities
+            .iter()
+            .filter_map(|entity| locations.get(*entity).ok())
+            .collect();
+        if vehicles.is_empty() {
+            continue;
+        }
+        let inside = vehicles
+            .iter()
+            .filter(|location| window.contains(&data, location))
+            .count();
+        let next = stops.next;
+
+        if stops.is_calling() {
+            if inside < vehicles.len() {
+                let call = stops.calls.last_mut().expect("the call to be recorded");
+                call.departure = Some(time.elapsed_seconds());
+                log::info!("{:?} departed from stop {}", train, next);
+
+                stops.next += 1;
+                stops.is_entered = false;
+            }
+        } else if inside == vehicles.len() && speed.0.abs() < STANDSTILL {
+            stops.calls.push(StopCall {
+                stop: next,
+                arrival: time.elapsed_seconds(),
+                departure: None,
+            });
+            log::info!("{:?} arrived at stop {}", train, next);
+        } else if inside > 0 {
+            stops.is_entered = true;
+        } else if stops.is_entered {
+            log::warn!("{:?} passed stop {} without calling", train, next);
+
+            stops.next += 1;
+            stops.is_entered = false;
+        }
+
+        let (direction, leading) = if speed.0 < 0.0 {
+            (Direction::Backward, entities.last())
+        } else {
+            (Direction::Forward, entities.first())
+        };
+        let leading = leading.and_then(|entity| locations.get(*entity).ok());
+
+        stops.distance = windows
+            .0
+            .get(stops.next)
+            .and_then(Option::as_ref)
+            .zip(leading)
+            .and_then(|(window, leading)| {
+                distance_to_window(
+                    &data,
+                    &switches,
+                    &leading.facing(&data, direction),
+                    window,
+                    STOP_LOOKAHEAD,
+                )
+            });
+    }
+}
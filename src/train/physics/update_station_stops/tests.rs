@@ -0,0 +1,156 @@
+use super::*;
+use crate::{
+    landscape::{CoordinatePoint, Path, StopWindow},
+    train::TrainComponent,
+};
+use coverage_helper::test;
+use std::time::Duration;
+
+// three straight rails of 1 km in a row
+#[coverage(off)]
+fn gen_data() -> OSMData {
+    let mut rails = std::collections::HashMap::default();
+    for i in 0..3 {
+        let mut path = Path {
+            start_id: i,
+            end_id: i + 1,
+            start_coords: CoordinatePoint(i as f64 * 1000.0, 0.0),
+            end_coords: CoordinatePoint((i + 1) as f64 * 1000.0, 0.0),
+            ..default()
+        };
+        if i > 0 {
+            path.backward_connections = vec![((i - 1, i), Direction::Backward)];
+        }
+        if i < 2 {
+            path.forward_connections = vec![((i + 1, i + 2), Direction::Forward)];
+        }
+        rails.insert(path.id(), path);
+    }
+    OSMData { rails, ..default() }
+}
+
+#[coverage(off)]
+fn location(id: (i64, i64), distance: f64) -> TrackLocation {
+    TrackLocation {
+        id,
+        distance,
+        travel_direction: Direction::Forward,
+    }
+}
+
+#[coverage(off)]
+fn window(id: (i64, i64), start: f64, end: f64) -> Option<StopWindow> {
+    Some(StopWindow {
+        segments: vec![(id, start, end)],
+    })
+}
+
+// the third stop is not on the track
+#[coverage(off)]
+fn setup() -> (App, Entity, Entity) {
+    let mut app = App::new();
+    app.add_systems(Update, system);
+    app.insert_resource(gen_data())
+        .insert_resource(StopWindows(vec![
+            window((0, 1), 0.0, 200.0),
+            window((1, 2), 400.0, 600.0),
+            None,
+            window((2, 3), 800.0, 1000.0),
+        ]))
+        .init_resource::<SwitchPositions>()
+        .init_resource::<Time>();
+
+    let engine = app.world_mut().spawn(location((0, 1), 100.0)).id();
+    let train = app
+        .world_mut()
+        .spawn((
+            TrainComposition {
+                components: vec![TrainComponent::Engine(engine)],
+            },
+            Speed(0.0),
+            StationStops::default(),
+        ))
+        .id();
+
+    (app, train, engine)
+}
+
+// one second at the given speed with the engine at `to`
+#[coverage(off)]
+fn drive(app: &mut App, train: Entity, engine: Entity, speed: f32, to: TrackLocation) {
+    app.world_mut().get_mut::<Speed>(train).unwrap().0 = speed;
+    app.world_mut().entity_mut(engine).insert(to);
+
+    let mut time = app.world_mut().resource_mut::<Time>();
+    time.advance_by(Duration::from_secs(1));
+    app.update();
+}
+
+#[coverage(off)]
+fn stops(app: &App, train: Entity) -> &StationStops {
+    app.world().get::<StationStops>(train).unwrap()
+}
+
+#[test]
+fn calls_at_stops() {
+    let (mut app, train, engine) = setup();
+
+    drive(&mut app, train, engine, 0.0, location((0, 1), 100.0));
+    assert!(stops(&app, train).is_calling());
+    assert_eq!(stops(&app, train).distance, Some(0.0));
+
+    drive(&mut app, train, engine, 10.0, location((0, 1), 300.0));
+    let progress = stops(&app, train);
+    assert!(!progress.is_calling());
+    assert_eq!(progress.next, 1);
+    assert_eq!(progress.distance, Some(1100.0));
+    assert_eq!(
+        progress.calls,
+        vec![StopCall {
+            stop: 0,
+            arrival: 1.0,
+            departure: Some(2.0),
+        }]
+    );
+
+    // still rolling inside the window
+    drive(&mut app, train, engine, 5.0, location((1, 2), 450.0));
+    assert!(!stops(&app, train).is_calling());
+    assert_eq!(stops(&app, train).distance, Some(0.0));
+
+    drive(&mut app, train, engine, 0.0, location((1, 2), 500.0));
+    assert!(stops(&app, train).is_calling());
+
+    // the stop that is not on the track is skipped
+    drive(&mut app, train, engine, 10.0, location((1, 2), 700.0));
+    drive(&mut app, train, engine, 10.0, location((1, 2), 710.0));
+    let progress = stops(&app, train);
+    assert_eq!(progress.next, 3);
+    assert_eq!(progress.distance, Some(1090.0));
+    assert_eq!(progress.calls.len(), 2);
+}
+
+#[test]
+fn passes_without_calling() {
+    let (mut app, train, engine) = setup();
+
+    drive(&mut app, train, engine, 10.0, location((0, 1), 100.0));
+    assert!(stops(&app, train).is_entered);
+
+    drive(&mut app, train, engine, 10.0, location((0, 1), 300.0));
+    let progress = stops(&app, train);
+    assert_eq!(progress.next, 1);
+    assert!(!progress.is_entered);
+    assert!(progress.calls.is_empty());
+}
+
+#[test]
+fn no_more_stops() {
+    let (mut app, train, engine) = setup();
+    app.insert_resource(StopWindows::default());
+
+    drive(&mut app, train, engine, 0.0, location((0, 1), 100.0));
+    let progress = stops(&app, train);
+    assert_eq!(progress.distance, None);
+    assert!(progress.calls.is_empty());
+}
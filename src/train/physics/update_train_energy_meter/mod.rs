@@ -0,0 +1,28 @@
+#[cfg(test)]
+mod tests;
+
+use crate::train::{EnergyMeter, Train, TrainComposition};
+use bevy::prelude::*;
+
+// traction and losses are metered from the forces of the whole train, what
+// is drawn, regenerated and burnt comes from its engines
+pub fn system(
+    mut trains: Query<(&mut EnergyMeter, &TrainComposition), With<Train>>,
+    engines: Query<&EnergyMeter, Without<Train>>,
+) {
+    for (mut energy_meter, composition) in trains.iter_mut() {
+        let engine_meters: Vec<&EnergyMeter> = composition
+            .entities()
+            .into_iter()
+            .filter_map(|entity| engines.get(entity).ok())
+            .collect();
+        let sum =
+            |value: fn(&EnergyMeter) -> f32| engine_meters.iter().map(|meter| value(meter)).sum();
+
+        // diesel and coal are kept apart, their energy is part of drawn
+        energy_meter.drawn = sum(|meter| meter.drawn);
+        energy_meter.regenerated = sum(|meter| meter.regenerated);
+        energy_meter.diesel = sum(|meter| meter.diesel);
+        energy_meter.coal = sum(|meter| meter.coal);
+    }
+}
@@ -0,0 +1,55 @@
+use super::*;
+use crate::train::{TrainBundle, TrainComponent};
+use coverage_helper::test;
+
+#[test]
+fn sums_up_engines() {
+    let mut app = App::new();
+    app.add_systems(Update, system);
+
+    let first = app
+        .world_mut()
+        .spawn(EnergyMeter {
+            drawn: 10.0,
+            regenerated: 2.0,
+            traction: 8.0,
+            ..default()
+        })
+        .id();
+    let second = app
+        .world_mut()
+        .spawn(EnergyMeter {
+            drawn: 5.0,
+            diesel: 3.0,
+            coal: 1.0,
+            ..default()
+        })
+        .id();
+    let wagon = app.world_mut().spawn_empty().id();
+
+    let train = app
+        .world_mut()
+        .spawn(TrainBundle::new(
+            "Test",
+            vec![
+                TrainComponent::Engine(first),
+                TrainComponent::Wagon(wagon),
+                TrainComponent::Engine(second),
+            ],
+        ))
+        .id();
+    app.world_mut()
+        .get_mut::<EnergyMeter>(train)
+        .unwrap()
+        .traction = 20.0;
+
+    app.update();
+
+    let meter = app.world().get::<EnergyMeter>(train).unwrap();
+    assert_eq!(meter.drawn, 15.0);
+    assert_eq!(meter.regenerated, 2.0);
+    assert_eq!(meter.diesel, 3.0);
+    assert_eq!(meter.coal, 1.0);
+    // metered by the train itself
+    assert_eq!(meter.traction, 20.0);
+}
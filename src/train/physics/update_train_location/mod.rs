@@ -2,34 +2,82 @@
 mod tests;
 
 use crate::{
-    landscape::OSMData,
-    train::{Speed, TrackLocation, TrainComposition},
+    landscape::{OSMData, SwitchPositions, SwitchTrailed},
+    train::{Collision, CollisionSeverity, Speed, TrackLocation, TrainComposition},
 };
-use bevy::prelude::*;
+use bevy::{prelude::*, utils::HashSet};
 
 pub fn system(
     data: Res<OSMData>,
-    trains: Query<(Entity, &Speed, &TrainComposition)>,
+    switches: Res<SwitchPositions>,
+    mut trains: Query<(Entity, &mut Speed, &TrainComposition)>,
     mut locations: Query<&mut TrackLocation>,
+    mut switch_trailed: EventWriter<SwitchTrailed>,
+    mut collisions: EventWriter<Collision>,
     time: Res<Time>,
 ) {
-    for (entity, speed, composition) in trains.iter() {
+    for (entity, mut speed, composition) in trains.iter_mut() {
         if speed.0.abs() < f32::EPSILON {
             continue;
         }
         let delta_distance = speed.0 as f64 * time.delta_seconds_f64();
 
-        {
-            let mut location = locations.get_mut(entity).expect("train to have a location");
-            location.add_distance(&data, delta_distance);
-        }
+        let mut entities = vec![entity];
+        entities.extend(composition.entities());
+
+        // the whole train stops as soon as one of its vehicles reaches a
+        // buffer stop, so that the consist keeps its length
+        let overrun = entities
+            .iter()
+            .filter_map(|entity| locations.get(*entity).ok())
+            .map(|location| {
+                let mut probe = location.clone();
+                probe.add_distance(&data, &switches, delta_distance).overrun
+            })
+            .fold(0.0, f64::max);
+        let delta_distance = delta_distance - delta_distance.signum() * overrun;
+
+        let mut trailed = vec![];
 
-        for component_entity in composition.entities() {
+        for component_entity in entities {
             let mut component_location = locations
                 .get_mut(component_entity)
                 .expect("component to have a location");
 
-            component_location.add_distance(&data, delta_distance);
+            trailed.extend(
+                component_location
+                    .add_distance(&data, &switches, delta_distance)
+                    .trailed,
+            );
+        }
+
+        // every vehicle of the train runs through the same switch
+        let mut seen = HashSet::new();
+        trailed.retain(|entry| seen.insert(*entry));
+
+        for (leg, branch) in trailed {
+            switch_trailed.send(SwitchTrailed {
+                train: entity,
+                leg,
+                branch,
+            });
+        }
+
+        if overrun > 0.0 {
+            if let Some(severity) = CollisionSeverity::from_speed(speed.0) {
+                log::warn!(
+                    "{:?} hit a buffer stop at {:.1} km/h",
+                    entity,
+                    speed.as_kmh().abs()
+                );
+                collisions.send(Collision {
+                    train: entity,
+                    speed: speed.0.abs(),
+                    severity,
+                });
+            }
+
+            speed.0 = 0.0;
         }
     }
 }
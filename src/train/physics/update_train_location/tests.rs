@@ -40,6 +40,9 @@ fn updates_components() {
 
     app.add_systems(Update, system);
     app.insert_resource(gen_data());
+    app.init_resource::<SwitchPositions>();
+    app.add_event::<SwitchTrailed>();
+    app.add_event::<Collision>();
 
     let location = TrackLocation {
         id: (0, 1),
@@ -92,6 +95,9 @@ fn updates_distance() {
 
     app.add_systems(Update, system);
     app.insert_resource(gen_data());
+    app.init_resource::<SwitchPositions>();
+    app.add_event::<SwitchTrailed>();
+    app.add_event::<Collision>();
 
     let location = TrackLocation {
         id: (0, 1),
@@ -173,6 +179,9 @@ fn super_low_speed() {
 
     app.add_systems(Update, system);
     app.insert_resource(gen_data());
+    app.init_resource::<SwitchPositions>();
+    app.add_event::<SwitchTrailed>();
+    app.add_event::<Collision>();
 
     let location = TrackLocation {
         id: (0, 1),
@@ -207,3 +216,129 @@ fn super_low_speed() {
         assert_eq!(location.travel_direction, Direction::Forward);
     }
 }
+
+#[test]
+fn reports_trailed_switches() {
+    let mut app = App::new();
+
+    let mut data = gen_data();
+    data.rails
+        .get_mut(&(0, 1))
+        .unwrap()
+        .forward_connections
+        .push(((1, 3), Direction::Forward));
+    data.rails.insert(
+        (1, 3),
+        Path {
+            start_id: 1,
+            end_id: 3,
+            start_coords: CoordinatePoint(100.0, 100.0),
+            end_coords: CoordinatePoint(300.0, 150.0),
+            backward_connections: vec![((0, 1), Direction::Backward)],
+            ..default()
+        },
+    );
+
+    app.add_systems(Update, system);
+    app.insert_resource(data);
+    app.init_resource::<SwitchPositions>();
+    app.add_event::<SwitchTrailed>();
+    app.add_event::<Collision>();
+
+    let location = TrackLocation {
+        id: (1, 3),
+        travel_direction: Direction::Forward,
+        distance: 5.0,
+    };
+    let engine_id = app.world_mut().spawn(location.clone()).id();
+    let train_id = app
+        .world_mut()
+        .spawn((
+            location,
+            Speed(-10.0),
+            TrainComposition {
+                components: vec![TrainComponent::Engine(engine_id)],
+            },
+        ))
+        .id();
+
+    {
+        app.init_resource::<Time>();
+        let mut time = app.world_mut().resource_mut::<Time>();
+        time.advance_by(Duration::from_millis(1000));
+    }
+
+    app.update();
+
+    let events = app.world().resource::<Events<SwitchTrailed>>();
+    let mut reader = events.get_reader();
+    let trailed: Vec<&SwitchTrailed> = reader.read(events).collect();
+    assert_eq!(
+        trailed,
+        vec![&SwitchTrailed {
+            train: train_id,
+            leg: ((0, 1), Direction::Forward),
+            branch: ((1, 3), Direction::Forward),
+        }]
+    );
+}
+
+#[test]
+fn stops_at_buffer_stop() {
+    let mut app = App::new();
+
+    app.add_systems(Update, system);
+    app.insert_resource(gen_data());
+    app.init_resource::<SwitchPositions>();
+    app.add_event::<SwitchTrailed>();
+    app.add_event::<Collision>();
+
+    let location = TrackLocation {
+        id: (1, 2),
+        travel_direction: Direction::Forward,
+        distance: 190.0,
+    };
+    let engine_id = app.world_mut().spawn(location.clone()).id();
+    let mut wagon_location = location.clone();
+    wagon_location.distance = 170.0;
+    let wagon_id = app.world_mut().spawn(wagon_location).id();
+
+    let train_id = app
+        .world_mut()
+        .spawn((
+            location,
+            Speed(10.0),
+            TrainComposition {
+                components: vec![
+                    TrainComponent::Engine(engine_id),
+                    TrainComponent::Wagon(wagon_id),
+                ],
+            },
+        ))
+        .id();
+
+    {
+        app.init_resource::<Time>();
+        let mut time = app.world_mut().resource_mut::<Time>();
+        time.advance_by(Duration::from_millis(2000));
+    }
+
+    app.update();
+
+    let distance = |entity| app.world().get::<TrackLocation>(entity).unwrap().distance;
+    assert_eq!(distance(engine_id), 200.0);
+    assert_eq!(distance(wagon_id), 180.0);
+    assert_eq!(app.world().get::<Speed>(train_id).unwrap().0, 0.0);
+
+    let events = app.world().resource::<Events<Collision>>();
+    let mut reader = events.get_reader();
+    let collisions: Vec<&Collision> = reader.read(events).collect();
+    assert_eq!(
+        collisions,
+        vec![&Collision {
+            train: train_id,
+            speed: 10.0,
+            severity: CollisionSeverity::Severe,
+        }]
+    );
+}
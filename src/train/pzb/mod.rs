@@ -0,0 +1,139 @@
+#[cfg(test)]
+mod tests;
+
+use bevy::prelude::*;
+
+// PZB 90 for trains of the upper category
+// s, to acknowledge a 1000 Hz influence
+pub const PZB_ACKNOWLEDGE_TIME: f32 = 4.0;
+// km/h, the speed at the 1000 Hz magnet and the one to brake to
+const PZB_1000_HZ_START_SPEED: f32 = 165.0;
+const PZB_1000_HZ_TARGET_SPEED: f32 = 85.0;
+// s
+const PZB_1000_HZ_BRAKING_TIME: f32 = 23.0;
+// m
+pub const PZB_1000_HZ_DISTANCE: f32 = 1250.0;
+// m, after this the 1000 Hz lamp goes out and the monitoring can be released
+const PZB_1000_HZ_LAMP_DISTANCE: f32 = 700.0;
+// km/h, speeds at the 500 Hz magnet and at the end of its braking curve
+const PZB_500_HZ_START_SPEED: f32 = 65.0;
+const PZB_500_HZ_TARGET_SPEED: f32 = 45.0;
+// m
+const PZB_500_HZ_BRAKING_DISTANCE: f32 = 153.0;
+pub const PZB_500_HZ_DISTANCE: f32 = 250.0;
+// km/h, the restrictive curves are this much lower
+const PZB_RESTRICTIVE_REDUCTION: f32 = 20.0;
+const PZB_RESTRICTIVE_1000_HZ_SPEED: f32 = 45.0;
+// km/h and s, crawling this long during a 1000 Hz monitoring makes it restrictive
+pub const PZB_CRAWLING_SPEED: f32 = 10.0;
+pub const PZB_CRAWLING_TIME: f32 = 15.0;
+
+// time and distance since passing a magnet
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct PzbMonitoring {
+    // s
+    pub time: f32,
+    // m
+    pub distance: f32,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum Lamp {
+    #[default]
+    Off,
+    On,
+    Flashing,
+}
+
+// indicator lamps in the cab
+#[derive(Debug, Default, PartialEq)]
+pub struct PzbLamps {
+    // blue, train category
+    pub hz85: Lamp,
+    // blue, flashes alternately with 85 in restrictive mode
+    pub hz70: Lamp,
+    // yellow
+    pub hz1000: Lamp,
+    // red
+    pub hz500: Lamp,
+}
+
+// intermittent train protection, the driver acknowledges and releases with
+// the buttons, the system resets them once it has seen them
+#[derive(Component, Default, Debug)]
+pub struct Pzb {
+    pub hz1000: Option<PzbMonitoring>,
+    pub hz500: Option<PzbMonitoring>,
+    pub restrictive: bool,
+    // s left to acknowledge a 1000 Hz influence
+    pub acknowledge_time: Option<f32>,
+    // s spent below the crawling speed
+    pub crawling_time: f32,
+    pub emergency: bool,
+    pub acknowledge: bool,
+    pub release: bool,
+}
+
+impl Pzb {
+    // m/s
+    pub fn permitted_speed(&self) -> Option<f32> {
+        let reduction = if self.restrictive {
+            PZB_RESTRICTIVE_REDUCTION
+        } else {
+            0.0
+        };
+
+        let hz1000 = self.hz1000.as_ref().map(|monitoring| {
+            if self.restrictive {
+                PZB_RESTRICTIVE_1000_HZ_SPEED
+            } else {
+                let share = (monitoring.time / PZB_1000_HZ_BRAKING_TIME).min(1.0);
+                PZB_1000_HZ_START_SPEED
+                    - (PZB_1000_HZ_START_SPEED - PZB_1000_HZ_TARGET_SPEED) * share
+            }
+        });
+        let hz500 = self.hz500.as_ref().map(|monitoring| {
+            let share = (monitoring.distance / PZB_500_HZ_BRAKING_DISTANCE).min(1.0);
+            PZB_500_HZ_START_SPEED
+                - (PZB_500_HZ_START_SPEED - PZB_500_HZ_TARGET_SPEED) * share
+                - reduction
+        });
+
+        [hz1000, hz500]
+            .into_iter()
+            .flatten()
+            .reduce(f32::min)
+            .map(|kmh| kmh / 3.6)
+    }
+
+    pub fn can_release(&self) -> bool {
+        self.hz500.is_none()
+            && self.hz1000.as_ref().map_or(self.restrictive, |monitoring| {
+                monitoring.distance >= PZB_1000_HZ_LAMP_DISTANCE
+            })
+    }
+
+    pub fn lamps(&self) -> PzbLamps {
+        let hz1000_lamp = self
+            .hz1000
+            .as_ref()
+            .is_some_and(|monitoring| monitoring.distance < PZB_1000_HZ_LAMP_DISTANCE);
+        let is_monitoring = self.hz1000.is_some() || self.hz500.is_some();
+
+        let (hz85, hz70) = if self.restrictive {
+            (Lamp::Flashing, Lamp::Flashing)
+        } else if is_monitoring && !hz1000_lamp {
+            (Lamp::Flashing, Lamp::Off)
+        } else {
+            (Lamp::On, Lamp::Off)
+        };
+        let on = |is_on: bool| if is_on { Lamp::On } else { Lamp::Off };
+
+        PzbLamps {
+            hz85,
+            hz70,
+            hz1000: on(hz1000_lamp || self.emergency),
+            hz500: on(self.hz500.is_some()),
+        }
+    }
+}
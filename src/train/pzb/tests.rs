@@ -0,0 +1,42 @@
+use super::*;
+use coverage_helper::test;
+
+#[test]
+fn pzb_curves() {
+    let mut pzb = Pzb::default();
+    assert_eq!(pzb.permitted_speed(), None);
+    assert_eq!(
+        pzb.lamps(),
+        PzbLamps {
+            hz85: Lamp::On,
+            ..default()
+        }
+    );
+
+    pzb.hz1000 = Some(PzbMonitoring::default());
+    assert_eq!(pzb.permitted_speed(), Some(165.0 / 3.6));
+    assert_eq!(pzb.lamps().hz1000, Lamp::On);
+    assert!(!pzb.can_release());
+
+    pzb.hz1000 = Some(PzbMonitoring {
+        time: 30.0,
+        distance: 800.0,
+    });
+    assert_eq!(pzb.permitted_speed(), Some(85.0 / 3.6));
+    assert_eq!(pzb.lamps().hz1000, Lamp::Off);
+    assert_eq!(pzb.lamps().hz85, Lamp::Flashing);
+    assert!(pzb.can_release());
+
+    // the 500 Hz curve is lower
+    pzb.hz500 = Some(PzbMonitoring {
+        time: 5.0,
+        distance: 200.0,
+    });
+    assert_eq!(pzb.permitted_speed(), Some(45.0 / 3.6));
+    assert_eq!(pzb.lamps().hz500, Lamp::On);
+    assert!(!pzb.can_release());
+
+    pzb.restrictive = true;
+    assert_eq!(pzb.permitted_speed(), Some(25.0 / 3.6));
+    assert_eq!(pzb.lamps().hz70, Lamp::Flashing);
+}
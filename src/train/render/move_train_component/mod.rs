@@ -3,52 +3,77 @@ mod tests;
 
 use crate::{
     landscape::{HeightMap, OSMData, OriginOffset},
-    train::{Direction, TrackLocation},
+    train::{Direction, PreviousTrackLocation, TrackLocation},
 };
 use bevy::prelude::*;
 
-const SPEED: f32 = 3.0; // m/s
+// world position and heading of a location on the track
+fn placement(
+    data: &OSMData,
+    height_map: &HeightMap,
+    origin_offset: &OriginOffset,
+    location: &TrackLocation,
+) -> (Vec3, f32) {
+    let rail = data
+        .rails
+        .get(&location.id)
+        .expect("train location to be valid");
+
+    let (s, e) = match location.travel_direction {
+        Direction::Forward => (rail.start_coords, rail.end_coords),
+        Direction::Backward => (rail.end_coords, rail.start_coords),
+    };
+
+    let diff = e - s;
+    let length = rail.length();
+    let diff = diff / length;
+
+    let dest = s + diff * location.distance;
+    let dest = dest - origin_offset.0;
+
+    // calculate height
+
+    let height =
+        height_map.height_at_position(dest.0 + origin_offset.0 .0, dest.1 + origin_offset.0 .1);
+
+    let mut angle = rail.angle();
+    if location.travel_direction == Direction::Backward {
+        angle += std::f64::consts::PI;
+    }
+
+    (
+        Vec3::new(dest.0 as f32, height, -dest.1 as f32),
+        angle as f32,
+    )
+}
 
 pub fn system(
     data: Res<OSMData>,
-    mut engines: Query<(&TrackLocation, &mut Transform)>,
-    time: Res<Time>,
+    mut engines: Query<(
+        &TrackLocation,
+        Option<&PreviousTrackLocation>,
+        &mut Transform,
+    )>,
+    fixed_time: Res<Time<Fixed>>,
     height_map: Res<HeightMap>,
     origin_offset: Res<OriginOffset>,
 ) {
-    for (location, mut transform) in engines.iter_mut() {
-        let rail = data
-            .rails
-            .get(&location.id)
-            .expect("train location to be valid");
-
-        let (s, e) = match location.travel_direction {
-            Direction::Forward => (rail.start_coords, rail.end_coords),
-            Direction::Backward => (rail.end_coords, rail.start_coords),
-        };
-
-        let diff = e - s;
-        let length = rail.length();
-        let diff = diff / length;
-
-        let dest = s + diff * location.distance;
-        let dest = dest - origin_offset.0;
+    // share of the next physics tick that has already passed
+    let overstep = fixed_time.overstep_fraction();
 
-        // calculate height
+    for (location, previous_location, mut transform) in engines.iter_mut() {
+        let (target, angle) = placement(&data, &height_map, &origin_offset, location);
 
-        let height =
-            height_map.height_at_position(dest.0 + origin_offset.0 .0, dest.1 + origin_offset.0 .1);
-
-        let target = Vec3::new(dest.0 as f32, height, -dest.1 as f32);
-        let diff = target - transform.translation;
-        transform.translation +=
-            (diff * SPEED * time.delta_seconds()).clamp(-diff.abs(), diff.abs());
-
-        let mut angle = rail.angle();
-        if location.travel_direction == Direction::Backward {
-            angle += std::f64::consts::PI;
-        }
+        // smooth the movement between two physics ticks
+        transform.translation = match previous_location {
+            Some(previous_location) => {
+                let (previous, _) =
+                    placement(&data, &height_map, &origin_offset, &previous_location.0);
+                previous.lerp(target, overstep)
+            }
+            None => target,
+        };
 
-        transform.rotation = Quat::from_rotation_y(angle as f32);
+        transform.rotation = Quat::from_rotation_y(angle);
     }
 }
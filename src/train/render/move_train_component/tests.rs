@@ -1,5 +1,6 @@
 use super::*;
 use crate::landscape::{CoordinatePoint, Path};
+use bevy::time::{TimePlugin, TimeUpdateStrategy};
 use coverage_helper::test;
 use std::{collections::HashMap, time::Duration};
 
@@ -37,11 +38,7 @@ fn apply_transform_forwards() {
 
     let train_id = app.world_mut().spawn((Transform::default(), location)).id();
 
-    {
-        app.init_resource::<Time>();
-        let mut time = app.world_mut().resource_mut::<Time>();
-        time.advance_by(Duration::from_millis(1500));
-    }
+    app.init_resource::<Time<Fixed>>();
 
     app.update();
 
@@ -61,31 +58,12 @@ fn apply_transform_forwards() {
 
     let train_id = app.world_mut().spawn((Transform::default(), location)).id();
 
-    {
-        let mut time = app.world_mut().resource_mut::<Time>();
-        time.advance_by(Duration::from_millis(100));
-    }
     app.update();
 
     {
         let mut transform = app.world_mut().query::<&Transform>();
         let transform = transform.get(&app.world(), train_id).unwrap();
-        // moves partially to final destination
-        assert_eq!(transform.translation.x, 30.0);
-        assert_eq!(transform.translation.z, -30.0);
-        assert_eq!(transform.translation.y, 0.0);
-    }
-
-    {
-        let mut time = app.world_mut().resource_mut::<Time>();
-        time.advance_by(Duration::from_millis(10000));
-    }
-    app.update();
-
-    {
-        let mut transform = app.world_mut().query::<&Transform>();
-        let transform = transform.get(&app.world(), train_id).unwrap();
-        // moves partially to final destination
+        // without a previous location it is placed directly
         assert_eq!(transform.translation.x, 100.0);
         assert_eq!(transform.translation.z, -100.0);
         assert_eq!(transform.translation.y, 0.0);
@@ -110,11 +88,7 @@ fn apply_transform_backwards() {
 
     let train_id = app.world_mut().spawn((Transform::default(), location)).id();
 
-    {
-        app.init_resource::<Time>();
-        let mut time = app.world_mut().resource_mut::<Time>();
-        time.advance_by(Duration::from_millis(1500));
-    }
+    app.init_resource::<Time<Fixed>>();
 
     app.update();
 
@@ -134,18 +108,59 @@ fn apply_transform_backwards() {
 
     let train_id = app.world_mut().spawn((Transform::default(), location)).id();
 
-    {
-        let mut time = app.world_mut().resource_mut::<Time>();
-        time.advance_by(Duration::from_millis(100));
-    }
     app.update();
 
     {
         let mut transform = app.world_mut().query::<&Transform>();
         let transform = transform.get(&app.world(), train_id).unwrap();
-        // moves partially to final destination
         assert_eq!(transform.translation.x.round(), 0.0);
         assert_eq!(transform.translation.z.round(), 0.0);
         assert_eq!(transform.translation.y, 0.0);
     }
 }
+
+#[test]
+fn interpolates_between_ticks() {
+    let mut app = App::new();
+
+    app.add_plugins(TimePlugin);
+    app.insert_resource(Time::<Fixed>::from_seconds(1.0));
+    app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+        250,
+    )));
+    app.add_systems(Update, system);
+
+    app.insert_resource(HeightMap::test_dummy());
+    app.insert_resource(OriginOffset(CoordinatePoint(0.0, 0.0)));
+    app.insert_resource(gen_data());
+
+    let previous_location = TrackLocation {
+        id: (0, 1),
+        travel_direction: Direction::Forward,
+        distance: 0.0,
+    };
+    let location = TrackLocation {
+        distance: 141.42135624,
+        ..previous_location.clone()
+    };
+
+    let train_id = app
+        .world_mut()
+        .spawn((
+            Transform::default(),
+            location,
+            PreviousTrackLocation(previous_location),
+        ))
+        .id();
+
+    app.update();
+    app.update();
+
+    let overstep = app.world().resource::<Time<Fixed>>().overstep_fraction();
+    assert!(overstep > 0.0 && overstep < 1.0);
+
+    let mut transform = app.world_mut().query::<&Transform>();
+    let transform = transform.get(&app.world(), train_id).unwrap();
+    assert!((transform.translation.x - 100.0 * overstep).abs() < 0.001);
+    assert!((transform.translation.z + 100.0 * overstep).abs() < 0.001);
+}
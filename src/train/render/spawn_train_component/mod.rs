@@ -1,13 +1,11 @@
 use crate::{
-    landscape::OSMData,
+    landscape::{OSMData, SwitchPositions},
     scenario::ScenarioData,
-    train::{Dimension, LoadModelFile, TrackLocation, TrainComposition},
+    train::{Dimension, LoadModelFile, PreviousTrackLocation, TrackLocation, TrainComposition},
     TRAIN_HEIGHT_OFFSET,
 };
 use bevy::prelude::*;
 
-const WAGON_DISTANCE: f64 = 0.0;
-
 #[coverage(off)]
 pub fn system(
     trains: Query<(Entity, &TrainComposition), Without<TrackLocation>>,
@@ -15,37 +13,23 @@ pub fn system(
     dimensions: Query<&Dimension>,
     mut commands: Commands,
     asset_server: Res<AssetServer>,
-    data: Res<OSMData>,
-    scenario_data: Res<ScenarioData>,
+    (data, switches, scenario_data): (Res<OSMData>, Res<SwitchPositions>, Res<ScenarioData>),
 ) {
     if trains.is_empty() {
         return;
     }
 
-    let start_rail = scenario_data.stops.first().unwrap().node_id;
-
-    let id = data
-        .rails
-        .keys()
-        .find(
-            #[coverage(off)]
-            |(s, _e)| *s == start_rail,
-        )
-        .expect("to find rail with start id");
+    // the route reports a start that is not on the track
+    let Some(start) = TrackLocation::scenario_start(&data, &scenario_data) else {
+        return;
+    };
 
     for (entity, load_model_file) in engines.iter() {
         let model = asset_server.load(format!("{}#Scene0", load_model_file.0));
 
         commands
             .entity(entity)
-            .insert((
-                TrackLocation {
-                    id: *id,
-                    distance: 0.0,
-                    travel_direction: scenario_data.info.starting_direction,
-                },
-                PbrBundle::default(),
-            ))
+            .insert((start.clone(), PbrBundle::default()))
             .remove::<LoadModelFile>()
             .with_children(
                 #[coverage(off)]
@@ -60,30 +44,28 @@ pub fn system(
     }
 
     for (entity, composition) in trains.iter() {
-        let mut location = TrackLocation {
-            id: *id,
-            distance: 0.0,
-            travel_direction: scenario_data.info.starting_direction,
-        };
+        commands.entity(entity).insert(start.clone());
 
-        commands.entity(entity).insert(location.clone());
-
-        for (index, component_entity) in composition.entities().into_iter().enumerate() {
-            let dimension = dimensions
-                .get(component_entity)
-                .expect("component to have a dimension");
+        let entities = composition.entities();
+        let lengths: Vec<f32> = entities
+            .iter()
+            .map(
+                #[coverage(off)]
+                |component_entity| {
+                    dimensions
+                        .get(*component_entity)
+                        .expect("component to have a dimension")
+                        .length
+                },
+            )
+            .collect();
 
-            let mut new_location = location.clone();
-            if index > 0 {
-                new_location.add_distance(&data, -dimension.length as f64 / 2.0 - WAGON_DISTANCE);
-            }
+        let locations = start.consist_locations(&data, &switches, &lengths);
 
+        for (component_entity, location) in entities.into_iter().zip(locations) {
             commands
                 .entity(component_entity)
-                .insert(new_location.clone());
-
-            new_location.add_distance(&data, -dimension.length as f64 / 2.0);
-            location = new_location;
+                .insert((PreviousTrackLocation(location.clone()), location));
         }
     }
 }
@@ -0,0 +1,75 @@
+#[cfg(test)]
+mod tests;
+
+use bevy::prelude::*;
+use serde::Deserialize;
+
+// driver vigilance device (Sifa), timings in s
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct SifaTiming {
+    // driving without acknowledging until the lamp lights
+    pub interval: f32,
+    // the lamp is lit this long before the buzzer sounds
+    pub visual_warning: f32,
+    // the buzzer sounds this long before the brakes are applied
+    pub audible_warning: f32,
+}
+
+impl Default for SifaTiming {
+    fn default() -> Self {
+        Self {
+            interval: 30.0,
+            visual_warning: 4.0,
+            audible_warning: 2.5,
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum SifaStage {
+    #[default]
+    Monitoring,
+    VisualWarning,
+    AudibleWarning,
+    Emergency,
+}
+
+// the driver acknowledges with a button, the system resets it once it has
+// seen it
+#[derive(Component, Default, Debug)]
+pub struct Sifa {
+    pub timing: SifaTiming,
+    // s driven since the last acknowledgement
+    pub elapsed: f32,
+    pub emergency: bool,
+    pub acknowledge: bool,
+}
+
+impl Sifa {
+    pub fn stage(&self) -> SifaStage {
+        if self.emergency {
+            SifaStage::Emergency
+        } else if self.elapsed >= self.timing.interval + self.timing.visual_warning {
+            SifaStage::AudibleWarning
+        } else if self.elapsed >= self.timing.interval {
+            SifaStage::VisualWarning
+        } else {
+            SifaStage::Monitoring
+        }
+    }
+
+    // s until the brakes are applied
+    pub fn time_left(&self) -> f32 {
+        let timing = &self.timing;
+        (timing.interval + timing.visual_warning + timing.audible_warning - self.elapsed).max(0.0)
+    }
+}
+
+// whether the vigilance devices of a scenario's engines are switched on
+#[derive(Resource, Default, Debug, Deserialize, Clone, Copy, PartialEq)]
+pub enum Vigilance {
+    #[default]
+    Enabled,
+    Disabled,
+}
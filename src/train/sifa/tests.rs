@@ -0,0 +1,21 @@
+use super::*;
+use coverage_helper::test;
+
+#[test]
+fn sifa_stages() {
+    let mut sifa = Sifa::default();
+    assert_eq!(sifa.stage(), SifaStage::Monitoring);
+    assert_eq!(sifa.time_left(), 36.5);
+
+    sifa.elapsed = 30.0;
+    assert_eq!(sifa.stage(), SifaStage::VisualWarning);
+
+    sifa.elapsed = 35.0;
+    assert_eq!(sifa.stage(), SifaStage::AudibleWarning);
+    assert_eq!(sifa.time_left(), 1.5);
+
+    sifa.elapsed = 40.0;
+    assert_eq!(sifa.time_left(), 0.0);
+    sifa.emergency = true;
+    assert_eq!(sifa.stage(), SifaStage::Emergency);
+}
@@ -1,6 +1,7 @@
 use super::*;
 use coverage_helper::test;
 use physics::TrainPhysicsPlugin;
+#[cfg(feature = "graphics")]
 use render::TrainRenderPlugin;
 
 #[test]
@@ -13,6 +14,7 @@ fn direction_reverse() {
 fn plugin() {
     let mut app = App::default();
     app.add_plugins(TrainPlugins);
+    #[cfg(feature = "graphics")]
     assert!(app.is_plugin_added::<TrainRenderPlugin>());
     assert!(app.is_plugin_added::<TrainPhysicsPlugin>());
 }
@@ -34,7 +36,11 @@ fn train_composition() {
             Entity::from_raw(2),
             Entity::from_raw(3),
         ],
-    )
+    );
+    assert_eq!(
+        train_composition.controlling_engine(),
+        Some(Entity::from_raw(1))
+    );
 }
 
 #[test]
@@ -47,6 +53,8 @@ fn wrapped_value_derived() {
         Box::new(ForceBraking(0.0)),
         Box::new(ForceFriction(0.0)),
         Box::new(ForceAirResistance(0.0)),
+        Box::new(ForceCurveResistance(0.0)),
+        Box::new(ForceGradient(0.0)),
     ];
 
     for item in items.iter_mut() {
@@ -64,3 +72,299 @@ fn meter_per_second_conversions() {
     let max_speed = MaxSpeed::from_kmh(36.0);
     assert_eq!(max_speed.0, 10.0);
 }
+
+#[test]
+fn adhesion_coefficient() {
+    let dry = RailCondition::Dry.adhesion_coefficient(0.0, false);
+
+    assert!(RailCondition::Dry.adhesion_coefficient(30.0, false) < dry);
+    assert_eq!(
+        RailCondition::Dry.adhesion_coefficient(-30.0, false),
+        RailCondition::Dry.adhesion_coefficient(30.0, false)
+    );
+    assert!(RailCondition::Wet.adhesion_coefficient(0.0, false) < dry);
+    assert!(
+        RailCondition::Slippery.adhesion_coefficient(0.0, false)
+            < RailCondition::Wet.adhesion_coefficient(0.0, false)
+    );
+    assert!(
+        RailCondition::Wet.adhesion_coefficient(0.0, true)
+            > RailCondition::Wet.adhesion_coefficient(0.0, false)
+    );
+}
+
+#[test]
+fn tractive_effort_interpolation() {
+    let tractive_effort = TractiveEffort {
+        curve: vec![(0.0, 300.0), (60.0, 300.0), (120.0, 150.0)],
+        ..default()
+    };
+
+    assert_eq!(tractive_effort.force(0.0, 1.0), Some(300_000.0));
+    assert_eq!(tractive_effort.force(0.0, 0.5), Some(150_000.0));
+    assert!((tractive_effort.force(90.0 / 3.6, 1.0).unwrap() - 225_000.0).abs() < 1.0);
+    assert_eq!(
+        tractive_effort.force(-90.0 / 3.6, 1.0),
+        tractive_effort.force(90.0 / 3.6, 1.0)
+    );
+    // constant power beyond the table
+    assert!((tractive_effort.force(240.0 / 3.6, 1.0).unwrap() - 75_000.0).abs() < 1.0);
+
+    assert_eq!(TractiveEffort::default().force(10.0, 1.0), None);
+}
+
+#[test]
+fn tractive_effort_sorting() {
+    let mut tractive_effort = TractiveEffort {
+        curve: vec![(120.0, 150.0), (0.0, 300.0), (60.0, 300.0)],
+        notches: vec![vec![(60.0, 50.0), (0.0, 100.0)]],
+    };
+    tractive_effort.sort();
+
+    assert_eq!(
+        tractive_effort.curve,
+        vec![(0.0, 300.0), (60.0, 300.0), (120.0, 150.0)]
+    );
+    assert_eq!(tractive_effort.notches[0], vec![(0.0, 100.0), (60.0, 50.0)]);
+}
+
+#[test]
+fn tractive_effort_notches() {
+    let tractive_effort = TractiveEffort {
+        notches: vec![vec![(0.0, 100.0)], vec![(0.0, 200.0)], vec![(0.0, 300.0)]],
+        ..default()
+    };
+
+    assert_eq!(tractive_effort.force(0.0, 0.0), Some(0.0));
+    assert_eq!(tractive_effort.force(0.0, 0.1), Some(0.0));
+    assert_eq!(tractive_effort.force(0.0, 0.34), Some(100_000.0));
+    assert_eq!(tractive_effort.force(0.0, 0.66), Some(200_000.0));
+    assert_eq!(tractive_effort.force(0.0, 1.0), Some(300_000.0));
+}
+
+#[test]
+fn brake_valve_positions() {
+    let positions = BrakeValvePosition::all();
+    assert_eq!(positions.first(), Some(&BrakeValvePosition::Release));
+    assert_eq!(positions.last(), Some(&BrakeValvePosition::Emergency));
+    assert_eq!(positions.len(), 4 + SERVICE_STEPS as usize);
+
+    assert_eq!(
+        BrakeValvePosition::Lap.next(),
+        BrakeValvePosition::Service(1)
+    );
+    assert_eq!(
+        BrakeValvePosition::Service(1).previous(),
+        BrakeValvePosition::Lap
+    );
+    assert_eq!(
+        BrakeValvePosition::Emergency.next(),
+        BrakeValvePosition::Emergency
+    );
+    assert_eq!(
+        BrakeValvePosition::Release.previous(),
+        BrakeValvePosition::Release
+    );
+
+    assert!(!BrakeValvePosition::Running.is_braking());
+    assert!(!BrakeValvePosition::Lap.is_braking());
+    assert!(BrakeValvePosition::Service(3).is_braking());
+    assert!(BrakeValvePosition::Emergency.is_braking());
+
+    assert_eq!(BrakeValvePosition::Service(3).to_string(), "Service 3");
+    assert_eq!(BrakeValvePosition::Emergency.to_string(), "Emergency");
+}
+
+#[test]
+fn steam_engine_tractive_effort() {
+    let steam_engine = SteamEngine {
+        cylinders: 2,
+        cylinder_diameter: 0.6,
+        piston_stroke: 0.66,
+        wheel_diameter: 1.4,
+        max_boiler_pressure: 16.0,
+        ..default()
+    };
+
+    assert!(steam_engine.is_available());
+    assert!(!SteamEngine::default().is_available());
+
+    let full = steam_engine.tractive_effort(16.0, MAX_CUT_OFF);
+    assert!(full > 200_000.0 && full < 300_000.0);
+    assert!(steam_engine.tractive_effort(16.0, 0.2) < full);
+    assert!(steam_engine.tractive_effort(8.0, MAX_CUT_OFF) < full);
+    assert_eq!(steam_engine.tractive_effort(16.0, 0.0), 0.0);
+
+    assert_eq!(steam_engine.steam_consumption(16.0, 0.5, 0.0), 0.0);
+    assert_eq!(steam_engine.steam_consumption(0.0, 0.5, 10.0), 0.0);
+    assert!(
+        steam_engine.steam_consumption(16.0, 0.2, 10.0)
+            < steam_engine.steam_consumption(16.0, 0.5, 10.0)
+    );
+    assert!(
+        steam_engine.steam_consumption(16.0, 0.5, 10.0)
+            < steam_engine.steam_consumption(16.0, 0.5, 20.0)
+    );
+}
+
+#[test]
+fn finding_supplies() {
+    let engine = Entity::from_raw(1);
+    let tender = Entity::from_raw(2);
+    let wagon = Entity::from_raw(3);
+    let entities = vec![engine, tender, wagon];
+
+    assert_eq!(
+        supplies_for(&entities, engine, |entity| entity == tender),
+        Some(tender)
+    );
+    assert_eq!(
+        supplies_for(&entities, engine, |entity| entity != wagon),
+        Some(engine)
+    );
+    assert_eq!(
+        supplies_for(&entities, engine, |entity| entity == wagon),
+        None
+    );
+    assert_eq!(supplies_for(&entities, Entity::from_raw(4), |_| true), None);
+
+    // tender running ahead of the engine
+    assert_eq!(
+        supplies_for(&[tender, engine], engine, |entity| entity == tender),
+        Some(tender)
+    );
+}
+
+#[test]
+fn throttle_notches() {
+    let throttle_lever = ThrottleLever {
+        percentage: 0.6,
+        notches: 8,
+        ..default()
+    };
+    assert_eq!(throttle_lever.notch(), 5);
+
+    let throttle_lever = ThrottleLever {
+        percentage: 0.6,
+        ..default()
+    };
+    assert_eq!(throttle_lever.notch(), 0);
+}
+
+#[test]
+fn diesel_engine_characteristics() {
+    let diesel_engine = DieselEngine {
+        notches: 4,
+        idle_rpm: 600.0,
+        max_rpm: 1800.0,
+        idle_fuel: 10.0,
+        full_fuel: 100.0,
+        transmission: Transmission {
+            kind: TransmissionKind::Hydraulic,
+            stages: vec![
+                TransmissionStage {
+                    max_speed: 30.0,
+                    efficiency: 0.8,
+                },
+                TransmissionStage {
+                    max_speed: 60.0,
+                    efficiency: 0.85,
+                },
+            ],
+        },
+        ..default()
+    };
+
+    assert!(diesel_engine.is_available());
+    assert!(!DieselEngine::default().is_available());
+
+    assert_eq!(diesel_engine.target_rpm(0), 600.0);
+    assert_eq!(diesel_engine.target_rpm(2), 1200.0);
+    assert_eq!(diesel_engine.target_rpm(9), 1800.0);
+
+    assert_eq!(diesel_engine.power_share(300.0), 0.0);
+    assert_eq!(diesel_engine.power_share(1800.0), 1.0);
+
+    assert_eq!(diesel_engine.fuel_consumption(0.0), 0.0);
+    assert_eq!(diesel_engine.fuel_consumption(600.0), 10.0 / 3600.0);
+    assert_eq!(diesel_engine.fuel_consumption(1800.0), 100.0 / 3600.0);
+
+    let transmission = &diesel_engine.transmission;
+    assert_eq!(transmission.stage_for(0, 20.0 / 3.6), 0);
+    assert_eq!(transmission.stage_for(0, 50.0 / 3.6), 1);
+    assert_eq!(transmission.stage_for(1, 70.0 / 3.6), 1);
+    assert_eq!(transmission.stage_for(1, 10.0 / 3.6), 0);
+    assert_eq!(Transmission::default().stage_for(3, 10.0), 0);
+
+    let state = DieselState {
+        rpm: 1800.0,
+        stage: 1,
+        ..default()
+    };
+    assert_eq!(
+        diesel_engine.force(100.0, &state, 10.0),
+        100_000.0 * 0.85 / 10.0
+    );
+    // beyond the top stage
+    assert_eq!(diesel_engine.force(100.0, &state, 70.0 / 3.6), 0.0);
+}
+
+#[test]
+fn rotating_mass_from_factor() {
+    assert_eq!(RotatingMass::from_factor(&Mass(80_000.0), 1.25).0, 20_000.0);
+    assert_eq!(RotatingMass::from_factor(&Mass(80_000.0), 0.9).0, 0.0);
+}
+
+#[test]
+fn payload_changes_mass() {
+    let mut mass = Mass(20_000.0);
+    let mut payload = Payload {
+        capacity: 60_000.0,
+        ..default()
+    };
+
+    payload.set_share(0.5, &mut mass);
+    assert_eq!(mass.0, 50_000.0);
+    assert_eq!(payload.share(), 0.5);
+
+    payload.set_share(2.0, &mut mass);
+    assert_eq!(mass.0, 80_000.0);
+    assert_eq!(payload.share(), 1.0);
+
+    payload.set_share(0.0, &mut mass);
+    assert_eq!(mass.0, 20_000.0);
+    assert_eq!(Payload::default().share(), 0.0);
+}
+
+#[test]
+fn collision_severity() {
+    assert_eq!(CollisionSeverity::from_speed(0.2), None);
+    assert_eq!(
+        CollisionSeverity::from_speed(1.0),
+        Some(CollisionSeverity::Light)
+    );
+    assert_eq!(
+        CollisionSeverity::from_speed(-3.0),
+        Some(CollisionSeverity::Moderate)
+    );
+    assert_eq!(
+        CollisionSeverity::from_speed(10.0),
+        Some(CollisionSeverity::Severe)
+    );
+}
+
+#[test]
+fn brake_interventions() {
+    let mut brake_lever = BrakeLever {
+        valve: BrakeValvePosition::Release,
+        ..default()
+    };
+    assert_eq!(brake_lever.effective_valve(), BrakeValvePosition::Release);
+
+    brake_lever.set_intervention(BrakeIntervention::Overspeed, true);
+    assert_eq!(brake_lever.effective_valve(), BrakeValvePosition::Emergency);
+    assert_eq!(brake_lever.valve, BrakeValvePosition::Release);
+
+    brake_lever.set_intervention(BrakeIntervention::Overspeed, false);
+    assert_eq!(brake_lever.effective_valve(), BrakeValvePosition::Release);
+}
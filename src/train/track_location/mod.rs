@@ -2,7 +2,8 @@
 mod tests;
 
 use crate::{
-    landscape::{OSMData, PathId},
+    landscape::{OSMData, PathId, SwitchLeg, SwitchPositions},
+    scenario::ScenarioData,
     train::Direction,
 };
 use bevy::prelude::*;
@@ -14,8 +15,79 @@ pub struct TrackLocation {
     pub travel_direction: Direction,
 }
 
+// location at the previous physics tick
+#[derive(Component, Default, Clone, Debug)]
+pub struct PreviousTrackLocation(pub TrackLocation);
+
+const WAGON_DISTANCE: f64 = 0.0;
+
+// what happened while moving a location along the track
+#[derive(Default, Debug, PartialEq)]
+pub struct TrackMovement {
+    // switches that were trailed against their position, each with the
+    // branch the location came from
+    pub trailed: Vec<(SwitchLeg, SwitchLeg)>,
+    // m, the part of the distance that ended up in a buffer stop
+    pub overrun: f64,
+}
+
 impl TrackLocation {
-    pub fn add_distance(&mut self, data: &OSMData, amount: f64) {
+    // start of the scenario, at its first stop. none if the scenario has no
+    // stops or no rail starts at the first one
+    pub fn scenario_start(data: &OSMData, scenario_data: &ScenarioData) -> Option<Self> {
+        let start_rail = scenario_data.stops.first()?.node_id;
+
+        let id = data.rails.keys().find(|(s, _e)| *s == start_rail)?;
+
+        Some(Self {
+            id: *id,
+            distance: 0.0,
+            travel_direction: scenario_data.info.starting_direction,
+        })
+    }
+
+    // whether a contact line runs above the location
+    pub fn is_electrified(&self, data: &OSMData) -> bool {
+        data.rails
+            .get(&self.id)
+            .is_some_and(|rail| rail.tags.is_electrified())
+    }
+
+    // locations of the vehicles of a train with the given lengths, lined up
+    // behind this location
+    pub fn consist_locations(
+        &self,
+        data: &OSMData,
+        switches: &SwitchPositions,
+        lengths: &[f32],
+    ) -> Vec<Self> {
+        let mut location = self.clone();
+        let mut locations = vec![];
+
+        for (index, length) in lengths.iter().enumerate() {
+            let mut new_location = location.clone();
+            if index > 0 {
+                new_location.add_distance(data, switches, -*length as f64 / 2.0 - WAGON_DISTANCE);
+            }
+
+            locations.push(new_location.clone());
+
+            new_location.add_distance(data, switches, -*length as f64 / 2.0);
+            location = new_location;
+        }
+
+        locations
+    }
+
+    // follows the switches as they are set. track ends are buffer stops, the
+    // location stays at the end and reports the remaining distance
+    pub fn add_distance(
+        &mut self,
+        data: &OSMData,
+        switches: &SwitchPositions,
+        amount: f64,
+    ) -> TrackMovement {
+        let mut movement = TrackMovement::default();
         self.distance += amount;
 
         // when we add or subtract distance we might change id or travel direction
@@ -39,15 +111,34 @@ impl TrackLocation {
             // need to move to next rail segment
             if let Some(crossing_direction) = crossing_direction {
                 let old_travel_direction = self.travel_direction;
-                let possible = rail.possible_connections_by_direction(match crossing_direction {
+                let leaving_direction = match crossing_direction {
                     Direction::Forward => self.travel_direction,
                     Direction::Backward => self.travel_direction.opposite(),
-                });
-                let (next_id, next_direction) = possible
-                    .first()
-                    .expect("at least one possible followup location to exist");
+                };
+                let possible = rail.possible_connections_by_direction(leaving_direction);
+                let Some((next_id, next_direction)) = possible
+                    .get(switches.position(&(self.id, leaving_direction)))
+                    .or(possible.first())
+                else {
+                    let end = self.distance.clamp(0.0, length);
+                    movement.overrun = (self.distance - end).abs();
+                    self.distance = end;
+
+                    log::debug!("buffer stop at the end of {:?}", rail.id());
+                    break;
+                };
                 let next_rail = data.rails.get(next_id).expect("location to be valid");
 
+                // coming from a branch, the switch has to be set to it
+                let trailing_leg = (*next_id, next_direction.opposite());
+                let trailing = next_rail.possible_connections_by_direction(trailing_leg.1);
+                let branch = (self.id, leaving_direction.opposite());
+                if trailing.len() > 1
+                    && trailing.get(switches.position(&trailing_leg)) != Some(&branch)
+                {
+                    movement.trailed.push((trailing_leg, branch));
+                }
+
                 self.id = *next_id;
                 self.travel_direction = match crossing_direction {
                     Direction::Forward => *next_direction,
@@ -69,5 +160,108 @@ impl TrackLocation {
                 break;
             }
         }
+
+        movement
+    }
+
+    // the same spot seen when moving in `direction` relative to the travel
+    // direction
+    pub fn facing(&self, data: &OSMData, direction: Direction) -> Self {
+        match direction {
+            Direction::Forward => self.clone(),
+            Direction::Backward => Self {
+                id: self.id,
+                distance: data
+                    .rails
+                    .get(&self.id)
+                    .map_or(0.0, |rail| rail.length() - self.distance),
+                travel_direction: self.travel_direction.opposite(),
+            },
+        }
+    }
+
+    // the next facing switch within the given distance when moving in
+    // `direction` relative to the travel direction, along with the distance
+    // to it
+    pub fn next_switch(
+        &self,
+        data: &OSMData,
+        switches: &SwitchPositions,
+        direction: Direction,
+        max_distance: f64,
+    ) -> Option<(SwitchLeg, f64)> {
+        let rail = data.rails.get(&self.id)?;
+        let (mut leaving_direction, mut distance) = match direction {
+            Direction::Forward => (self.travel_direction, rail.length() - self.distance),
+            Direction::Backward => (self.travel_direction.opposite(), self.distance),
+        };
+        let mut id = self.id;
+
+        while distance <= max_distance {
+            let rail = data.rails.get(&id)?;
+            let possible = rail.possible_connections_by_direction(leaving_direction);
+
+            if possible.len() > 1 {
+                return Some(((id, leaving_direction), distance));
+            }
+
+            let (next_id, next_direction) = possible
+                .get(switches.position(&(id, leaving_direction)))
+                .or(possible.first())?;
+
+            id = *next_id;
+            leaving_direction = *next_direction;
+            distance += data.rails.get(&id)?.length();
+        }
+
+        None
+    }
+
+    // km/h line speeds when moving in `direction` relative to the travel
+    // direction, each with the distance to where it starts. the first entry
+    // is the limit at this location
+    pub fn speed_limits_ahead(
+        &self,
+        data: &OSMData,
+        switches: &SwitchPositions,
+        direction: Direction,
+        max_distance: f64,
+    ) -> Vec<(f64, f32)> {
+        let Some(rail) = data.rails.get(&self.id) else {
+            return vec![];
+        };
+        let (mut leaving_direction, mut distance) = match direction {
+            Direction::Forward => (self.travel_direction, rail.length() - self.distance),
+            Direction::Backward => (self.travel_direction.opposite(), self.distance),
+        };
+        let mut id = self.id;
+        let mut limits = vec![(0.0, rail.speed_limit())];
+
+        while distance <= max_distance {
+            let Some(rail) = data.rails.get(&id) else {
+                break;
+            };
+            let possible = rail.possible_connections_by_direction(leaving_direction);
+            let Some((next_id, next_direction)) = possible
+                .get(switches.position(&(id, leaving_direction)))
+                .or(possible.first())
+            else {
+                break;
+            };
+            let Some(next_rail) = data.rails.get(next_id) else {
+                break;
+            };
+
+            let limit = next_rail.speed_limit();
+            if limits.last().is_some_and(|(_, last)| *last != limit) {
+                limits.push((distance, limit));
+            }
+
+            id = *next_id;
+            leaving_direction = *next_direction;
+            distance += next_rail.length();
+        }
+
+        limits
     }
 }
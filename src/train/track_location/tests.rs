@@ -1,6 +1,7 @@
 use super::*;
 use crate::{
     landscape::{CoordinatePoint, Path},
+    scenario::{ScenarioInfo, ScenarioStop},
     train::Direction,
 };
 use coverage_helper::test;
@@ -44,7 +45,7 @@ fn updates_distance() {
         distance: 140.0,
     };
 
-    location.add_distance(&data, 10.0);
+    location.add_distance(&data, &SwitchPositions::default(), 10.0);
 
     {
         assert_eq!(location.id, (1, 2));
@@ -52,7 +53,7 @@ fn updates_distance() {
         assert_eq!(location.travel_direction, Direction::Forward);
     }
 
-    location.add_distance(&data, 10.0);
+    location.add_distance(&data, &SwitchPositions::default(), 10.0);
 
     {
         assert_eq!(location.id, (1, 2));
@@ -60,7 +61,7 @@ fn updates_distance() {
         assert_eq!(location.travel_direction, Direction::Forward);
     }
 
-    location.add_distance(&data, -20.0);
+    location.add_distance(&data, &SwitchPositions::default(), -20.0);
 
     {
         assert_eq!(location.id, (0, 1));
@@ -68,3 +69,259 @@ fn updates_distance() {
         assert_eq!(location.travel_direction, Direction::Forward);
     }
 }
+
+#[test]
+fn scenario_start() {
+    let data = gen_data();
+
+    let scenario_data = ScenarioData {
+        info: ScenarioInfo {
+            starting_direction: Direction::Backward,
+            ..default()
+        },
+        stops: vec![ScenarioStop {
+            node_id: 1,
+            ..default()
+        }],
+        ..default()
+    };
+
+    let location = TrackLocation::scenario_start(&data, &scenario_data).unwrap();
+
+    assert_eq!(location.id, (1, 2));
+    assert_eq!(location.distance, 0.0);
+    assert_eq!(location.travel_direction, Direction::Backward);
+
+    let off_track = ScenarioData {
+        stops: vec![ScenarioStop {
+            node_id: 42,
+            ..default()
+        }],
+        ..default()
+    };
+    assert!(TrackLocation::scenario_start(&data, &off_track).is_none());
+    assert!(TrackLocation::scenario_start(&data, &ScenarioData::default()).is_none());
+}
+
+#[test]
+fn consist_locations() {
+    let data = gen_data();
+
+    let location = TrackLocation {
+        id: (1, 2),
+        travel_direction: Direction::Forward,
+        distance: 50.0,
+    };
+
+    let locations =
+        location.consist_locations(&data, &SwitchPositions::default(), &[20.0, 10.0, 10.0]);
+
+    assert_eq!(locations.len(), 3);
+    assert_eq!(locations[0].distance, 50.0);
+    assert_eq!(locations[1].distance, 35.0);
+    assert_eq!(locations[2].distance, 25.0);
+    assert!(locations.iter().all(|location| location.id == (1, 2)));
+}
+
+#[coverage(off)]
+fn gen_turnout() -> OSMData {
+    let mut rails = HashMap::default();
+    rails.insert(
+        (0, 1),
+        Path {
+            start_id: 0,
+            end_id: 1,
+            start_coords: CoordinatePoint(0.0, 0.0),
+            end_coords: CoordinatePoint(100.0, 0.0),
+            forward_connections: vec![((1, 2), Direction::Forward), ((1, 3), Direction::Forward)],
+            ..default()
+        },
+    );
+    for (id, end) in [((1, 2), (200.0, 0.0)), ((1, 3), (200.0, 20.0))] {
+        rails.insert(
+            id,
+            Path {
+                start_id: id.0,
+                end_id: id.1,
+                start_coords: CoordinatePoint(100.0, 0.0),
+                end_coords: CoordinatePoint(end.0, end.1),
+                backward_connections: vec![((0, 1), Direction::Backward)],
+                ..default()
+            },
+        );
+    }
+    OSMData { rails, ..default() }
+}
+
+#[test]
+fn follows_switch_position() {
+    let data = gen_turnout();
+    let location = TrackLocation {
+        id: (0, 1),
+        travel_direction: Direction::Forward,
+        distance: 90.0,
+    };
+
+    let mut straight = location.clone();
+    straight.add_distance(&data, &SwitchPositions::default(), 20.0);
+    assert_eq!(straight.id, (1, 2));
+
+    let mut switches = SwitchPositions::default();
+    switches.0.insert(((0, 1), Direction::Forward), 1);
+
+    let mut diverging = location.clone();
+    let trailed = diverging.add_distance(&data, &switches, 20.0).trailed;
+    assert_eq!(diverging.id, (1, 3));
+    assert!(trailed.is_empty());
+}
+
+#[test]
+fn detects_trailing() {
+    let data = gen_turnout();
+    let location = TrackLocation {
+        id: (1, 3),
+        travel_direction: Direction::Forward,
+        distance: 5.0,
+    };
+
+    let mut against = location.clone();
+    let trailed = against
+        .add_distance(&data, &SwitchPositions::default(), -10.0)
+        .trailed;
+    assert_eq!(against.id, (0, 1));
+    assert_eq!(against.travel_direction, Direction::Forward);
+    assert_eq!(against.distance.round(), 95.0);
+    assert_eq!(
+        trailed,
+        vec![(((0, 1), Direction::Forward), ((1, 3), Direction::Forward))]
+    );
+
+    let mut switches = SwitchPositions::default();
+    switches.0.insert(((0, 1), Direction::Forward), 1);
+
+    let mut set = location.clone();
+    assert!(set.add_distance(&data, &switches, -10.0).trailed.is_empty());
+}
+
+#[test]
+fn finds_next_switch() {
+    let data = gen_turnout();
+    let switches = SwitchPositions::default();
+
+    let location = TrackLocation {
+        id: (0, 1),
+        travel_direction: Direction::Forward,
+        distance: 10.0,
+    };
+    assert_eq!(
+        location.next_switch(&data, &switches, Direction::Forward, 500.0),
+        Some((((0, 1), Direction::Forward), 90.0))
+    );
+    assert_eq!(
+        location.next_switch(&data, &switches, Direction::Forward, 50.0),
+        None
+    );
+    assert_eq!(
+        location.next_switch(&data, &switches, Direction::Backward, 500.0),
+        None
+    );
+
+    let location = TrackLocation {
+        id: (1, 2),
+        travel_direction: Direction::Backward,
+        distance: 30.0,
+    };
+    assert_eq!(
+        location.next_switch(&data, &switches, Direction::Forward, 500.0),
+        None
+    );
+    assert_eq!(
+        location.next_switch(&data, &switches, Direction::Backward, 500.0),
+        None
+    );
+}
+
+#[test]
+fn stops_at_buffer_stops() {
+    let data = gen_data();
+    let switches = SwitchPositions::default();
+
+    let mut location = TrackLocation {
+        id: (1, 2),
+        travel_direction: Direction::Forward,
+        distance: 190.0,
+    };
+
+    let movement = location.add_distance(&data, &switches, 30.0);
+    assert_eq!(location.id, (1, 2));
+    assert_eq!(location.distance, 200.0);
+    assert_eq!(movement.overrun, 20.0);
+
+    let mut location = TrackLocation {
+        id: (0, 1),
+        travel_direction: Direction::Forward,
+        distance: 10.0,
+    };
+
+    let movement = location.add_distance(&data, &switches, -25.0);
+    assert_eq!(location.id, (0, 1));
+    assert_eq!(location.distance, 0.0);
+    assert_eq!(movement.overrun, 15.0);
+
+    let movement = location.add_distance(&data, &switches, 5.0);
+    assert_eq!(movement, TrackMovement::default());
+}
+
+#[test]
+fn finds_speed_limits_ahead() {
+    let mut data = gen_data();
+    data.rails.get_mut(&(1, 2)).unwrap().tags.max_speed = Some(60.0);
+    let switches = SwitchPositions::default();
+
+    let location = TrackLocation {
+        id: (0, 1),
+        distance: 50.0,
+        travel_direction: Direction::Forward,
+    };
+
+    let limits = location.speed_limits_ahead(&data, &switches, Direction::Forward, 1000.0);
+    assert_eq!(limits.len(), 2);
+    assert_eq!(limits[0], (0.0, 100.0));
+    assert!((limits[1].0 - (f64::sqrt(20000.0) - 50.0)).abs() < 1e-9);
+    assert_eq!(limits[1].1, 60.0);
+
+    // out of sight
+    let limits = location.speed_limits_ahead(&data, &switches, Direction::Forward, 10.0);
+    assert_eq!(limits, vec![(0.0, 100.0)]);
+
+    // nothing behind the start of the line
+    let limits = location.speed_limits_ahead(&data, &switches, Direction::Backward, 1000.0);
+    assert_eq!(limits, vec![(0.0, 100.0)]);
+
+    let nowhere = TrackLocation {
+        id: (7, 8),
+        ..location
+    };
+    assert!(nowhere
+        .speed_limits_ahead(&data, &switches, Direction::Forward, 1000.0)
+        .is_empty());
+}
+
+#[test]
+fn faces_either_way() {
+    let data = gen_data();
+    let location = TrackLocation {
+        id: (1, 2),
+        distance: 50.0,
+        travel_direction: Direction::Forward,
+    };
+
+    let forward = location.facing(&data, Direction::Forward);
+    assert_eq!(forward.distance, 50.0);
+    assert_eq!(forward.travel_direction, Direction::Forward);
+
+    let backward = location.facing(&data, Direction::Backward);
+    assert_eq!(backward.id, (1, 2));
+    assert_eq!(backward.distance, 150.0);
+    assert_eq!(backward.travel_direction, Direction::Backward);
+}
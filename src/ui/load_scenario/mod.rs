@@ -1,7 +1,10 @@
 #[cfg(test)]
 mod tests;
 
-use crate::scenario::ScenarioData;
+use crate::{
+    landscape::Route,
+    scenario::{ScenarioData, ScenarioStop},
+};
 use bevy::{prelude::*, window::PrimaryWindow};
 use bevy_egui::{egui, EguiContexts};
 use glob::glob;
@@ -48,6 +51,8 @@ fn load_scenario(
                         let mut window = window.single_mut();
                         window.title = format!("rustrail - {}", scenario_data.info.name);
 
+                        commands.insert_resource(scenario_data.info.rail_condition);
+                        commands.insert_resource(scenario_data.info.vigilance);
                         commands.insert_resource(scenario_data);
                     }
                 }
@@ -55,13 +60,50 @@ fn load_scenario(
         );
 }
 
+fn route_problem_texts(route: &Route, stops: &[ScenarioStop]) -> Vec<String> {
+    route
+        .problems
+        .iter()
+        .filter_map(|(index, problem)| {
+            let stop = stops.get(*index)?;
+            Some(format!("Stop {} {}", stop.name, problem))
+        })
+        .collect()
+}
+
+#[coverage(off)]
+fn route_problems(mut contexts: EguiContexts, route: Res<Route>, scenario_data: Res<ScenarioData>) {
+    let texts = route_problem_texts(&route, &scenario_data.stops);
+    if texts.is_empty() {
+        return;
+    }
+
+    egui::Window::new("Route Problems")
+        .anchor(egui::Align2::CENTER_TOP, (0.0, 10.0))
+        .collapsible(true)
+        .resizable(false)
+        .show(
+            contexts.ctx_mut(),
+            #[coverage(off)]
+            |ui| {
+                for text in texts {
+                    ui.colored_label(egui::Color32::RED, text);
+                }
+            },
+        );
+}
+
 pub struct LoadScenarioPlugin;
 
 impl Plugin for LoadScenarioPlugin {
     fn build(&self, app: &mut App) {
         app.add_systems(
             Update,
-            load_scenario.run_if(not(resource_exists::<ScenarioData>)),
+            (
+                load_scenario.run_if(not(resource_exists::<ScenarioData>)),
+                route_problems
+                    .run_if(resource_exists::<Route>.and_then(resource_exists::<ScenarioData>)),
+            ),
         );
     }
 }
@@ -1,4 +1,5 @@
 use super::*;
+use crate::landscape::StopProblem;
 use coverage_helper::test;
 
 #[test]
@@ -7,3 +8,30 @@ fn plugin() {
     app.add_plugins(LoadScenarioPlugin);
     assert!(app.is_plugin_added::<LoadScenarioPlugin>());
 }
+
+#[test]
+fn route_problems_list_the_stops() {
+    let stops = vec![
+        ScenarioStop {
+            name: "Start".to_string(),
+            node_id: 1,
+        },
+        ScenarioStop {
+            name: "End".to_string(),
+            node_id: 2,
+        },
+    ];
+    let route = Route {
+        problems: vec![(0, StopProblem::NotOnTrack), (1, StopProblem::Unreachable)],
+        ..default()
+    };
+
+    assert_eq!(
+        route_problem_texts(&route, &stops),
+        vec![
+            "Stop Start is not on the track".to_string(),
+            "Stop End is unreachable".to_string(),
+        ]
+    );
+    assert!(route_problem_texts(&Route::default(), &stops).is_empty());
+}
@@ -1,12 +1,21 @@
 #[cfg(test)]
 mod tests;
 
-use bevy::prelude::*;
+use bevy::{ecs::system::SystemParam, prelude::*};
 use bevy_egui::{egui, EguiContexts};
 
 use crate::{
     camera,
-    train::{AirPressure, BrakeLever, Mass, Name, Speed, ThrottleLever},
+    landscape::{OSMData, Switch, SwitchPositions},
+    scenario::{ScenarioData, ScenarioStop},
+    train::{
+        supplies_for, AirPressure, Boiler, BrakeCylinder, BrakeIntervention, BrakeLever,
+        BrakeValvePosition, CabSignalling, DieselEngine, DieselState, Direction, DynamicBrake,
+        EnergyMeter, Lamp, LoadChange, Mass, MaxSpeed, Name, Overspeed, Payload, Pzb, Sanding,
+        Sifa, SifaStage, Speed, SpeedLimit, StationStops, SteamControls, SteamEngine,
+        SupervisionStatus, Supplies, ThrottleLever, TrackLocation, TrainComposition, WheelSlip,
+        MAX_CUT_OFF, STANDSTILL,
+    },
 };
 
 type TrainControlQuery<'a> = (
@@ -15,19 +24,171 @@ type TrainControlQuery<'a> = (
     &'a Speed,
     &'a Mass,
     &'a AirPressure,
+    &'a BrakeCylinder,
+    &'a WheelSlip,
+    &'a DynamicBrake,
+    &'a EnergyMeter,
+    (&'a SteamEngine, &'a mut SteamControls, &'a Boiler),
+    (&'a DieselEngine, &'a DieselState),
     &'a mut ThrottleLever,
     &'a mut BrakeLever,
+    &'a mut Sanding,
 );
 
+// what the driver sees of the track in front of the train
+#[derive(SystemParam)]
+struct TrackAhead<'w, 's> {
+    locations: Query<'w, 's, &'static TrackLocation>,
+    switches: Query<'w, 's, &'static mut Switch>,
+    switch_positions: Res<'w, SwitchPositions>,
+    data: Option<Res<'w, OSMData>>,
+    scenario_data: Option<Res<'w, ScenarioData>>,
+}
+
+// train protection in the cab
+#[derive(SystemParam)]
+struct Protection<'w, 's> {
+    pzbs: Query<'w, 's, &'static mut Pzb>,
+    sifas: Query<'w, 's, &'static mut Sifa>,
+    cab_signals: Query<
+        'w,
+        's,
+        (
+            &'static TrainComposition,
+            &'static MaxSpeed,
+            &'static mut CabSignalling,
+        ),
+    >,
+}
+
+// the freight or passengers of the train's wagons
+#[derive(SystemParam)]
+struct Loading<'w, 's> {
+    payloads: Query<'w, 's, &'static Payload>,
+    load_changes: EventWriter<'w, LoadChange>,
+}
+
+type MeterReading = fn(&EnergyMeter) -> f32;
+
+type CompositionQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        &'static TrainComposition,
+        &'static EnergyMeter,
+        &'static SpeedLimit,
+        &'static Overspeed,
+        &'static StationStops,
+    ),
+>;
+
 const MAX_SPEED_WHEN_REVERSING: f32 = 8.0 /* km/h */ / 3.6;
+// km/h, scales of the cab signalling speed dial
+const DIAL_RANGES: [f32; 4] = [140.0, 180.0, 250.0, 400.0];
+// degrees the dial sweeps from 0 to its full scale
+const DIAL_SWEEP: f32 = 288.0;
+const DIAL_ARC_STEPS: usize = 32;
+// m, the target distance bar fills up from here
+const TARGET_BAR_DISTANCE: f64 = 1000.0;
+// m, how far ahead of the train switches can be thrown
+const SWITCH_LOOKAHEAD: f64 = 1000.0;
+// Hz, tone of the Sifa buzzer
+const BUZZER_FREQUENCY: f32 = 800.0;
+
+// the sound of a Sifa in its audible warning stage
+#[derive(Component)]
+struct SifaBuzzer;
+
+fn brake_valve_input(keyboard_input: &ButtonInput<KeyCode>, brake_lever: &mut BrakeLever) {
+    if keyboard_input.just_released(KeyCode::Space) {
+        brake_lever.valve = BrakeValvePosition::Emergency;
+    } else if keyboard_input.just_released(KeyCode::KeyB) {
+        brake_lever.valve = brake_lever.valve.next();
+    } else if keyboard_input.just_released(KeyCode::KeyV) {
+        brake_lever.valve = brake_lever.valve.previous();
+    }
+}
+
+fn next_stop_text(station_stops: &StationStops, stops: &[ScenarioStop]) -> Option<String> {
+    let stop = stops.get(station_stops.next)?;
+
+    Some(if station_stops.is_calling() {
+        format!("At {}", stop.name)
+    } else if let Some(distance) = station_stops.distance {
+        format!("Next stop {} in {:.0} m", stop.name, distance)
+    } else {
+        format!("Next stop {}", stop.name)
+    })
+}
+
+// the smallest scale that fits the train
+fn dial_range(max_speed_kmh: f32) -> f32 {
+    DIAL_RANGES
+        .into_iter()
+        .find(|range| *range >= max_speed_kmh)
+        .unwrap_or(DIAL_RANGES[DIAL_RANGES.len() - 1])
+}
+
+// radians clockwise from straight up, the dial is open at the bottom
+fn dial_angle(speed_kmh: f32, range: f32) -> f32 {
+    ((speed_kmh / range).clamp(0.0, 1.0) - 0.5) * DIAL_SWEEP.to_radians()
+}
+
+// share of the payload capacity of all wagons that is loaded, none for a
+// train that carries nothing
+fn load_share<'a>(payloads: impl Iterator<Item = &'a Payload>) -> Option<f32> {
+    let (load, capacity) = payloads.fold((0.0, 0.0), |(load, capacity), payload| {
+        (load + payload.load, capacity + payload.capacity)
+    });
+
+    (capacity > 0.0).then(|| load / capacity)
+}
+
+fn pzb_input(keyboard_input: &ButtonInput<KeyCode>, pzb: &mut Pzb) {
+    if keyboard_input.just_released(KeyCode::KeyQ) {
+        pzb.acknowledge = true;
+    }
+    if keyboard_input.just_released(KeyCode::KeyR) {
+        pzb.release = true;
+    }
+}
+
+fn sifa_input(keyboard_input: &ButtonInput<KeyCode>, sifa: &mut Sifa) {
+    if keyboard_input.just_released(KeyCode::KeyF) {
+        sifa.acknowledge = true;
+    }
+}
 
 #[coverage(off)]
 fn train_controls(
     mut selected_engine: Local<Option<Entity>>,
     mut trains: Query<TrainControlQuery>,
     mut contexts: EguiContexts,
-    mut camera: Query<&mut camera::GameCameraState>,
+    (mut camera, keyboard_input): (
+        Query<&mut camera::GameCameraState>,
+        Res<ButtonInput<KeyCode>>,
+    ),
+    (compositions, supplies, loading): (CompositionQuery, Query<&Supplies>, Loading),
+    track: TrackAhead,
+    protection: Protection,
 ) {
+    let TrackAhead {
+        locations,
+        mut switches,
+        switch_positions,
+        data,
+        scenario_data,
+    } = track;
+    let Protection {
+        mut pzbs,
+        mut sifas,
+        mut cab_signals,
+    } = protection;
+    let Loading {
+        payloads,
+        mut load_changes,
+    } = loading;
+
     if trains.is_empty() {
         return;
     }
@@ -44,9 +205,106 @@ fn train_controls(
             options.push((train.0, train.1 .0.to_owned()));
         }
 
-        if let Ok((entity, name, speed, mass, air_pressure, mut throttle_lever, mut brake_lever)) =
-            trains.get_mut(entity)
+        if let Ok((
+            entity,
+            name,
+            speed,
+            mass,
+            air_pressure,
+            brake_cylinder,
+            wheel_slip,
+            dynamic_brake,
+            energy_meter,
+            (steam_engine, mut steam_controls, boiler),
+            (diesel_engine, diesel_state),
+            mut throttle_lever,
+            mut brake_lever,
+            mut sanding,
+        )) = trains.get_mut(entity)
         {
+            brake_valve_input(&keyboard_input, &mut brake_lever);
+            let mut pzb = pzbs.get_mut(entity).ok();
+            if let Some(pzb) = pzb.as_mut() {
+                pzb_input(&keyboard_input, pzb);
+            }
+            let mut sifa = sifas.get_mut(entity).ok();
+            if let Some(sifa) = sifa.as_mut() {
+                sifa_input(&keyboard_input, sifa);
+            }
+
+            let train = compositions.iter().find(
+                #[coverage(off)]
+                |(composition, ..)| composition.entities().contains(&entity),
+            );
+            let train_energy_meter = train.map(
+                #[coverage(off)]
+                |(_, energy_meter, ..)| energy_meter,
+            );
+            let speed_supervision = train.map(
+                #[coverage(off)]
+                |(_, _, speed_limit, overspeed, _)| (speed_limit, overspeed),
+            );
+            let next_stop = train.zip(scenario_data.as_ref()).and_then(
+                #[coverage(off)]
+                |((.., station_stops), scenario_data)| {
+                    next_stop_text(station_stops, &scenario_data.stops)
+                },
+            );
+
+            // looks out from the vehicle leading in the selected direction
+            let switch_ahead = train.zip(data.as_ref()).and_then(
+                #[coverage(off)]
+                |((composition, ..), data)| {
+                    let entities = composition.entities();
+                    let leading = match throttle_lever.direction {
+                        Direction::Forward => entities.first(),
+                        Direction::Backward => entities.last(),
+                    }?;
+
+                    locations.get(*leading).ok()?.next_switch(
+                        data,
+                        &switch_positions,
+                        throttle_lever.direction,
+                        SWITCH_LOOKAHEAD,
+                    )
+                },
+            );
+
+            let wagons: Vec<Entity> = train
+                .map(
+                    #[coverage(off)]
+                    |(composition, ..)| {
+                        composition
+                            .entities()
+                            .into_iter()
+                            .filter(
+                                #[coverage(off)]
+                                |entity| payloads.contains(*entity),
+                            )
+                            .collect()
+                    },
+                )
+                .unwrap_or_default();
+            let mut share = load_share(payloads.iter_many(&wagons));
+
+            let engine_supplies = compositions
+                .iter()
+                .find_map(
+                    #[coverage(off)]
+                    |(composition, ..)| {
+                        supplies_for(
+                            &composition.entities(),
+                            entity,
+                            #[coverage(off)]
+                            |entity| supplies.get(entity).is_ok_and(|s| !s.is_empty()),
+                        )
+                    },
+                )
+                .and_then(
+                    #[coverage(off)]
+                    |entity| supplies.get(entity).ok(),
+                );
+
             egui::TopBottomPanel::bottom("info").show(
                 contexts.ctx_mut(),
                 #[coverage(off)]
@@ -73,21 +331,114 @@ fn train_controls(
                             }
 
                             ui.label(format!("{:.2} km/h", speed.as_kmh()));
+                            if let Some((speed_limit, overspeed)) = speed_supervision {
+                                speed_limit_labels(ui, speed.0, speed_limit, overspeed);
+                            }
+                            if let Some(next_stop) = &next_stop {
+                                ui.separator();
+                                ui.label(next_stop);
+                            }
                             ui.separator();
-                            ui.label(format!(
-                                "Throttle: {:.0}%",
-                                throttle_lever.percentage * 100.0
-                            ));
-                            ui.add(
-                                egui::Slider::new(&mut throttle_lever.percentage, 0.0..=1.0)
+                            if steam_engine.is_available() {
+                                ui.label(format!(
+                                    "Regulator: {:.0}%",
+                                    steam_controls.regulator * 100.0
+                                ));
+                                ui.add(
+                                    egui::Slider::new(&mut steam_controls.regulator, 0.0..=1.0)
+                                        .show_value(false),
+                                );
+                                ui.label(format!(
+                                    "Cut-off: {:.0}%",
+                                    steam_controls.cut_off * 100.0
+                                ));
+                                ui.add(
+                                    egui::Slider::new(
+                                        &mut steam_controls.cut_off,
+                                        0.0..=MAX_CUT_OFF,
+                                    )
                                     .show_value(false),
-                            );
+                                );
+                                ui.label(format!(
+                                    "Firing: {:.0}%",
+                                    steam_controls.firing_rate * 100.0
+                                ));
+                                ui.add(
+                                    egui::Slider::new(&mut steam_controls.firing_rate, 0.0..=1.0)
+                                        .show_value(false),
+                                );
+                                ui.label(format!("Boiler {:.1} bar", boiler.pressure));
+                                match engine_supplies {
+                                    Some(supplies) => {
+                                        ui.label(format!(
+                                            "Coal {:.2} t, Water {:.1} m³",
+                                            supplies.coal / 1000.0,
+                                            supplies.water / 1000.0
+                                        ));
+                                    }
+                                    None => {
+                                        ui.colored_label(egui::Color32::RED, "No coal or water");
+                                    }
+                                }
+                            } else if throttle_lever.notches > 0 {
+                                ui.label(format!(
+                                    "Notch: {}/{}",
+                                    throttle_lever.notch(),
+                                    throttle_lever.notches
+                                ));
+                                let step = 1.0 / throttle_lever.notches as f64;
+                                ui.add(
+                                    egui::Slider::new(&mut throttle_lever.percentage, 0.0..=1.0)
+                                        .step_by(step)
+                                        .show_value(false),
+                                );
+                            } else {
+                                ui.label(format!(
+                                    "Throttle: {:.0}%",
+                                    throttle_lever.percentage * 100.0
+                                ));
+                                ui.add(
+                                    egui::Slider::new(&mut throttle_lever.percentage, 0.0..=1.0)
+                                        .show_value(false),
+                                );
+                            }
+                            if diesel_engine.is_available() {
+                                ui.label(format!("{:.0} rpm", diesel_state.rpm));
+                                if diesel_engine.transmission.stages.len() > 1 {
+                                    ui.label(format!("Stage {}", diesel_state.stage + 1));
+                                }
+                                ui.label(format!("Fuel {:.0} l", diesel_state.fuel));
+                            }
                             ui.separator();
-                            ui.label(format!("Brake: {:.0}%", brake_lever.release_valve * 100.0));
-                            ui.add(
-                                egui::Slider::new(&mut brake_lever.release_valve, 0.0..=1.0)
-                                    .show_value(false),
-                            );
+                            if ui.selectable_label(sanding.0, "Sand").clicked() {
+                                sanding.0 = !sanding.0;
+                            }
+                            match wheel_slip {
+                                WheelSlip::Gripping => {}
+                                WheelSlip::Slipping => {
+                                    ui.colored_label(egui::Color32::RED, "Wheel slip");
+                                }
+                                WheelSlip::Sliding => {
+                                    ui.colored_label(egui::Color32::RED, "Wheel slide");
+                                }
+                            }
+                            ui.separator();
+                            ui.label("Brake:");
+                            egui::ComboBox::from_id_source("brake_valve")
+                                .selected_text(brake_lever.valve.to_string())
+                                .show_ui(
+                                    ui,
+                                    #[coverage(off)]
+                                    |ui| {
+                                        for position in BrakeValvePosition::all() {
+                                            ui.selectable_value(
+                                                &mut brake_lever.valve,
+                                                position,
+                                                position.to_string(),
+                                            );
+                                        }
+                                    },
+                                );
                             ui.separator();
                             ui.label(format!(
                                 "Engine Brake: {:.0}%",
@@ -97,13 +448,32 @@ fn train_controls(
                                 egui::Slider::new(&mut brake_lever.engine_brake, 0.0..=1.0)
                                     .show_value(false),
                             );
+                            if dynamic_brake.is_available() {
+                                ui.separator();
+                                ui.label(format!(
+                                    "Dynamic Brake: {:.0}%",
+                                    brake_lever.dynamic_brake * 100.0
+                                ));
+                                ui.add(
+                                    egui::Slider::new(&mut brake_lever.dynamic_brake, 0.0..=1.0)
+                                        .show_value(false),
+                                );
+                            }
                             ui.separator();
                             ui.label(format!("{:.2} t", mass.0 / 1000.0));
                             ui.separator();
-                            ui.label(format!("{:.2} bar", air_pressure.0));
+                            ui.label(format!("BP {:.2} bar", air_pressure.0));
+                            ui.label(format!("BC {:.2} bar", brake_cylinder.0));
+                            if brake_lever
+                                .interventions
+                                .contains(&BrakeIntervention::Overspeed)
+                            {
+                                ui.colored_label(egui::Color32::RED, "Penalty brake");
+                            }
                             ui.separator();
                             let can_change_direction = speed.0.abs() < MAX_SPEED_WHEN_REVERSING
-                                && throttle_lever.percentage == 0.0;
+                                && throttle_lever.percentage == 0.0
+                                && steam_controls.regulator == 0.0;
                             if ui
                                 .small_button(format!("{:?}", throttle_lever.direction))
                                 .clicked()
@@ -112,6 +482,29 @@ fn train_controls(
                                 throttle_lever.direction = throttle_lever.direction.opposite();
                             }
 
+                            if let Some((leg, distance)) = switch_ahead {
+                                if let Some(mut switch) = switches.iter_mut().find(
+                                    #[coverage(off)]
+                                    |switch| switch.leg == leg,
+                                ) {
+                                    ui.separator();
+                                    ui.label(format!(
+                                        "Switch in {:.0} m: {}/{}",
+                                        distance,
+                                        switch.position + 1,
+                                        switch.branches.len()
+                                    ));
+                                    // switches under a train stay where they are
+                                    let occupied = switch.is_occupied(locations.iter());
+                                    if ui
+                                        .add_enabled(!occupied, egui::Button::new("Throw").small())
+                                        .clicked()
+                                    {
+                                        switch.throw(locations.iter());
+                                    }
+                                }
+                            }
+
                             if ui.small_button("Follow").clicked() {
                                 let mut camera = camera.single_mut();
                                 camera.follow = Some(entity);
@@ -120,16 +513,416 @@ fn train_controls(
                     );
                 },
             );
+
+            let cab_signalling = cab_signals.iter_mut().find(
+                #[coverage(off)]
+                |(composition, ..)| composition.entities().contains(&entity),
+            );
+            if let Some((_, max_speed, mut cab_signalling)) = cab_signalling {
+                egui::Window::new("Cab signalling").show(
+                    contexts.ctx_mut(),
+                    #[coverage(off)]
+                    |ui| {
+                        cab_signalling_panel(ui, speed.0, max_speed.0, &mut cab_signalling);
+                    },
+                );
+            }
+
+            if let Some(mut pzb) = pzb {
+                egui::Window::new("PZB").show(
+                    contexts.ctx_mut(),
+                    #[coverage(off)]
+                    |ui| {
+                        pzb_panel(ui, &mut pzb);
+                    },
+                );
+            }
+
+            if let Some(mut sifa) = sifa {
+                egui::Window::new("Sifa").show(
+                    contexts.ctx_mut(),
+                    #[coverage(off)]
+                    |ui| {
+                        sifa_panel(ui, &mut sifa);
+                    },
+                );
+            }
+
+            // wagons only get loaded or unloaded while the train stands
+            if let Some(share) = share.as_mut() {
+                let standing = speed.0.abs() < STANDSTILL;
+                let mut changed = false;
+                egui::Window::new("Load").default_open(false).show(
+                    contexts.ctx_mut(),
+                    #[coverage(off)]
+                    |ui| {
+                        changed = load_panel(ui, share, standing);
+                    },
+                );
+                if changed {
+                    load_changes.send_batch(wagons.iter().map(
+                        #[coverage(off)]
+                        |wagon| LoadChange {
+                            wagon: *wagon,
+                            share: *share,
+                        },
+                    ));
+                }
+            }
+
+            egui::Window::new("Energy").default_open(false).show(
+                contexts.ctx_mut(),
+                #[coverage(off)]
+                |ui| {
+                    energy_grid(ui, energy_meter, train_energy_meter);
+                },
+            );
         } else {
             *selected_engine = None;
         }
     }
 }
 
+#[coverage(off)]
+fn speed_limit_labels(
+    ui: &mut egui::Ui,
+    speed: f32,
+    speed_limit: &SpeedLimit,
+    overspeed: &Overspeed,
+) {
+    let Some(current) = speed_limit.current else {
+        return;
+    };
+
+    let text = format!("Limit {:.0} km/h", current * 3.6);
+    if speed.abs() > current {
+        ui.colored_label(egui::Color32::RED, text);
+    } else {
+        ui.label(text);
+    }
+
+    if let Some((next, distance)) = speed_limit.next {
+        ui.label(format!("{:.0} km/h in {:.0} m", next * 3.6, distance));
+    }
+
+    if !overspeed.violations.is_empty() {
+        ui.label(format!("Violations: {}", overspeed.violations.len()));
+    }
+}
+
+#[coverage(off)]
+fn sifa_panel(ui: &mut egui::Ui, sifa: &mut Sifa) {
+    let stage = sifa.stage();
+    let color = match stage {
+        SifaStage::Monitoring => egui::Color32::DARK_GRAY,
+        SifaStage::VisualWarning | SifaStage::AudibleWarning => egui::Color32::WHITE,
+        SifaStage::Emergency => egui::Color32::RED,
+    };
+
+    ui.horizontal(
+        #[coverage(off)]
+        |ui| {
+            ui.colored_label(color, "Sifa");
+            match stage {
+                SifaStage::Monitoring => {}
+                SifaStage::VisualWarning => {
+                    ui.label(format!("{:.0} s", sifa.time_left()));
+                }
+                SifaStage::AudibleWarning => {
+                    ui.colored_label(
+                        egui::Color32::YELLOW,
+                        format!("Buzzer, braking in {:.0} s", sifa.time_left()),
+                    );
+                }
+                SifaStage::Emergency => {
+                    ui.colored_label(egui::Color32::RED, "Emergency brake");
+                }
+            }
+        },
+    );
+    if ui.button("Acknowledge (F)").clicked() {
+        sifa.acknowledge = true;
+    }
+}
+
+#[coverage(off)]
+fn pzb_panel(ui: &mut egui::Ui, pzb: &mut Pzb) {
+    let lamps = pzb.lamps();
+    let blink = (ui.input(
+        #[coverage(off)]
+        |input| input.time,
+    ) * 2.0) as i64
+        % 2
+        == 0;
+    // restrictive mode flashes 70 and 85 alternately
+    let alternate = lamps.hz70 == Lamp::Flashing;
+
+    ui.horizontal(
+        #[coverage(off)]
+        |ui| {
+            let blue = egui::Color32::from_rgb(40, 90, 255);
+            for (label, lamp, color, inverted) in [
+                ("85", lamps.hz85, blue, false),
+                ("70", lamps.hz70, blue, alternate),
+                ("1000 Hz", lamps.hz1000, egui::Color32::YELLOW, false),
+                ("500 Hz", lamps.hz500, egui::Color32::RED, false),
+            ] {
+                let is_lit = match lamp {
+                    Lamp::Off => false,
+                    Lamp::On => true,
+                    Lamp::Flashing => blink != inverted,
+                };
+                let color = if is_lit {
+                    color
+                } else {
+                    egui::Color32::DARK_GRAY
+                };
+                ui.colored_label(color, label);
+            }
+        },
+    );
+    ui.horizontal(
+        #[coverage(off)]
+        |ui| {
+            if ui.button("Acknowledge (Q)").clicked() {
+                pzb.acknowledge = true;
+            }
+            if ui.button("Release (R)").clicked() {
+                pzb.release = true;
+            }
+        },
+    );
+    if pzb.emergency {
+        ui.colored_label(egui::Color32::RED, "Emergency brake");
+    }
+}
+
+// whether the driver changed the share
+#[coverage(off)]
+fn load_panel(ui: &mut egui::Ui, share: &mut f32, standing: bool) -> bool {
+    ui.label(format!("Loaded: {:.0}%", *share * 100.0));
+    let changed = ui
+        .add_enabled(
+            standing,
+            egui::Slider::new(share, 0.0..=1.0).show_value(false),
+        )
+        .changed();
+    if !standing {
+        ui.label("Stop the train to load or unload it");
+    }
+
+    changed
+}
+
+#[coverage(off)]
+fn dial_point(center: egui::Pos2, radius: f32, angle: f32) -> egui::Pos2 {
+    center + radius * egui::vec2(angle.sin(), -angle.cos())
+}
+
+#[coverage(off)]
+fn cab_signalling_panel(
+    ui: &mut egui::Ui,
+    speed: f32,
+    max_speed: f32,
+    cab_signalling: &mut CabSignalling,
+) {
+    ui.checkbox(&mut cab_signalling.active, "Active");
+    if !cab_signalling.active {
+        return;
+    }
+
+    let range = dial_range(max_speed * 3.6);
+    let (response, painter) = ui.allocate_painter(egui::vec2(200.0, 200.0), egui::Sense::hover());
+    let center = response.rect.center();
+    let radius = response.rect.width() / 2.0 - 10.0;
+    let at = |kmh: f32, radius: f32| dial_point(center, radius, dial_angle(kmh, range));
+    let arc = |from: f32, to: f32, color: egui::Color32| {
+        let points = (0..=DIAL_ARC_STEPS)
+            .map(
+                #[coverage(off)]
+                |step| {
+                    at(
+                        from + (to - from) * step as f32 / DIAL_ARC_STEPS as f32,
+                        radius,
+                    )
+                },
+            )
+            .collect();
+        painter.add(egui::Shape::line(points, egui::Stroke::new(6.0, color)));
+    };
+
+    painter.circle_filled(center, radius + 8.0, egui::Color32::from_gray(30));
+
+    // the permitted speed around the rim, yellow down to the target
+    let permitted = cab_signalling.permitted * 3.6;
+    arc(0.0, permitted, egui::Color32::GRAY);
+    if let Some(target) = cab_signalling.target {
+        let target_speed = target.speed * 3.6;
+        if target_speed < permitted {
+            arc(target_speed, permitted, egui::Color32::YELLOW);
+        }
+    }
+
+    let step = if range <= 180.0 { 20 } else { 50 };
+    for kmh in (0..=range as u32).step_by(step / 2) {
+        let is_major = kmh as usize % step == 0;
+        let inner = if is_major {
+            radius - 12.0
+        } else {
+            radius - 6.0
+        };
+        painter.line_segment(
+            [at(kmh as f32, inner), at(kmh as f32, radius - 2.0)],
+            egui::Stroke::new(1.5, egui::Color32::WHITE),
+        );
+        if is_major {
+            painter.text(
+                at(kmh as f32, radius - 24.0),
+                egui::Align2::CENTER_CENTER,
+                kmh.to_string(),
+                egui::FontId::proportional(11.0),
+                egui::Color32::WHITE,
+            );
+        }
+    }
+
+    let needle = match cab_signalling.status {
+        SupervisionStatus::Normal => egui::Color32::LIGHT_GRAY,
+        SupervisionStatus::Overspeed | SupervisionStatus::Warning => {
+            egui::Color32::from_rgb(255, 150, 0)
+        }
+        SupervisionStatus::Intervention => egui::Color32::RED,
+    };
+    let speed_kmh = speed.abs() * 3.6;
+    painter.line_segment(
+        [center, at(speed_kmh, radius - 8.0)],
+        egui::Stroke::new(3.0, needle),
+    );
+    painter.circle_filled(center, 18.0, needle);
+    painter.text(
+        center,
+        egui::Align2::CENTER_CENTER,
+        format!("{:.0}", speed_kmh),
+        egui::FontId::proportional(14.0),
+        egui::Color32::BLACK,
+    );
+
+    match cab_signalling.target {
+        Some(target) => {
+            ui.label(format!(
+                "Target {:.0} km/h in {:.0} m",
+                target.speed * 3.6,
+                target.distance
+            ));
+            ui.add(egui::ProgressBar::new(
+                (target.distance / TARGET_BAR_DISTANCE).min(1.0) as f32,
+            ));
+        }
+        None => {
+            ui.label("No target");
+        }
+    }
+
+    match cab_signalling.status {
+        SupervisionStatus::Normal | SupervisionStatus::Overspeed => {}
+        SupervisionStatus::Warning => {
+            ui.colored_label(egui::Color32::from_rgb(255, 150, 0), "Warning");
+        }
+        SupervisionStatus::Intervention => {
+            ui.colored_label(egui::Color32::RED, "Brake intervention");
+        }
+    }
+}
+
+#[coverage(off)]
+fn energy_grid(ui: &mut egui::Ui, engine: &EnergyMeter, train: Option<&EnergyMeter>) {
+    let rows: [(&str, MeterReading); 5] = [
+        ("Traction", |meter| meter.traction),
+        ("Drawn", |meter| meter.drawn),
+        ("Regenerated", |meter| meter.regenerated),
+        ("Braking losses", |meter| meter.braking_losses()),
+        ("Resistance", |meter| meter.resistance),
+    ];
+
+    // only the fuels the engine or the train burn
+    let fuels: [(&str, &str, MeterReading); 2] = [
+        ("Diesel", "l", |meter| meter.diesel),
+        ("Coal", "kg", |meter| meter.coal),
+    ];
+
+    egui::Grid::new("energy").striped(true).show(
+        ui,
+        #[coverage(off)]
+        |ui| {
+            ui.label("");
+            ui.label("Engine");
+            if train.is_some() {
+                ui.label("Train");
+            }
+            ui.end_row();
+
+            for (label, value) in rows {
+                ui.label(label);
+                ui.label(format!("{:.1} kWh", value(engine)));
+                if let Some(train) = train {
+                    ui.label(format!("{:.1} kWh", value(train)));
+                }
+                ui.end_row();
+            }
+
+            for (label, unit, value) in fuels {
+                let burnt = value(engine) > 0.0
+                    || train.is_some_and(
+                        #[coverage(off)]
+                        |train| value(train) > 0.0,
+                    );
+                if !burnt {
+                    continue;
+                }
+                ui.label(label);
+                ui.label(format!("{:.1} {}", value(engine), unit));
+                if let Some(train) = train {
+                    ui.label(format!("{:.1} {}", value(train), unit));
+                }
+                ui.end_row();
+            }
+        },
+    );
+}
+
+// sounds while any engine's Sifa is in its audible warning stage
+fn sifa_buzzer(
+    mut commands: Commands,
+    sifas: Query<&Sifa>,
+    buzzers: Query<Entity, With<SifaBuzzer>>,
+    mut pitches: ResMut<Assets<Pitch>>,
+) {
+    let sounding = sifas
+        .iter()
+        .any(|sifa| sifa.stage() == SifaStage::AudibleWarning);
+
+    match (sounding, buzzers.get_single()) {
+        (true, Err(_)) => {
+            commands.spawn((
+                PitchBundle {
+                    source: pitches.add(Pitch::new(
+                        BUZZER_FREQUENCY,
+                        std::time::Duration::from_secs(1),
+                    )),
+                    settings: PlaybackSettings::LOOP,
+                },
+                SifaBuzzer,
+            ));
+        }
+        (false, Ok(buzzer)) => commands.entity(buzzer).despawn(),
+        _ => {}
+    }
+}
+
 pub struct TrainControlsPlugin;
 
 impl Plugin for TrainControlsPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Update, train_controls);
+        app.add_systems(Update, (train_controls, sifa_buzzer));
     }
 }
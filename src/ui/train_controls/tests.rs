@@ -1,4 +1,5 @@
 use super::*;
+use crate::train::StopCall;
 use coverage_helper::test;
 
 #[test]
@@ -7,3 +8,163 @@ fn plugin() {
     app.add_plugins(TrainControlsPlugin);
     assert!(app.is_plugin_added::<TrainControlsPlugin>());
 }
+
+#[test]
+fn brake_valve_keys() {
+    let mut inputs: ButtonInput<KeyCode> = ButtonInput::default();
+    let mut brake_lever = BrakeLever::default();
+
+    brake_valve_input(&inputs, &mut brake_lever);
+    assert_eq!(brake_lever.valve, BrakeValvePosition::Running);
+
+    inputs.press(KeyCode::KeyB);
+    brake_valve_input(&inputs, &mut brake_lever);
+    assert_eq!(brake_lever.valve, BrakeValvePosition::Running);
+
+    inputs.release(KeyCode::KeyB);
+    brake_valve_input(&inputs, &mut brake_lever);
+    assert_eq!(brake_lever.valve, BrakeValvePosition::Lap);
+
+    inputs.clear();
+    inputs.press(KeyCode::KeyV);
+    inputs.release(KeyCode::KeyV);
+    brake_valve_input(&inputs, &mut brake_lever);
+    assert_eq!(brake_lever.valve, BrakeValvePosition::Running);
+
+    inputs.clear();
+    inputs.press(KeyCode::Space);
+    inputs.release(KeyCode::Space);
+    brake_valve_input(&inputs, &mut brake_lever);
+    assert_eq!(brake_lever.valve, BrakeValvePosition::Emergency);
+}
+
+#[test]
+fn pzb_keys() {
+    let mut inputs: ButtonInput<KeyCode> = ButtonInput::default();
+    let mut pzb = Pzb::default();
+
+    pzb_input(&inputs, &mut pzb);
+    assert!(!pzb.acknowledge && !pzb.release);
+
+    inputs.press(KeyCode::KeyQ);
+    inputs.release(KeyCode::KeyQ);
+    pzb_input(&inputs, &mut pzb);
+    assert!(pzb.acknowledge && !pzb.release);
+
+    inputs.clear();
+    inputs.press(KeyCode::KeyR);
+    inputs.release(KeyCode::KeyR);
+    pzb_input(&inputs, &mut pzb);
+    assert!(pzb.release);
+}
+
+#[test]
+fn sifa_key() {
+    let mut inputs: ButtonInput<KeyCode> = ButtonInput::default();
+    let mut sifa = Sifa::default();
+
+    sifa_input(&inputs, &mut sifa);
+    assert!(!sifa.acknowledge);
+
+    inputs.press(KeyCode::KeyF);
+    sifa_input(&inputs, &mut sifa);
+    assert!(!sifa.acknowledge);
+
+    inputs.release(KeyCode::KeyF);
+    sifa_input(&inputs, &mut sifa);
+    assert!(sifa.acknowledge);
+}
+
+#[test]
+fn sifa_buzzer_sounds_in_the_audible_stage() {
+    let mut app = App::new();
+    app.init_resource::<Assets<Pitch>>()
+        .add_systems(Update, sifa_buzzer);
+    let engine = app.world_mut().spawn(Sifa::default()).id();
+    let mut buzzers = app.world_mut().query_filtered::<(), With<SifaBuzzer>>();
+
+    app.update();
+    assert_eq!(buzzers.iter(app.world()).count(), 0);
+
+    app.world_mut().get_mut::<Sifa>(engine).unwrap().elapsed = 35.0;
+    app.update();
+    app.update();
+    // only one buzzer however long the stage lasts
+    assert_eq!(buzzers.iter(app.world()).count(), 1);
+
+    app.world_mut().get_mut::<Sifa>(engine).unwrap().elapsed = 0.0;
+    app.update();
+    assert_eq!(buzzers.iter(app.world()).count(), 0);
+}
+
+#[test]
+fn train_load_share() {
+    let empty = Payload {
+        capacity: 20_000.0,
+        load: 0.0,
+    };
+    let full = Payload {
+        capacity: 60_000.0,
+        load: 60_000.0,
+    };
+
+    assert_eq!(load_share([&empty, &full].into_iter()), Some(0.75));
+    assert_eq!(load_share([&Payload::default()].into_iter()), None);
+    assert_eq!(load_share(std::iter::empty()), None);
+}
+
+#[test]
+fn next_stop() {
+    let stops = vec![
+        ScenarioStop {
+            name: "Start".to_string(),
+            node_id: 1,
+        },
+        ScenarioStop {
+            name: "End".to_string(),
+            node_id: 2,
+        },
+    ];
+    let mut station_stops = StationStops {
+        next: 1,
+        ..default()
+    };
+
+    assert_eq!(
+        next_stop_text(&station_stops, &stops),
+        Some("Next stop End".to_string())
+    );
+
+    station_stops.distance = Some(1234.4);
+    assert_eq!(
+        next_stop_text(&station_stops, &stops),
+        Some("Next stop End in 1234 m".to_string())
+    );
+
+    station_stops.calls.push(StopCall {
+        stop: 1,
+        arrival: 10.0,
+        departure: None,
+    });
+    assert_eq!(
+        next_stop_text(&station_stops, &stops),
+        Some("At End".to_string())
+    );
+
+    station_stops.next = 2;
+    assert_eq!(next_stop_text(&station_stops, &stops), None);
+}
+
+#[test]
+fn speed_dial() {
+    assert_eq!(dial_range(100.0), 140.0);
+    assert_eq!(dial_range(160.0), 180.0);
+    assert_eq!(dial_range(230.0), 250.0);
+    assert_eq!(dial_range(500.0), 400.0);
+
+    // symmetric around straight up
+    assert_eq!(dial_angle(70.0, 140.0), 0.0);
+    assert!((dial_angle(0.0, 140.0) + dial_angle(140.0, 140.0)).abs() < 1e-6);
+    assert!((dial_angle(140.0, 140.0) - 144.0_f32.to_radians()).abs() < 1e-6);
+    assert_eq!(dial_angle(200.0, 140.0), dial_angle(140.0, 140.0));
+}
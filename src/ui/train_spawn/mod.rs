@@ -9,11 +9,18 @@ use bevy::prelude::*;
 use bevy_egui::{egui, EguiContexts};
 
 #[coverage(off)]
-fn spawn(mut commands: Commands, mut contexts: EguiContexts, mut id: Local<i64>) {
+fn spawn(
+    mut commands: Commands,
+    mut contexts: EguiContexts,
+    mut id: Local<i64>,
+    mut load: Local<f32>,
+) {
     egui::Window::new("Debug: Spawn train").show(
         contexts.ctx_mut(),
         #[coverage(off)]
         |ui| {
+            ui.add(egui::Slider::new(&mut *load, 0.0..=1.0).text("Wagon load"));
+
             if ui.small_button("BR 111 (single engine)").clicked() {
                 *id += 1;
                 let engine = commands
@@ -39,7 +46,9 @@ fn spawn(mut commands: Commands, mut contexts: EguiContexts, mut id: Local<i64>)
                 for _ in 0..25 {
                     components.push(TrainComponent::Wagon(
                         commands
-                            .spawn(WagonBundle::from_file("assets/models/eanos.toml"))
+                            .spawn(
+                                WagonBundle::from_file("assets/models/eanos.toml").with_load(*load),
+                            )
                             .id(),
                     ));
                 }
@@ -65,7 +74,9 @@ fn spawn(mut commands: Commands, mut contexts: EguiContexts, mut id: Local<i64>)
                 for _ in 0..25 {
                     components.push(TrainComponent::Wagon(
                         commands
-                            .spawn(WagonBundle::from_file("assets/models/eanos.toml"))
+                            .spawn(
+                                WagonBundle::from_file("assets/models/eanos.toml").with_load(*load),
+                            )
                             .id(),
                     ));
                 }
@@ -85,7 +96,10 @@ fn spawn(mut commands: Commands, mut contexts: EguiContexts, mut id: Local<i64>)
                 for _ in 0..3 {
                     components.push(TrainComponent::Wagon(
                         commands
-                            .spawn(WagonBundle::from_file("assets/models/nwagen.toml"))
+                            .spawn(
+                                WagonBundle::from_file("assets/models/nwagen.toml")
+                                    .with_load(*load),
+                            )
                             .id(),
                     ));
                 }
@@ -105,7 +119,10 @@ fn spawn(mut commands: Commands, mut contexts: EguiContexts, mut id: Local<i64>)
                 for _ in 0..3 {
                     components.push(TrainComponent::Wagon(
                         commands
-                            .spawn(WagonBundle::from_file("assets/models/nwagen.toml"))
+                            .spawn(
+                                WagonBundle::from_file("assets/models/nwagen.toml")
+                                    .with_load(*load),
+                            )
                             .id(),
                     ));
                 }
@@ -125,7 +142,10 @@ fn spawn(mut commands: Commands, mut contexts: EguiContexts, mut id: Local<i64>)
                 for _ in 0..3 {
                     components.push(TrainComponent::Wagon(
                         commands
-                            .spawn(WagonBundle::from_file("assets/models/nwagen.toml"))
+                            .spawn(
+                                WagonBundle::from_file("assets/models/nwagen.toml")
+                                    .with_load(*load),
+                            )
                             .id(),
                     ));
                 }
@@ -141,7 +161,9 @@ fn spawn(mut commands: Commands, mut contexts: EguiContexts, mut id: Local<i64>)
                     .id();
 
                 let tender = commands
-                    .spawn(WagonBundle::from_file("assets/models/BR52_tender.toml"))
+                    .spawn(
+                        WagonBundle::from_file("assets/models/BR52_tender.toml").with_load(*load),
+                    )
                     .id();
 
                 let mut components = vec![
@@ -152,7 +174,10 @@ fn spawn(mut commands: Commands, mut contexts: EguiContexts, mut id: Local<i64>)
                 for _ in 0..3 {
                     components.push(TrainComponent::Wagon(
                         commands
-                            .spawn(WagonBundle::from_file("assets/models/nwagen.toml"))
+                            .spawn(
+                                WagonBundle::from_file("assets/models/nwagen.toml")
+                                    .with_load(*load),
+                            )
                             .id(),
                     ));
                 }
@@ -175,7 +200,10 @@ fn spawn(mut commands: Commands, mut contexts: EguiContexts, mut id: Local<i64>)
                 for _ in 0..3 {
                     components.push(TrainComponent::Wagon(
                         commands
-                            .spawn(WagonBundle::from_file("assets/models/nwagen.toml"))
+                            .spawn(
+                                WagonBundle::from_file("assets/models/nwagen.toml")
+                                    .with_load(*load),
+                            )
                             .id(),
                     ));
                 }
@@ -198,7 +226,10 @@ fn spawn(mut commands: Commands, mut contexts: EguiContexts, mut id: Local<i64>)
                 for _ in 0..3 {
                     components.push(TrainComponent::Wagon(
                         commands
-                            .spawn(WagonBundle::from_file("assets/models/nwagen.toml"))
+                            .spawn(
+                                WagonBundle::from_file("assets/models/nwagen.toml")
+                                    .with_load(*load),
+                            )
                             .id(),
                     ));
                 }
@@ -221,7 +252,10 @@ fn spawn(mut commands: Commands, mut contexts: EguiContexts, mut id: Local<i64>)
                 for _ in 0..3 {
                     components.push(TrainComponent::Wagon(
                         commands
-                            .spawn(WagonBundle::from_file("assets/models/nwagen.toml"))
+                            .spawn(
+                                WagonBundle::from_file("assets/models/nwagen.toml")
+                                    .with_load(*load),
+                            )
                             .id(),
                     ));
                 }
@@ -237,7 +271,7 @@ fn spawn(mut commands: Commands, mut contexts: EguiContexts, mut id: Local<i64>)
                     .id();
 
                 let control_car = commands
-                    .spawn(WagonBundle::from_file("assets/models/VS98.toml"))
+                    .spawn(WagonBundle::from_file("assets/models/VS98.toml").with_load(*load))
                     .id();
 
                 let components = vec![